@@ -0,0 +1,44 @@
+//! Render a chord progression with a bass line to WAV, without any MIDI devices.
+//! Run with: cargo run --example render_wav --features midi
+
+use rust_music_theory::harmony::Progression;
+use rust_music_theory::midi::{
+    Channel, Duration, Envelope, Instrument, MidiBuilder, MidiFile, SampleFormat, Synth, Velocity,
+    Waveform,
+};
+use rust_music_theory::note::{Note, Pitch, PitchSymbol::*};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let progression = Progression::parse("| Am F | C G | Am F | C E7 |")?;
+
+    let mut chords = MidiBuilder::new();
+    chords.add_progression(&progression, Velocity::new(80).unwrap());
+
+    let mut bass = MidiBuilder::new();
+    for root in [A, F, C, G, A, F, C, E] {
+        let note = Note::new(Pitch::from(root), 2);
+        bass.add(&note, Duration::Quarter, Velocity::new(110).unwrap())
+            .add(&note, Duration::Quarter, Velocity::new(70).unwrap());
+    }
+
+    let file = MidiFile::new()
+        .tempo(100)
+        .track(chords, Channel::new(0).unwrap())
+        .track(bass, Channel::new(1).unwrap());
+
+    let pad = Instrument::new(Waveform::Triangle)
+        .envelope(Envelope::new(0.08, 0.3, 0.6, 0.6));
+    let bass_synth = Instrument::new(Waveform::Saw)
+        .envelope(Envelope::new(0.005, 0.15, 0.4, 0.1))
+        .gain(0.5);
+
+    let mut audio = Synth::new()
+        .instrument(pad)
+        .channel(Channel::new(1).unwrap(), bass_synth)
+        .render_file(&file);
+    audio.normalize(0.9);
+
+    audio.save_wav("render_wav.wav", SampleFormat::Int16)?;
+    println!("Wrote render_wav.wav ({:.1} seconds)", audio.duration());
+    Ok(())
+}
@@ -1,8 +1,44 @@
 use clap::{App, Arg, ArgMatches};
 use rust_music_theory::chord::{Chord, SUPPORTED_CHORD_SYNTAX};
-use rust_music_theory::note::Notes;
+use rust_music_theory::fretboard::{Fretboard, Tuning};
+use rust_music_theory::interval::Interval;
+use rust_music_theory::note::{Note, Notes};
 use rust_music_theory::scale::{Direction, Mode, Scale};
 
+/// The widest stretch of frets a printed fingering may use.
+const FINGERING_SPAN: u8 = 4;
+
+/// The tunings `--tuning` accepts for chord diagrams and tablature.
+const TUNINGS: [&str; 6] = [
+    "standard",
+    "drop-d",
+    "dadgad",
+    "bass",
+    "ukulele",
+    "seven-string",
+];
+
+/// The fretboard for the `--tuning` option, with 12 frets on a ukulele and 22 otherwise.
+fn fretboard(matches: &ArgMatches) -> Fretboard {
+    match matches.value_of("tuning").unwrap_or("standard") {
+        "drop-d" => Fretboard::new(Tuning::drop_d(), 22),
+        "dadgad" => Fretboard::new(Tuning::dadgad(), 22),
+        "bass" => Fretboard::new(Tuning::bass(), 22),
+        "ukulele" => Fretboard::new(Tuning::ukulele(), 12),
+        "seven-string" => Fretboard::new(Tuning::seven_string(), 22),
+        _ => Fretboard::new(Tuning::standard(), 22),
+    }
+}
+
+fn tuning_arg() -> Arg<'static, 'static> {
+    Arg::with_name("tuning")
+        .help("the instrument tuning for --diagram and --tab")
+        .long("tuning")
+        .takes_value(true)
+        .possible_values(&TUNINGS)
+        .default_value("standard")
+}
+
 const STANDALONE_SCALES: [&str; 5] = [
     "Pentatonic Major",
     "Pentatonic Minor",
@@ -36,7 +72,13 @@ fn scale_command(scale_matches: &ArgMatches) -> Result<(), String> {
 
             let scale = Scale::from_regex_in_direction(&scale_args, direction)
                 .map_err(|error| error.to_string())?;
-            scale.print_notes();
+            if scale_matches.is_present("tab") {
+                fretboard(scale_matches)
+                    .print_tab(&scale)
+                    .map_err(|error| error.to_string())?;
+            } else {
+                scale.print_notes();
+            }
             Ok(())
         }
     }
@@ -68,12 +110,55 @@ fn chord_command(chord_matches: &ArgMatches) -> Result<(), String> {
         _ => {
             let chord_args = joined_args(chord_matches)?;
             let chord = Chord::parse(&chord_args).map_err(|error| error.to_string())?;
-            chord.print_notes();
+            if chord_matches.is_present("diagram") {
+                let fingering = fretboard(chord_matches)
+                    .chord_fingerings(&chord, FINGERING_SPAN)
+                    .into_iter()
+                    .next()
+                    .ok_or_else(|| format!("no fingering for {}", chord))?;
+                println!("{}: {}", chord, fingering);
+                println!("{}", fingering.diagram());
+            } else {
+                chord.print_notes();
+            }
             Ok(())
         }
     }
 }
 
+fn interval_command(interval_matches: &ArgMatches) -> Result<(), String> {
+    let expression = interval_matches
+        .values_of("args")
+        .map(|values| values.collect::<Vec<_>>().join(" "))
+        .filter(|value| !value.trim().is_empty())
+        .ok_or_else(|| "missing interval expression".to_string())?;
+
+    let tokens = expression.split_whitespace().collect::<Vec<_>>();
+    let operator = tokens
+        .iter()
+        .position(|token| matches!(*token, "+" | "-"))
+        .ok_or_else(|| "expected an expression such as Eb4 + m7".to_string())?;
+    let note = tokens[..operator]
+        .join(" ")
+        .parse::<Note>()
+        .map_err(|error| error.to_string())?;
+    let interval = tokens[operator + 1..]
+        .join(" ")
+        .parse::<Interval>()
+        .map_err(|error| error.to_string())?;
+
+    let second_note = if tokens[operator] == "+" {
+        interval.second_note_from(note.clone())
+    } else {
+        interval.second_note_down_from(note.clone())
+    };
+    println!("Notes:");
+    for (i, note) in [note, second_note].iter().enumerate() {
+        println!("  {}: {}{}", i + 1, note.pitch, note.octave);
+    }
+    Ok(())
+}
+
 fn main() {
     let matches = App::new("RustMusicTheory")
         .version(env!("CARGO_PKG_VERSION"))
@@ -93,7 +178,14 @@ fn main() {
                         .help("list scale in descending order")
                         .short("d")
                         .long("descending"),
-                ),
+                )
+                .arg(
+                    Arg::with_name("tab")
+                        .help("print the scale as tablature")
+                        .short("t")
+                        .long("tab"),
+                )
+                .arg(tuning_arg()),
         )
         .subcommand(
             App::new("chord")
@@ -113,6 +205,22 @@ fn main() {
                     Arg::with_name("args")
                         .help("chord args, examples:\nC minor\nAb augmented major seventh\nF# dominant seventh / C#\nC/1")
                         .multiple(true),
+                )
+                .arg(
+                    Arg::with_name("diagram")
+                        .help("print the easiest fingering as a chord diagram")
+                        .long("diagram"),
+                )
+                .arg(tuning_arg()),
+        )
+        .subcommand(
+            App::new("interval")
+                .about("Prints the notes of a note moved up or down by an interval")
+                .arg(
+                    Arg::with_name("args")
+                        .help("interval expression, examples:\nEb4 + m7\nC5 - perfect fifth\nD3 + M9")
+                        .multiple(true)
+                        .allow_hyphen_values(true),
                 ),
         )
         .get_matches();
@@ -122,6 +230,8 @@ fn main() {
 
         ("chord", Some(chord_matches)) => chord_command(chord_matches),
 
+        ("interval", Some(interval_matches)) => interval_command(interval_matches),
+
         _ => {
             println!("Please use the help command to see the available commands");
             Ok(())
@@ -2,19 +2,23 @@
 
 mod chord;
 mod errors;
+mod identify;
 mod number;
 mod parser;
 mod quality;
 mod spec;
+mod voicing;
 
 pub use chord::{Chord, ChordBuilder};
 pub use errors::ChordError;
+pub use identify::ChordCandidate;
 pub use number::Number;
 pub use quality::Quality;
 pub use spec::{
     ChordExtension, ChordFormula, ChordModifier, ChordSpec, ChordTone, SeventhQuality, Suspension,
     TriadQuality,
 };
+pub use voicing::Voicing;
 
 /// Human-readable syntax registry used by documentation and command-line discovery.
 pub const SUPPORTED_CHORD_SYNTAX: &[&str] = &[
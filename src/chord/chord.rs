@@ -6,7 +6,8 @@ use crate::chord::{
     SeventhQuality, Suspension, TriadQuality,
 };
 use crate::interval::Interval;
-use crate::note::{Note, NoteLetter, Notes, Pitch};
+use crate::note::{Note, NoteLetter, Notes, Pitch, Spelling};
+use crate::scale::Direction;
 use std::fmt;
 use std::str::FromStr;
 
@@ -238,6 +239,44 @@ impl Chord {
         formula_intervals(&spec.formula()?)
     }
 
+    /// Move the chord by a spelled interval, keeping its specification, inversion and slash bass.
+    pub fn transpose(&self, interval: Interval, direction: Direction) -> Self {
+        let root = Note::new(self.root, self.octave).transpose(interval, direction);
+        self.with_root(root.pitch, root.octave)
+    }
+
+    /// Move the chord by a signed number of semitones, spelling the new root with `spelling`.
+    ///
+    /// Use a key signature spelling to choose between enharmonic roots such as F# and Gb.
+    pub fn transpose_semitones(&self, semitones: i16, spelling: &Spelling) -> Self {
+        let root = Note::new(self.root, self.octave).transpose_semitones(semitones, spelling);
+        self.with_root(root.pitch, root.octave)
+    }
+
+    /// Move the chord to a new written root in the same octave.
+    pub fn transpose_to(&self, root: Pitch) -> Self {
+        self.with_root(root, self.octave)
+    }
+
+    fn with_root(&self, root: Pitch, octave: i16) -> Self {
+        // A slash bass keeps its letter distance from the root, so C/E becomes Eb/G rather than
+        // Eb/F##.
+        let slash_bass = self.slash_bass.map(|bass| {
+            let letter_steps = bass.letter.index() - self.root.letter.index();
+            let semitones = bass.into_u8() as i16 - self.root.into_u8() as i16;
+            Pitch::from_u8_with_letter(
+                (root.into_u8() as i16 + semitones).rem_euclid(12) as u8,
+                root.letter.offset(letter_steps),
+            )
+        });
+        Self {
+            root,
+            octave,
+            slash_bass,
+            ..self.clone()
+        }
+    }
+
     /// Deprecated compatibility name for [`Chord::parse`].
     #[deprecated(since = "0.5.0", note = "use Chord::parse or str::parse")]
     pub fn from_regex(string: &str) -> Result<Self, ChordError> {
@@ -253,7 +292,7 @@ impl Chord {
         Ok(())
     }
 
-    fn set_bass(&mut self, bass: Pitch) -> Result<(), ChordError> {
+    pub(crate) fn set_bass(&mut self, bass: Pitch) -> Result<(), ChordError> {
         if let Some(index) = self
             .root_position_notes()
             .iter()
@@ -1,3 +1,4 @@
+use crate::chord::Voicing;
 use crate::note::NoteError;
 use std::error;
 use std::fmt;
@@ -21,6 +22,8 @@ pub enum ChordError {
     UnknownIntervalPattern(Vec<u8>),
     UnsupportedChord(String),
     InvalidInversion(u8),
+    UnsupportedVoicing { voicing: Voicing, message: String },
+    VoicingOutOfRange(Voicing),
 }
 
 impl fmt::Display for ChordError {
@@ -80,6 +83,12 @@ impl fmt::Display for ChordError {
             ChordError::InvalidInversion(inversion) => {
                 write!(f, "Invalid chord inversion: {}", inversion)
             }
+            ChordError::UnsupportedVoicing { voicing, message } => {
+                write!(f, "Unsupported {} voicing: chord {}", voicing, message)
+            }
+            ChordError::VoicingOutOfRange(voicing) => {
+                write!(f, "No {} voicing fits in the range", voicing)
+            }
         }
     }
 }
@@ -0,0 +1,312 @@
+use crate::chord::parser::parse_chord;
+use crate::chord::{Chord, ChordExtension, ChordModifier, ChordSpec, ChordTone};
+use crate::note::key_finding::conventional_key;
+use crate::note::{Note, Notes, Pitch};
+use crate::scale::Direction;
+use lazy_static::lazy_static;
+use std::cmp::Ordering;
+
+/// Chord descriptors tried on every candidate root, from simplest to most complex.
+const DESCRIPTORS: &[&str] = &[
+    "",
+    "m",
+    "dim",
+    "aug",
+    "5",
+    "sus2",
+    "sus4",
+    "6",
+    "m6",
+    "6/9",
+    "m6/9",
+    "7",
+    "maj7",
+    "m7",
+    "mMaj7",
+    "m7b5",
+    "dim7",
+    "aug7",
+    "augMaj7",
+    "7sus4",
+    "add9",
+    "madd9",
+    "add11",
+    "9",
+    "maj9",
+    "m9",
+    "mMaj9",
+    "9sus4",
+    "11",
+    "maj11",
+    "m11",
+    "13",
+    "maj13",
+    "m13",
+    "7b5",
+    "7b9",
+    "7#9",
+    "7#11",
+    "7b13",
+    "maj7#11",
+    "9#11",
+    "13#11",
+    "7(b9,#11)",
+    "7(b9,b13)",
+    "7alt",
+];
+
+lazy_static! {
+    static ref CATALOGUE: Vec<(ChordSpec, u32)> = {
+        let mut catalogue = Vec::new();
+        for (complexity, descriptor) in DESCRIPTORS.iter().enumerate() {
+            let spec = parse_chord(&format!("C{}", descriptor)).unwrap().spec;
+            catalogue.extend(with_omissions(spec, complexity as u32));
+        }
+        catalogue
+    };
+}
+
+/// A chord that may explain a set of notes.
+#[derive(Debug, Clone)]
+pub struct ChordCandidate {
+    /// The candidate chord, with the lowest given note as its inversion or slash bass.
+    pub chord: Chord,
+    /// Given notes that are neither chord tones nor the slash bass.
+    pub unexplained: Vec<Note>,
+    /// Chord tones that were not given.
+    pub missing: Vec<Pitch>,
+    score: u32,
+}
+
+impl ChordCandidate {
+    /// Whether the chord accounts for every given note and omits none of its tones.
+    pub fn is_exact(&self) -> bool {
+        self.unexplained.is_empty() && self.missing.is_empty()
+    }
+}
+
+impl Chord {
+    /// Rank the chords that could explain an unordered set of notes.
+    ///
+    /// Every candidate is rooted on one of the given notes, keeps the given spelling, and uses
+    /// the lowest note as its bass. Candidates may leave notes unexplained or miss chord tones,
+    /// and are ranked so that exact matches in root position with simple symbols come first.
+    pub fn from_notes(notes: &[Note]) -> Vec<ChordCandidate> {
+        identify(notes, false)
+    }
+
+    /// Rank the chords that could explain a set of MIDI note numbers, as [`Chord::from_notes`]
+    /// does, spelling each candidate root with the fewest accidentals.
+    pub fn from_midi_pitches(pitches: &[u8]) -> Vec<ChordCandidate> {
+        let notes: Vec<Note> = pitches
+            .iter()
+            .map(|&pitch| Note::new(Pitch::from_u8(pitch % 12), pitch as i16 / 12 - 1))
+            .collect();
+        identify(&notes, true)
+    }
+}
+
+fn identify(notes: &[Note], respell: bool) -> Vec<ChordCandidate> {
+    let bass = match notes.iter().min_by_key(|note| note_absolute(note)) {
+        Some(bass) => bass,
+        None => return Vec::new(),
+    };
+    let given = pitch_class_set(notes.iter().map(|note| note.pitch));
+
+    let mut candidates = Vec::new();
+    let mut roots: Vec<Pitch> = Vec::new();
+    for note in notes {
+        if !roots
+            .iter()
+            .any(|root| root.into_u8() == note.pitch.into_u8())
+        {
+            roots.push(note.pitch);
+        }
+    }
+    for root in roots {
+        for (spec, complexity) in CATALOGUE.iter() {
+            let root = if respell {
+                best_root_spelling(root, spec)
+            } else {
+                root
+            };
+            if let Some(candidate) = candidate(root, spec, *complexity, notes, bass, given, respell)
+            {
+                candidates.push(candidate);
+            }
+        }
+    }
+    candidates.sort_by_key(|candidate| candidate.score);
+    candidates
+}
+
+fn candidate(
+    root: Pitch,
+    spec: &ChordSpec,
+    complexity: u32,
+    notes: &[Note],
+    bass: &Note,
+    given: u16,
+    respell: bool,
+) -> Option<ChordCandidate> {
+    let mut chord = Chord::from_spec(root, spec.clone()).ok()?;
+    let tones = spec.formula().ok()?;
+    let chord_set = tones
+        .tones()
+        .iter()
+        .fold(0u16, |set, tone| set | pitch_class_bit(root, *tone));
+
+    let missing_tones: Vec<ChordTone> = tones
+        .tones()
+        .iter()
+        .copied()
+        .filter(|tone| pitch_class_bit(root, *tone) & given == 0)
+        .collect();
+    if missing_tones.len() > 2 || tones.tones().len() - missing_tones.len() < 2 {
+        return None;
+    }
+
+    let prefer_flats = respell && prefers_flats(&chord);
+    let respelled = |pitch: Pitch| {
+        if prefer_flats {
+            Pitch::from_u8_with_direction(pitch.into_u8(), Direction::Descending)
+        } else {
+            pitch
+        }
+    };
+    let bass_class = bass.pitch.into_u8();
+    if bass_class != root.into_u8() {
+        chord.set_bass(respelled(bass.pitch)).ok()?;
+    }
+    let slash = chord_set & (1 << bass_class) == 0;
+    // The chord's octave is that of its lowest voiced chord tone; a slash bass sits below it.
+    let lowest_tone = notes
+        .iter()
+        .filter(|note| chord_set & (1 << note.pitch.into_u8()) != 0)
+        .min_by_key(|note| note_absolute(note))?;
+    chord = chord.with_octave(lowest_tone.octave);
+    let unexplained: Vec<Note> = notes
+        .iter()
+        .filter(|note| {
+            let class = note.pitch.into_u8();
+            chord_set & (1 << class) == 0 && !(slash && class == bass_class)
+        })
+        .map(|note| Note::new(respelled(note.pitch), note.octave))
+        .collect();
+    if unexplained.len() > 2 {
+        return None;
+    }
+
+    let root_notes = chord.notes();
+    let missing = root_notes
+        .iter()
+        .map(|note| note.pitch)
+        .filter(|pitch| {
+            missing_tones
+                .iter()
+                .any(|tone| pitch_class_bit(root, *tone) == 1 << pitch.into_u8())
+        })
+        .collect();
+    // A foreign bass or an inversion outweighs any difference in symbol complexity, so D F# A C E
+    // reads as D9 rather than Am6/D.
+    let bass_cost = if slash {
+        60
+    } else if chord.inversion() > 0 {
+        30
+    } else {
+        0
+    };
+    let score =
+        unexplained.len() as u32 * 100 + missing_tones.len() as u32 * 80 + bass_cost + complexity;
+    Some(ChordCandidate {
+        chord,
+        unexplained,
+        missing,
+        score,
+    })
+}
+
+/// The chord with the omissions common in practice: the unaltered fifth of chords with at least
+/// four tones, and the eleventh of thirteenth chords. Each omission adds to the complexity.
+fn with_omissions(spec: ChordSpec, complexity: u32) -> Vec<(ChordSpec, u32)> {
+    let mut variants = vec![(spec, complexity)];
+    if variants[0].0.extension() == ChordExtension::Thirteenth {
+        if let Some(without_eleventh) = omit(&variants[0].0, 11) {
+            variants.push((without_eleventh, complexity + 1));
+        }
+    }
+    for index in 0..variants.len() {
+        let (spec, complexity) = variants[index].clone();
+        let tones = match spec.formula() {
+            Ok(formula) => formula.tones().to_vec(),
+            Err(_) => continue,
+        };
+        let has_fifth = tones
+            .iter()
+            .any(|tone| tone.degree() == 5 && tone.alteration() == 0);
+        if has_fifth && tones.len() >= 4 {
+            if let Some(without_fifth) = omit(&spec, 5) {
+                variants.push((without_fifth, complexity + 1));
+            }
+        }
+    }
+    variants
+}
+
+fn omit(spec: &ChordSpec, degree: u8) -> Option<ChordSpec> {
+    let mut modifiers = spec.modifiers().to_vec();
+    modifiers.push(ChordModifier::Omit(degree));
+    ChordSpec::from_parts(
+        spec.triad_quality(),
+        spec.seventh_quality(),
+        spec.extension(),
+        spec.suspension(),
+        modifiers,
+    )
+    .ok()
+}
+
+/// Whether the chord is spelled with more flats than sharps.
+fn prefers_flats(chord: &Chord) -> bool {
+    let balance: i32 = chord
+        .notes()
+        .iter()
+        .map(|note| note.pitch.accidental.signum() as i32)
+        .sum();
+    balance < 0
+}
+
+/// Spell the root with sharps or flats, whichever gives the chord fewer accidentals, and as the
+/// tonic of the conventional major key when both are equal.
+fn best_root_spelling(root: Pitch, spec: &ChordSpec) -> Pitch {
+    let sharp = Pitch::from_u8(root.into_u8());
+    let flat = Pitch::from_u8_with_direction(root.into_u8(), Direction::Descending);
+    let accidentals = |root: Pitch| -> i32 {
+        Chord::from_spec(root, spec.clone())
+            .map(|chord| {
+                chord
+                    .notes()
+                    .iter()
+                    .map(|note| (note.pitch.accidental as i32).abs())
+                    .sum()
+            })
+            .unwrap_or(i32::MAX)
+    };
+    match accidentals(flat).cmp(&accidentals(sharp)) {
+        Ordering::Less => flat,
+        Ordering::Greater => sharp,
+        Ordering::Equal => conventional_key(root.into_u8(), false).tonic,
+    }
+}
+
+fn pitch_class_bit(root: Pitch, tone: ChordTone) -> u16 {
+    1 << (root.into_u8() as i16 + tone.semitones()).rem_euclid(12)
+}
+
+fn pitch_class_set(pitches: impl Iterator<Item = Pitch>) -> u16 {
+    pitches.fold(0, |set, pitch| set | (1 << pitch.into_u8()))
+}
+
+fn note_absolute(note: &Note) -> i32 {
+    note.octave as i32 * 12 + note.pitch.semitones_from_c() as i32
+}
@@ -1,4 +1,5 @@
 use crate::chord::ChordError;
+use crate::interval::{Interval, Number};
 
 /// The quality of the chord's base triad before extensions and modifiers.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -91,6 +92,21 @@ impl ChordTone {
         (self.degree as i16 - 1) % 7
     }
 
+    /// The spelled interval above the root, e.g. a minor ninth for a flat ninth.
+    ///
+    /// # Errors
+    ///
+    /// Fails for a lowered root, which lies below the chord root.
+    pub fn interval(self) -> Result<Interval, ChordError> {
+        let steps = self.degree - 1;
+        let number = Number::from_steps(steps % 7).expect("validated chord degree");
+        number
+            .quality_from_deviation(self.alteration as i16)
+            .and_then(|quality| Interval::from_number_and_quality(number, quality).ok())
+            .and_then(|interval| interval.compound(steps / 7).ok())
+            .ok_or(ChordError::InvalidAlteration(self.alteration))
+    }
+
     pub(crate) fn accidental_prefix(self) -> String {
         let accidental = if self.alteration < 0 { 'b' } else { '#' };
         (0..self.alteration.unsigned_abs())
@@ -0,0 +1,444 @@
+use crate::chord::{Chord, ChordError, ChordTone};
+use crate::note::{Note, Pitch};
+use std::fmt;
+
+/// A way of spreading a chord's tones across the keyboard or a section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Voicing {
+    /// Every chord tone within an octave, from the bass up.
+    Close,
+    /// The close voicing with every other tone raised an octave, as in 1-5-10.
+    Open,
+    /// A four-tone close voicing with the second tone from the top dropped an octave.
+    Drop2,
+    /// A four-tone close voicing with the third tone from the top dropped an octave.
+    Drop3,
+    /// A four-tone close voicing with the second and fourth tones from the top dropped an octave.
+    Drop24,
+    /// The root under the third and seventh.
+    Shell,
+    /// The rootless piano voicing built up from the third: 3-5-7-9, or 3-13-7-9 on a dominant.
+    RootlessA,
+    /// The rootless piano voicing built up from the seventh: 7-9-3-5, or 7-9-3-13 on a dominant.
+    RootlessB,
+    /// The root under the fifth and the third an octave up, as in 1-5-10.
+    SpreadTriad,
+    /// Four tones a perfect fourth apart, including the third and no avoid notes.
+    Quartal,
+    /// Three perfect fourths topped by a major third, as in the voicings of Bill Evans on "So What".
+    SoWhat,
+}
+
+/// Four tones at most are kept in drop voicings.
+const DROP_TONES: usize = 4;
+
+impl fmt::Display for Voicing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Voicing::Close => "close",
+            Voicing::Open => "open",
+            Voicing::Drop2 => "drop-2",
+            Voicing::Drop3 => "drop-3",
+            Voicing::Drop24 => "drop-2&4",
+            Voicing::Shell => "shell",
+            Voicing::RootlessA => "rootless A",
+            Voicing::RootlessB => "rootless B",
+            Voicing::SpreadTriad => "spread triad",
+            Voicing::Quartal => "quartal",
+            Voicing::SoWhat => "So What",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A tone of a voicing as semitones above the root in the chord's octave, with its spelling.
+type Shape = Vec<(i16, Pitch)>;
+
+impl Chord {
+    /// Voice the chord with its lowest tone in the octave from the chord's root.
+    ///
+    /// Voicings other than close place a named bass below the voiced chord. Close, open and drop
+    /// voicings keep the chord's inversion, and rootless, shell and spread voicings use the chord's
+    /// own ninth, fifth and thirteenth when it alters them.
+    ///
+    /// ```
+    /// use rust_music_theory::chord::{Chord, Voicing};
+    ///
+    /// let chord = Chord::parse("Cmaj7").unwrap();
+    /// let notes: Vec<String> = chord
+    ///     .voicing(Voicing::Drop2)
+    ///     .unwrap()
+    ///     .iter()
+    ///     .map(|note| format!("{}{}", note.pitch, note.octave))
+    ///     .collect();
+    /// assert_eq!(notes, ["G4", "C5", "E5", "B5"]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`ChordError::UnsupportedVoicing`] when the chord lacks the tones the voicing
+    /// needs, such as a seventh for a shell or four tones for a drop-3 voicing.
+    pub fn voicing(&self, voicing: Voicing) -> Result<Vec<Note>, ChordError> {
+        let shapes = self.voicing_shapes(voicing, false)?;
+        let shape = shapes
+            .first()
+            .ok_or(ChordError::VoicingOutOfRange(voicing))?;
+        let lowest = shape.iter().map(|&(offset, _)| offset).min().unwrap_or(0);
+        Ok(self.place(shape, -lowest.div_euclid(12) * 12))
+    }
+
+    /// Voice the chord with its top tone as close as possible to a target note.
+    ///
+    /// Close, open and drop voicings of a chord without a named bass may take any inversion to
+    /// reach the target, and shell, spread, quartal and So What voicings may choose their upper
+    /// tones.
+    pub fn voicing_with_top(&self, voicing: Voicing, top: &Note) -> Result<Vec<Note>, ChordError> {
+        let target = top.midi_pitch() as i16 - self.root_midi();
+        let (shape, shift) = self
+            .voicing_shapes(voicing, true)?
+            .into_iter()
+            .map(|shape| {
+                let highest = shape.iter().map(|&(offset, _)| offset).max().unwrap_or(0);
+                let shift = ((target - highest) as f64 / 12.0).round() as i16 * 12;
+                (shape, shift)
+            })
+            .min_by_key(|(shape, shift)| {
+                let highest = shape.iter().map(|&(offset, _)| offset).max().unwrap_or(0);
+                (highest + shift - target).abs()
+            })
+            .ok_or(ChordError::VoicingOutOfRange(voicing))?;
+        Ok(self.place(&shape, shift))
+    }
+
+    /// Voice the chord within a range of notes, inclusive, as near its middle as possible.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`ChordError::VoicingOutOfRange`] when no placement of the voicing fits.
+    pub fn voicing_in_range(
+        &self,
+        voicing: Voicing,
+        lowest: &Note,
+        highest: &Note,
+    ) -> Result<Vec<Note>, ChordError> {
+        let root = self.root_midi();
+        let low = lowest.midi_pitch() as i16 - root;
+        let high = highest.midi_pitch() as i16 - root;
+        let mut best: Option<(i16, Shape, i16)> = None;
+        for shape in self.voicing_shapes(voicing, true)? {
+            let bottom = shape.iter().map(|&(offset, _)| offset).min().unwrap_or(0);
+            let top = shape.iter().map(|&(offset, _)| offset).max().unwrap_or(0);
+            let mut shift = (low - bottom).div_euclid(12) * 12;
+            if bottom + shift < low {
+                shift += 12;
+            }
+            while top + shift <= high {
+                let distance = (bottom + top + 2 * shift - low - high).abs();
+                let better = match &best {
+                    Some((best, ..)) => distance < *best,
+                    None => true,
+                };
+                if better {
+                    best = Some((distance, shape.clone(), shift));
+                }
+                shift += 12;
+            }
+        }
+        let (_, shape, shift) = best.ok_or(ChordError::VoicingOutOfRange(voicing))?;
+        Ok(self.place(&shape, shift))
+    }
+
+    fn root_midi(&self) -> i16 {
+        (self.octave() + 1) * 12 + self.root().semitones_from_c()
+    }
+
+    fn place(&self, shape: &[(i16, Pitch)], shift: i16) -> Vec<Note> {
+        let root = self.root_midi();
+        shape
+            .iter()
+            .map(|&(offset, pitch)| Note::from_midi_with_pitch(pitch, root + offset + shift))
+            .collect()
+    }
+
+    /// The shapes a voicing can take from the bass up, the default first. Only the default is
+    /// built unless `choices` is set.
+    fn voicing_shapes(&self, voicing: Voicing, choices: bool) -> Result<Vec<Shape>, ChordError> {
+        let unsupported = |message: &str| ChordError::UnsupportedVoicing {
+            voicing,
+            message: message.to_string(),
+        };
+        let tones: Vec<ChordTone> = self.formula().tones().to_vec();
+        // An inversion puts a chord tone in the bass; a slash bass is never a chord tone.
+        let bass_tone = match self.inversion() {
+            0 => None,
+            inversion => tones.get(inversion as usize).copied(),
+        };
+        let mut keeps_bass = false;
+
+        let mut shapes: Vec<Shape> = match voicing {
+            Voicing::Close | Voicing::Open => {
+                keeps_bass = bass_tone.is_some();
+                let rotations = self.close_rotations(&tones, bass_tone, choices);
+                if voicing == Voicing::Close {
+                    rotations
+                } else {
+                    rotations.into_iter().map(|shape| open(&shape)).collect()
+                }
+            }
+            Voicing::Drop2 | Voicing::Drop3 | Voicing::Drop24 => {
+                let kept = keep_priority(&tones, DROP_TONES);
+                let needed = if voicing == Voicing::Drop2 { 3 } else { 4 };
+                if kept.len() < needed {
+                    return Err(unsupported("needs four chord tones"));
+                }
+                let dropped: &[usize] = match voicing {
+                    Voicing::Drop2 => &[2],
+                    Voicing::Drop3 => &[3],
+                    _ => &[2, 4],
+                };
+                // Try every inversion when the bass must come out of the drop.
+                keeps_bass = matches!(bass_tone, Some(bass) if kept.contains(&bass));
+                let bass = bass_tone.map(|bass| self.spell(bass));
+                self.close_rotations(&kept, None, choices || keeps_bass)
+                    .into_iter()
+                    .map(|shape| drop_tones(&shape, dropped))
+                    .filter(|shape| !keeps_bass || shape.first().map(|&(_, pitch)| pitch) == bass)
+                    .collect()
+            }
+            Voicing::Shell => {
+                let third = third(&tones).ok_or_else(|| unsupported("needs a third"))?;
+                let seventh = seventh(&tones).ok_or_else(|| unsupported("needs a seventh"))?;
+                let root = self.tone(ChordTone::new(1, 0)?);
+                let mut shapes = vec![stack(&[root, self.tone(third), self.tone(seventh)])];
+                if choices {
+                    shapes.push(stack(&[root, self.tone(seventh), self.tone(third)]));
+                }
+                shapes
+            }
+            Voicing::RootlessA | Voicing::RootlessB => {
+                let third = third(&tones).ok_or_else(|| unsupported("needs a third"))?;
+                let seventh = seventh(&tones).ok_or_else(|| unsupported("needs a seventh"))?;
+                let ninth = find(&tones, 9).unwrap_or(ChordTone::new(9, 0)?);
+                let dominant = third == ChordTone::new(3, 0)? && seventh == ChordTone::new(7, -1)?;
+                let fifth = find(&tones, 5).unwrap_or(ChordTone::new(5, 0)?);
+                let colour = if dominant && fifth.alteration() == 0 {
+                    find(&tones, 13).unwrap_or(ChordTone::new(13, 0)?)
+                } else {
+                    fifth
+                };
+                let order = if voicing == Voicing::RootlessA {
+                    [third, colour, seventh, ninth]
+                } else {
+                    [seventh, ninth, third, colour]
+                };
+                let tones: Vec<(i16, Pitch)> = order.iter().map(|&tone| self.tone(tone)).collect();
+                vec![stack(&tones)]
+            }
+            Voicing::SpreadTriad => {
+                let third = third(&tones).ok_or_else(|| unsupported("needs a third"))?;
+                let fifth = find(&tones, 5).ok_or_else(|| unsupported("needs a fifth"))?;
+                let root = self.tone(ChordTone::new(1, 0)?);
+                let (third, fifth) = (self.tone(third), self.tone(fifth));
+                let mut shapes = vec![spread(&[root, fifth, third])];
+                if choices {
+                    shapes.push(spread(&[root, third, fifth]));
+                }
+                shapes
+            }
+            Voicing::Quartal | Voicing::SoWhat => {
+                let steps: &[i16] = if voicing == Voicing::Quartal {
+                    &[5, 5, 5]
+                } else {
+                    &[5, 5, 5, 4]
+                };
+                let shapes = self.fourths(&tones, steps);
+                if shapes.is_empty() {
+                    return Err(unsupported("has no stack of fourths without avoid notes"));
+                }
+                shapes
+            }
+        };
+        if !choices {
+            shapes.truncate(1);
+        }
+
+        if let (Some(bass), false) = (self.bass(), keeps_bass) {
+            let class = (bass.into_u8() as i16 - self.root().into_u8() as i16).rem_euclid(12);
+            for shape in &mut shapes {
+                let lowest = shape.iter().map(|&(offset, _)| offset).min().unwrap_or(0);
+                let offset = lowest - 1 - (lowest - 1 - class).rem_euclid(12);
+                shape.insert(0, (offset, bass));
+            }
+        }
+        Ok(shapes)
+    }
+
+    /// Close voicings from the bass up, in every inversion when `choices` is set and the chord
+    /// names no bass, or starting from the bass tone.
+    fn close_rotations(
+        &self,
+        tones: &[ChordTone],
+        bass_tone: Option<ChordTone>,
+        choices: bool,
+    ) -> Vec<Shape> {
+        let mut ordered: Vec<(i16, Pitch)> = tones.iter().map(|&tone| self.tone(tone)).collect();
+        ordered
+            .iter_mut()
+            .for_each(|tone| tone.0 = tone.0.rem_euclid(12));
+        ordered.sort_by_key(|&(offset, _)| offset);
+        ordered.dedup_by_key(|tone| tone.0);
+        let start = bass_tone
+            .map(|tone| tone.semitones().rem_euclid(12))
+            .and_then(|class| ordered.iter().position(|&(offset, _)| offset == class));
+        let rotations: Vec<usize> = match start {
+            Some(start) => vec![start],
+            None if choices => (0..ordered.len()).collect(),
+            None => vec![0],
+        };
+        rotations
+            .into_iter()
+            .map(|rotation| {
+                let mut rotated = ordered.clone();
+                rotated.rotate_left(rotation);
+                stack(&rotated)
+            })
+            .collect()
+    }
+
+    /// Stacks of intervals on each chord tone that hold the third and no tone a semitone above a
+    /// chord tone.
+    fn fourths(&self, tones: &[ChordTone], steps: &[i16]) -> Vec<Shape> {
+        let classes: Vec<i16> = tones
+            .iter()
+            .map(|&tone| tone.semitones().rem_euclid(12))
+            .collect();
+        let third = match third(tones) {
+            Some(third) => third.semitones().rem_euclid(12),
+            None => return Vec::new(),
+        };
+        let mut shapes = Vec::new();
+        for &tone in tones {
+            let (mut offset, mut pitch) = self.tone(tone);
+            offset = offset.rem_euclid(12);
+            let mut shape = vec![(offset, pitch)];
+            for &step in steps {
+                offset += step;
+                let letter_steps = if step == 5 { 3 } else { 2 };
+                pitch = Pitch::from_u8_with_letter(
+                    (pitch.into_u8() as i16 + step).rem_euclid(12) as u8,
+                    pitch.letter.offset(letter_steps),
+                );
+                shape.push((offset, pitch));
+            }
+            let avoided = shape.iter().any(|&(offset, _)| {
+                let class = offset.rem_euclid(12);
+                !classes.contains(&class) && classes.contains(&(class - 1).rem_euclid(12))
+            });
+            let holds_third = shape
+                .iter()
+                .any(|&(offset, _)| offset.rem_euclid(12) == third);
+            if holds_third && !avoided {
+                shapes.push(shape);
+            }
+        }
+        shapes
+    }
+
+    /// A chord tone as semitones above the root, spelled from the root.
+    fn tone(&self, tone: ChordTone) -> (i16, Pitch) {
+        (tone.semitones(), self.spell(tone))
+    }
+
+    fn spell(&self, tone: ChordTone) -> Pitch {
+        let root = self.root();
+        Pitch::from_u8_with_letter(
+            (root.into_u8() as i16 + tone.semitones()).rem_euclid(12) as u8,
+            root.letter.offset(tone.letter_offset()),
+        )
+    }
+}
+
+fn find(tones: &[ChordTone], degree: u8) -> Option<ChordTone> {
+    tones.iter().copied().find(|tone| tone.degree() == degree)
+}
+
+/// The third, or the suspended tone replacing it.
+fn third(tones: &[ChordTone]) -> Option<ChordTone> {
+    find(tones, 3)
+        .or_else(|| find(tones, 4))
+        .or_else(|| find(tones, 2))
+}
+
+/// The seventh, or the sixth of a sixth chord.
+fn seventh(tones: &[ChordTone]) -> Option<ChordTone> {
+    find(tones, 7).or_else(|| find(tones, 6))
+}
+
+/// The most important tones, in formula order: the third and seventh first, then extensions and
+/// altered fifths, then the root and the fifth.
+fn keep_priority(tones: &[ChordTone], count: usize) -> Vec<ChordTone> {
+    let rank = |tone: &ChordTone| match (tone.degree(), tone.alteration()) {
+        (2, _) | (3, _) | (4, _) => 1,
+        (6, _) | (7, _) => 2,
+        (1, _) => 4,
+        (5, 0) => 5,
+        _ => 3,
+    };
+    let mut ranked: Vec<(usize, ChordTone)> = tones.iter().copied().enumerate().collect();
+    ranked.sort_by_key(|(index, tone)| (rank(tone), *index));
+    ranked.truncate(count);
+    ranked.sort_by_key(|&(index, _)| index);
+    ranked.into_iter().map(|(_, tone)| tone).collect()
+}
+
+/// Stack tones upward in order, each above the one before.
+fn stack(tones: &[(i16, Pitch)]) -> Shape {
+    let mut shape: Shape = Vec::with_capacity(tones.len());
+    for &(offset, pitch) in tones {
+        let mut offset = offset.rem_euclid(12);
+        if let Some(&(below, _)) = shape.last() {
+            while offset <= below {
+                offset += 12;
+            }
+        }
+        shape.push((offset, pitch));
+    }
+    shape
+}
+
+/// Stack tones upward in order, raising the upper tones an octave if they would stay within an
+/// octave of the bass.
+fn spread(tones: &[(i16, Pitch)]) -> Shape {
+    let mut shape = stack(tones);
+    let span = shape.last().map_or(0, |&(offset, _)| offset) - shape[0].0;
+    if span <= 12 {
+        for tone in shape.iter_mut().skip(1) {
+            tone.0 += 12;
+        }
+    }
+    shape
+}
+
+/// Raise every other tone above the bass an octave.
+fn open(shape: &[(i16, Pitch)]) -> Shape {
+    let mut opened: Shape = shape
+        .iter()
+        .enumerate()
+        .map(|(index, &(offset, pitch))| (offset + 12 * (index % 2) as i16, pitch))
+        .collect();
+    opened.sort_by_key(|&(offset, _)| offset);
+    opened
+}
+
+/// Drop the tones at the given positions counted from the top, starting at 1, an octave.
+fn drop_tones(shape: &[(i16, Pitch)], positions: &[usize]) -> Shape {
+    let mut dropped: Shape = shape.to_vec();
+    let length = dropped.len();
+    for &position in positions {
+        if position <= length {
+            dropped[length - position].0 -= 12;
+        }
+    }
+    dropped.sort_by_key(|&(offset, _)| offset);
+    dropped
+}
@@ -0,0 +1,26 @@
+//! Fretted instruments: tunings, note positions, chord fingerings, scale boxes, chord diagrams and
+//! tablature.
+//!
+//! ```
+//! use rust_music_theory::chord::Chord;
+//! use rust_music_theory::fretboard::{Fretboard, Tuning};
+//!
+//! let guitar = Fretboard::new(Tuning::standard(), 22);
+//! let fingerings = guitar.chord_fingerings(&Chord::parse("C").unwrap(), 4);
+//! let frets: Vec<Option<u8>> = fingerings[0].frets.clone();
+//! assert_eq!(frets, [None, Some(3), Some(2), Some(0), Some(1), Some(0)]);
+//! ```
+
+mod diagram;
+mod errors;
+mod fingering;
+mod fretboard;
+mod scale_box;
+mod tab;
+mod tuning;
+
+pub use errors::FretboardError;
+pub use fingering::{Barre, Fingering};
+pub use fretboard::{FretPosition, Fretboard, FrettedNote};
+pub use scale_box::{CagedShape, ScaleBox, ScalePattern};
+pub use tuning::Tuning;
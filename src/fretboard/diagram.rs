@@ -0,0 +1,97 @@
+use crate::fretboard::Fingering;
+use std::fmt;
+
+/// The fewest fret rows a chord box shows.
+const ROWS: u8 = 4;
+
+impl Fingering {
+    /// The shape as one line from the lowest string, such as `x32010`.
+    ///
+    /// Frets above 9 would run together, so shapes that use them separate the strings with
+    /// dashes, as in `x-10-12-12-12-10`.
+    pub fn compact(&self) -> String {
+        let wide = self
+            .frets
+            .iter()
+            .any(|fret| matches!(fret, Some(fret) if *fret > 9));
+        let frets: Vec<String> = self
+            .frets
+            .iter()
+            .map(|fret| fret.map_or("x".to_string(), |fret| fret.to_string()))
+            .collect();
+        frets.join(if wide { "-" } else { "" })
+    }
+
+    /// The shape as a chord box, with the lowest string on the left.
+    ///
+    /// The first line marks muted strings with `x` and open strings with `o`. The nut is drawn as
+    /// `=` when the box starts at the first fret; otherwise the first row is labelled with its
+    /// fret. Each row is a fret, with `o` where a finger stops a string and a barre drawn across
+    /// the strings it covers.
+    ///
+    /// ```text
+    /// x     o   o
+    /// ===========
+    /// | | | | o |
+    /// | | o | | |
+    /// | o | | | |
+    /// | | | | | |
+    /// ```
+    pub fn diagram(&self) -> String {
+        let fretted: Vec<u8> = self
+            .frets
+            .iter()
+            .filter_map(|&fret| fret.filter(|&fret| fret > 0))
+            .collect();
+        let low = fretted.iter().copied().min().unwrap_or(1);
+        let high = fretted.iter().copied().max().unwrap_or(1);
+        let first = if high <= ROWS { 1 } else { low };
+        let last = high.max(first + ROWS - 1);
+
+        let header = self
+            .frets
+            .iter()
+            .map(|fret| match fret {
+                None => "x",
+                Some(0) => "o",
+                Some(_) => " ",
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let width = self.frets.len() * 2 - 1;
+        let mut lines = vec![header.trim_end().to_string()];
+        lines.push(if first == 1 {
+            "=".repeat(width)
+        } else {
+            "-".repeat(width)
+        });
+        for fret in first..=last {
+            let mut row = String::with_capacity(width);
+            for (string, played) in self.frets.iter().enumerate() {
+                let barred = self.barre.filter(|barre| {
+                    barre.fret == fret && (barre.first..=barre.last).contains(&string)
+                });
+                if string > 0 {
+                    let joined = matches!(barred, Some(barre) if string > barre.first);
+                    row.push(if joined { '-' } else { ' ' });
+                }
+                row.push(if barred.is_some() || *played == Some(fret) {
+                    'o'
+                } else {
+                    '|'
+                });
+            }
+            if fret == first && first > 1 {
+                row.push_str(&format!(" {}fr", first));
+            }
+            lines.push(row);
+        }
+        lines.join("\n")
+    }
+}
+
+impl fmt::Display for Fingering {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.compact())
+    }
+}
@@ -0,0 +1,34 @@
+use crate::note::Note;
+use crate::scale::ScaleType;
+use std::error;
+use std::fmt;
+
+/// An error while building a tuning or laying out notes on a fretboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FretboardError {
+    EmptyTuning,
+    TooFewStrings { strings: usize, needed: usize },
+    NotHeptatonic(ScaleType),
+    NoteOutOfRange(Note),
+}
+
+impl fmt::Display for FretboardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FretboardError::EmptyTuning => write!(f, "Tuning has no strings"),
+            FretboardError::TooFewStrings { strings, needed } => write!(
+                f,
+                "Tuning has {} strings, the pattern needs {}",
+                strings, needed
+            ),
+            FretboardError::NotHeptatonic(scale_type) => {
+                write!(f, "Scale type {} does not have seven degrees", scale_type)
+            }
+            FretboardError::NoteOutOfRange(note) => {
+                write!(f, "{}{} is not on the fretboard", note.pitch, note.octave)
+            }
+        }
+    }
+}
+
+impl error::Error for FretboardError {}
@@ -0,0 +1,241 @@
+use crate::chord::{Chord, ChordTone};
+use crate::fretboard::{FretPosition, Fretboard};
+use crate::note::{Note, Notes, Pitch};
+use std::collections::HashSet;
+
+/// The most fingers a fretting hand can use.
+const FINGERS: usize = 4;
+
+/// One finger stopping several adjacent strings at the same fret.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Barre {
+    /// The fret held down.
+    pub fret: u8,
+    /// The first string under the finger, in tuning order.
+    pub first: usize,
+    /// The last string under the finger, in tuning order.
+    pub last: usize,
+}
+
+/// A playable chord shape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fingering {
+    /// The fret played on each string in tuning order, or `None` for a muted string.
+    pub frets: Vec<Option<u8>>,
+    /// The barre the shape needs, if any.
+    pub barre: Option<Barre>,
+    /// A score that grows with the fingers used, the stretch, a barre, muted strings and the
+    /// distance from the nut.
+    pub difficulty: u32,
+    notes: Vec<Note>,
+}
+
+impl Fingering {
+    /// The positions of the sounding strings.
+    pub fn positions(&self) -> Vec<FretPosition> {
+        self.frets
+            .iter()
+            .enumerate()
+            .filter_map(|(string, fret)| fret.map(|fret| FretPosition { string, fret }))
+            .collect()
+    }
+}
+
+impl Notes for Fingering {
+    /// The sounding notes in string order, spelled as the chord spells them.
+    fn notes(&self) -> Vec<Note> {
+        self.notes.clone()
+    }
+}
+
+impl Fretboard {
+    /// Every playable shape of a chord within a span of frets, easiest first.
+    ///
+    /// A shape sounds the chord's bass on its lowest note, at least three strings, and every chord
+    /// tone except an unaltered fifth in chords of four or more tones and an unaltered ninth or
+    /// eleventh in chords of six or more. It may use open strings and needs at most four fingers,
+    /// one of which may bar the lowest fret across adjacent strings.
+    pub fn chord_fingerings(&self, chord: &Chord, span: u8) -> Vec<Fingering> {
+        let root = chord.root();
+        let formula = chord.formula().tones();
+        let mut spellings: Vec<Pitch> = formula.iter().map(|&tone| spell(root, tone)).collect();
+        let bass = chord.bass().unwrap_or(root);
+        if !spellings
+            .iter()
+            .any(|pitch| pitch.into_u8() == bass.into_u8())
+        {
+            spellings.push(bass);
+        }
+        let optional = |tone: &ChordTone| {
+            tone.alteration() == 0
+                && match tone.degree() {
+                    5 => formula.len() >= 4,
+                    9 | 11 => formula.len() >= 6,
+                    _ => false,
+                }
+        };
+        let mut required: Vec<u8> = formula
+            .iter()
+            .filter(|tone| !optional(tone))
+            .map(|&tone| spell(root, tone).into_u8())
+            .collect();
+        required.push(bass.into_u8());
+
+        let strings = self.tuning().len();
+        let span = span.clamp(1, self.frets().max(1));
+        let mut seen = HashSet::new();
+        let mut fingerings = Vec::new();
+        for base in 1..=self.frets().saturating_sub(span - 1).max(1) {
+            let options: Vec<Vec<Option<u8>>> = (0..strings)
+                .map(|string| {
+                    let mut options = vec![None];
+                    let frets = std::iter::once(0).chain((base..=self.frets()).take(span as usize));
+                    for fret in frets.filter(|&fret| fret <= self.frets()) {
+                        let position = FretPosition { string, fret };
+                        let midi = self.midi_at(position).unwrap_or(0);
+                        if spellings
+                            .iter()
+                            .any(|pitch| pitch.into_u8() as i16 == midi.rem_euclid(12))
+                        {
+                            options.push(Some(fret));
+                        }
+                    }
+                    options
+                })
+                .collect();
+            let mut frets = Vec::with_capacity(strings);
+            self.search(&options, &mut frets, &mut |frets| {
+                if seen.insert(frets.to_vec()) {
+                    if let Some(fingering) = self.fingering(frets, &spellings, &required, bass) {
+                        fingerings.push(fingering);
+                    }
+                }
+            });
+        }
+        fingerings.sort_by(|a, b| {
+            a.difficulty
+                .cmp(&b.difficulty)
+                .then_with(|| a.frets.cmp(&b.frets))
+        });
+        fingerings
+    }
+
+    fn search(
+        &self,
+        options: &[Vec<Option<u8>>],
+        frets: &mut Vec<Option<u8>>,
+        visit: &mut dyn FnMut(&[Option<u8>]),
+    ) {
+        if frets.len() == options.len() {
+            visit(frets);
+            return;
+        }
+        for &option in &options[frets.len()] {
+            frets.push(option);
+            self.search(options, frets, visit);
+            frets.pop();
+        }
+    }
+
+    fn fingering(
+        &self,
+        frets: &[Option<u8>],
+        spellings: &[Pitch],
+        required: &[u8],
+        bass: Pitch,
+    ) -> Option<Fingering> {
+        let sounding: Vec<(usize, u8, i16)> = frets
+            .iter()
+            .enumerate()
+            .filter_map(|(string, fret)| {
+                let fret = (*fret)?;
+                Some((string, fret, self.midi_at(FretPosition { string, fret })?))
+            })
+            .collect();
+        if sounding.len() < 3.min(frets.len()) {
+            return None;
+        }
+        let lowest = sounding.iter().map(|&(.., midi)| midi).min()?;
+        if lowest.rem_euclid(12) != bass.into_u8() as i16 {
+            return None;
+        }
+        let classes: HashSet<i16> = sounding
+            .iter()
+            .map(|&(.., midi)| midi.rem_euclid(12))
+            .collect();
+        if !required
+            .iter()
+            .all(|&class| classes.contains(&(class as i16)))
+        {
+            return None;
+        }
+
+        let fretted: Vec<(usize, u8)> = sounding
+            .iter()
+            .filter(|&&(_, fret, _)| fret > 0)
+            .map(|&(string, fret, _)| (string, fret))
+            .collect();
+        let low = fretted.iter().map(|&(_, fret)| fret).min().unwrap_or(0);
+        let high = fretted.iter().map(|&(_, fret)| fret).max().unwrap_or(0);
+        let mut barre = None;
+        let mut fingers = fretted.len();
+        if fingers > FINGERS {
+            let first = fretted.iter().find(|&&(_, fret)| fret == low)?.0;
+            let last = fretted.iter().rev().find(|&&(_, fret)| fret == low)?.0;
+            let covered = frets[first..=last]
+                .iter()
+                .all(|fret| matches!(fret, Some(fret) if *fret >= low));
+            if !covered {
+                return None;
+            }
+            fingers = 1 + fretted.iter().filter(|&&(_, fret)| fret > low).count();
+            if fingers > FINGERS {
+                return None;
+            }
+            barre = Some(Barre {
+                fret: low,
+                first,
+                last,
+            });
+        }
+
+        let first_sounding = sounding[0].0;
+        let last_sounding = sounding[sounding.len() - 1].0;
+        let muted = frets.len() - sounding.len();
+        let inner_muted = frets[first_sounding..=last_sounding]
+            .iter()
+            .filter(|fret| fret.is_none())
+            .count();
+        let difficulty = fingers as u32 * 2
+            + (high - low) as u32 * 2
+            + if barre.is_some() { 3 } else { 0 }
+            + inner_muted as u32 * 4
+            + muted as u32 * 4
+            + high as u32 / 2;
+
+        let notes = sounding
+            .iter()
+            .filter_map(|&(string, fret, midi)| {
+                let pitch = spellings
+                    .iter()
+                    .copied()
+                    .find(|pitch| pitch.into_u8() as i16 == midi.rem_euclid(12))?;
+                self.spelled_note_at(FretPosition { string, fret }, pitch)
+            })
+            .collect();
+        Some(Fingering {
+            frets: frets.to_vec(),
+            barre,
+            difficulty,
+            notes,
+        })
+    }
+}
+
+/// A chord tone spelled from the root.
+fn spell(root: Pitch, tone: ChordTone) -> Pitch {
+    Pitch::from_u8_with_letter(
+        (root.into_u8() as i16 + tone.semitones()).rem_euclid(12) as u8,
+        root.letter.offset(tone.letter_offset()),
+    )
+}
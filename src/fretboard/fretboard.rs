@@ -0,0 +1,106 @@
+use crate::fretboard::Tuning;
+use crate::note::{Note, Pitch};
+
+/// A place on the neck: a string, counted from zero in tuning order, and a fret, zero when open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FretPosition {
+    /// The string, as an index into the tuning.
+    pub string: usize,
+    /// The fret, or 0 for the open string.
+    pub fret: u8,
+}
+
+/// A note played at a position on the neck.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrettedNote {
+    /// Where the note is played.
+    pub position: FretPosition,
+    /// The note sounded.
+    pub note: Note,
+}
+
+/// A fretted instrument's neck: its tuning and how many frets it has.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fretboard {
+    tuning: Tuning,
+    frets: u8,
+}
+
+impl Fretboard {
+    /// A neck with a tuning and a number of frets.
+    pub fn new(tuning: Tuning, frets: u8) -> Self {
+        Fretboard { tuning, frets }
+    }
+
+    /// The tuning of the strings.
+    pub fn tuning(&self) -> &Tuning {
+        &self.tuning
+    }
+
+    /// The highest fret.
+    pub fn frets(&self) -> u8 {
+        self.frets
+    }
+
+    /// Every position that sounds a note, from the lowest string.
+    pub fn positions(&self, note: &Note) -> Vec<FretPosition> {
+        let midi = note.midi_pitch() as i16;
+        (0..self.tuning.len())
+            .filter_map(|string| {
+                let fret = midi - self.open_midi(string);
+                if (0..=self.frets as i16).contains(&fret) {
+                    Some(FretPosition {
+                        string,
+                        fret: fret as u8,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Every position that sounds a pitch in any octave, from the lowest string and fret.
+    pub fn pitch_positions(&self, pitch: Pitch) -> Vec<FretPosition> {
+        let class = pitch.into_u8() as i16;
+        (0..self.tuning.len())
+            .flat_map(|string| {
+                let first = (class - self.open_midi(string)).rem_euclid(12);
+                (first..=self.frets as i16)
+                    .step_by(12)
+                    .map(move |fret| FretPosition {
+                        string,
+                        fret: fret as u8,
+                    })
+            })
+            .collect()
+    }
+
+    /// The note sounded at a position, spelled with sharps, if the position is on the neck.
+    pub fn note_at(&self, position: FretPosition) -> Option<Note> {
+        self.midi_at(position)
+            .map(|midi| Note::from_midi_with_pitch(Pitch::from_u8(midi as u8), midi))
+    }
+
+    /// The note sounded at a position, spelled as `pitch` when it names the same pitch class.
+    pub(crate) fn spelled_note_at(&self, position: FretPosition, pitch: Pitch) -> Option<Note> {
+        let midi = self.midi_at(position)?;
+        if midi.rem_euclid(12) == pitch.into_u8() as i16 {
+            Some(Note::from_midi_with_pitch(pitch, midi))
+        } else {
+            self.note_at(position)
+        }
+    }
+
+    pub(crate) fn midi_at(&self, position: FretPosition) -> Option<i16> {
+        if position.string < self.tuning.len() && position.fret <= self.frets {
+            Some(self.open_midi(position.string) + position.fret as i16)
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn open_midi(&self, string: usize) -> i16 {
+        self.tuning.strings()[string].midi_pitch() as i16
+    }
+}
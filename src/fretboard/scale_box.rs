@@ -0,0 +1,241 @@
+use crate::fretboard::{FretPosition, Fretboard, FretboardError, FrettedNote};
+use crate::note::{Note, Notes, Pitch};
+use crate::scale::Scale;
+use std::fmt;
+
+/// The five chord shapes of the CAGED system, named after the open chord each one moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CagedShape {
+    C,
+    A,
+    G,
+    E,
+    D,
+}
+
+/// How a scale box is laid out on the neck.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScalePattern {
+    /// The scale around one of the CAGED chord shapes.
+    Caged(CagedShape),
+    /// Three notes on every string, starting on a scale degree counted from 1.
+    ThreeNotesPerString(u8),
+}
+
+/// A scale played in one position, from the lowest note up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScaleBox {
+    /// The layout of the box.
+    pub pattern: ScalePattern,
+    /// The notes with their positions, from the lowest string.
+    pub notes: Vec<FrettedNote>,
+}
+
+impl CagedShape {
+    /// Every shape in the order they follow each other up the neck from the C shape.
+    pub fn iterator() -> impl Iterator<Item = CagedShape> {
+        [
+            CagedShape::C,
+            CagedShape::A,
+            CagedShape::G,
+            CagedShape::E,
+            CagedShape::D,
+        ]
+        .iter()
+        .copied()
+    }
+
+    /// The string the shape's root lies on and how far the shape reaches below the root's fret.
+    fn root(self) -> (usize, i16) {
+        match self {
+            CagedShape::C => (1, 3),
+            CagedShape::A => (1, 0),
+            CagedShape::G => (0, 3),
+            CagedShape::E => (0, 0),
+            CagedShape::D => (2, 0),
+        }
+    }
+}
+
+impl fmt::Display for CagedShape {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} shape", self)
+    }
+}
+
+impl ScaleBox {
+    /// The positions of the notes, from the lowest string.
+    pub fn positions(&self) -> Vec<FretPosition> {
+        self.notes.iter().map(|fretted| fretted.position).collect()
+    }
+}
+
+impl Notes for ScaleBox {
+    fn notes(&self) -> Vec<Note> {
+        self.notes
+            .iter()
+            .map(|fretted| fretted.note.clone())
+            .collect()
+    }
+}
+
+impl Fretboard {
+    /// The scale around each CAGED chord shape of its tonic, lowest on the neck first.
+    ///
+    /// Each box takes the frets from one below the shape to three above its lowest fret, placing
+    /// every note on the lowest string that reaches it.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the tuning has fewer than three strings.
+    pub fn caged_boxes(&self, scale: &Scale) -> Result<Vec<ScaleBox>, FretboardError> {
+        self.require_strings(3)?;
+        let pitches = scale_pitches(scale);
+        let tonic = scale.tonic.into_u8() as i16;
+        let mut boxes: Vec<(u8, ScaleBox)> = CagedShape::iterator()
+            .filter_map(|shape| {
+                let (string, below) = shape.root();
+                let mut root = (tonic - self.open_midi(string)).rem_euclid(12);
+                if root < below {
+                    root += 12;
+                }
+                let lowest = root - below;
+                let first = (lowest - 1).max(0) as u8;
+                let last = (lowest + 3).min(self.frets() as i16);
+                if last < first as i16 {
+                    return None;
+                }
+                let mut notes = Vec::new();
+                let mut highest = i16::MIN;
+                for string in 0..self.tuning().len() {
+                    for fret in first..=last as u8 {
+                        let position = FretPosition { string, fret };
+                        let midi = self.open_midi(string) + fret as i16;
+                        if midi <= highest {
+                            continue;
+                        }
+                        if let Some(note) = self.scale_note_at(position, &pitches) {
+                            highest = midi;
+                            notes.push(FrettedNote { position, note });
+                        }
+                    }
+                }
+                let pattern = ScalePattern::Caged(shape);
+                Some((first, ScaleBox { pattern, notes }))
+            })
+            .collect();
+        boxes.sort_by_key(|&(first, _)| first);
+        Ok(boxes.into_iter().map(|(_, scale_box)| scale_box).collect())
+    }
+
+    /// The scale with three notes on every string, one pattern for each starting degree.
+    ///
+    /// Each pattern starts at the lowest fret of its degree on the lowest string, moving up an
+    /// octave when a later string would need a fret below the nut. Patterns that run past the
+    /// last fret are left out.
+    ///
+    /// # Errors
+    ///
+    /// Fails unless the scale has seven notes.
+    pub fn three_notes_per_string(&self, scale: &Scale) -> Result<Vec<ScaleBox>, FretboardError> {
+        let pitches = scale_pitches(scale);
+        if pitches.len() != 7 {
+            return Err(FretboardError::NotHeptatonic(scale.scale_type));
+        }
+        self.require_strings(1)?;
+        let strings = self.tuning().len();
+        let mut boxes = Vec::new();
+        for degree in 0..pitches.len() {
+            let class = pitches[degree].into_u8() as i16;
+            let mut start = self.open_midi(0) + (class - self.open_midi(0)).rem_euclid(12);
+            let positions = loop {
+                let positions = three_per_string(&pitches, degree, start, strings, |string| {
+                    self.open_midi(string)
+                });
+                if positions.iter().all(|&(_, fret, _)| fret >= 0) {
+                    break positions;
+                }
+                start += 12;
+            };
+            if positions
+                .iter()
+                .any(|&(_, fret, _)| fret > self.frets() as i16)
+            {
+                continue;
+            }
+            let notes = positions
+                .into_iter()
+                .filter_map(|(string, fret, pitch)| {
+                    let position = FretPosition {
+                        string,
+                        fret: fret as u8,
+                    };
+                    let note = self.spelled_note_at(position, pitch)?;
+                    Some(FrettedNote { position, note })
+                })
+                .collect();
+            boxes.push(ScaleBox {
+                pattern: ScalePattern::ThreeNotesPerString(degree as u8 + 1),
+                notes,
+            });
+        }
+        Ok(boxes)
+    }
+
+    fn require_strings(&self, needed: usize) -> Result<(), FretboardError> {
+        let strings = self.tuning().len();
+        if strings < needed {
+            Err(FretboardError::TooFewStrings { strings, needed })
+        } else {
+            Ok(())
+        }
+    }
+
+    fn scale_note_at(&self, position: FretPosition, pitches: &[Pitch]) -> Option<Note> {
+        let class = self.midi_at(position)?.rem_euclid(12);
+        let pitch = pitches
+            .iter()
+            .copied()
+            .find(|pitch| pitch.into_u8() as i16 == class)?;
+        self.spelled_note_at(position, pitch)
+    }
+}
+
+/// The scale's pitches from the tonic, each pitch class once.
+fn scale_pitches(scale: &Scale) -> Vec<Pitch> {
+    let mut pitches: Vec<Pitch> = Vec::new();
+    for note in scale.notes() {
+        if !pitches
+            .iter()
+            .any(|pitch| pitch.into_u8() == note.pitch.into_u8())
+        {
+            pitches.push(note.pitch);
+        }
+    }
+    pitches
+}
+
+/// Three successive scale notes on every string from a starting degree and MIDI pitch, as
+/// strings, possibly negative frets, and spellings.
+fn three_per_string(
+    pitches: &[Pitch],
+    degree: usize,
+    start: i16,
+    strings: usize,
+    open: impl Fn(usize) -> i16,
+) -> Vec<(usize, i16, Pitch)> {
+    let mut positions = Vec::with_capacity(strings * 3);
+    let mut midi = start;
+    let mut index = degree;
+    for string in 0..strings {
+        for _ in 0..3 {
+            positions.push((string, midi - open(string), pitches[index]));
+            let next = (index + 1) % pitches.len();
+            let step =
+                (pitches[next].into_u8() as i16 - pitches[index].into_u8() as i16).rem_euclid(12);
+            midi += step;
+            index = next;
+        }
+    }
+    positions
+}
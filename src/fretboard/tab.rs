@@ -0,0 +1,158 @@
+use crate::fretboard::{FretPosition, Fretboard, FretboardError};
+use crate::note::{Note, Notes};
+
+impl Fretboard {
+    /// Where to play each note of a melody, keeping the hand in as few positions as possible.
+    ///
+    /// The hand covers four frets; open strings can be played from anywhere. Each note is played
+    /// inside the hand's position where it can be, on the string nearest the previous note, and
+    /// the hand only moves when a note lies outside it. The first note and hand position are
+    /// chosen so the hand moves as little as possible, low on the neck.
+    ///
+    /// # Errors
+    ///
+    /// Fails when a note cannot be played on any string.
+    pub fn tab_positions(&self, notes: &[Note]) -> Result<Vec<FretPosition>, FretboardError> {
+        let candidates = notes
+            .iter()
+            .map(|note| {
+                let positions = self.positions(note);
+                if positions.is_empty() {
+                    Err(FretboardError::NoteOutOfRange(note.clone()))
+                } else {
+                    Ok(positions)
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let first = match candidates.first() {
+            Some(first) => first,
+            None => return Ok(Vec::new()),
+        };
+        let best = first
+            .iter()
+            .flat_map(|&start| {
+                let lowest = start.fret.saturating_sub(3).max(1);
+                (lowest..=start.fret.max(1)).map(move |hand| (start, hand))
+            })
+            .map(|(start, hand)| {
+                let (moved, positions) = place(&candidates, start, hand);
+                (
+                    (moved, start.fret, start.fret.saturating_sub(hand)),
+                    positions,
+                )
+            })
+            .min_by_key(|(cost, _)| *cost)
+            .map(|(_, positions)| positions)
+            .unwrap_or_default();
+        Ok(best)
+    }
+
+    /// A melody as tablature, with the highest string on top.
+    ///
+    /// ```text
+    /// E|-----------------|
+    /// B|-----------------|
+    /// G|-----------2-4-5-|
+    /// D|-----2-3-5-------|
+    /// A|-3-5-------------|
+    /// E|-----------------|
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails when a note cannot be played on any string.
+    pub fn tab(&self, notes: &[Note]) -> Result<String, FretboardError> {
+        Ok(self.render_tab(&self.tab_positions(notes)?))
+    }
+
+    /// Print a sequence of notes as tablature.
+    ///
+    /// See [`Fretboard::tab`] for how the notes are placed on the strings.
+    pub fn print_tab<N: Notes>(&self, notes: &N) -> Result<(), FretboardError> {
+        println!("{}", self.tab(&notes.notes())?);
+        Ok(())
+    }
+
+    /// Positions played one after another as tablature, with the highest string on top.
+    pub fn render_tab(&self, positions: &[FretPosition]) -> String {
+        let labels: Vec<String> = self
+            .tuning()
+            .strings()
+            .iter()
+            .map(|note| note.pitch.to_string())
+            .collect();
+        let label_width = labels.iter().map(String::len).max().unwrap_or(0);
+        let cell = positions
+            .iter()
+            .map(|position| position.fret.to_string().len())
+            .max()
+            .unwrap_or(1);
+        labels
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(string, label)| {
+                let cells: Vec<String> = positions
+                    .iter()
+                    .map(|position| {
+                        let fret = if position.string == string {
+                            position.fret.to_string()
+                        } else {
+                            String::new()
+                        };
+                        format!("{:-<width$}", fret, width = cell)
+                    })
+                    .collect();
+                format!(
+                    "{:<width$}|-{}-|",
+                    label,
+                    cells.join("-"),
+                    width = label_width
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// The positions chosen from a starting position and the lowest fret the hand covers, with how
+/// many frets the hand moved.
+fn place(
+    candidates: &[Vec<FretPosition>],
+    start: FretPosition,
+    mut hand: u8,
+) -> (u32, Vec<FretPosition>) {
+    let mut moved = 0;
+    let mut previous = start;
+    let mut positions = vec![start];
+    for options in &candidates[1..] {
+        let outside = |position: &FretPosition| {
+            if position.fret == 0 || position.fret >= hand && position.fret <= hand + 3 {
+                0
+            } else if position.fret < hand {
+                hand - position.fret
+            } else {
+                position.fret - hand - 3
+            }
+        };
+        let next = *options
+            .iter()
+            .min_by_key(|position| {
+                let strings = (position.string as i64 - previous.string as i64).abs();
+                (outside(position), strings, position.fret)
+            })
+            .unwrap_or(&previous);
+        let shift = outside(&next);
+        if shift > 0 {
+            moved += shift as u32;
+            hand = if next.fret < hand {
+                next.fret
+            } else {
+                next.fret - 3
+            };
+        }
+        positions.push(next);
+        previous = next;
+    }
+    (moved, positions)
+}
@@ -0,0 +1,97 @@
+use crate::fretboard::FretboardError;
+use crate::note::{Note, NoteLetter, Pitch};
+use std::fmt;
+
+/// The open notes of a fretted instrument's strings.
+///
+/// Strings are listed in the order they lie on the neck, starting from the string that sits lowest
+/// on a guitar, the sixth. They usually rise in pitch, but need not, as on a re-entrant ukulele.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tuning {
+    strings: Vec<Note>,
+}
+
+fn note(letter: NoteLetter, octave: i16) -> Note {
+    Note::new(Pitch::new(letter, 0), octave)
+}
+
+impl Tuning {
+    /// A tuning from the open notes of its strings.
+    pub fn new(strings: Vec<Note>) -> Result<Self, FretboardError> {
+        if strings.is_empty() {
+            return Err(FretboardError::EmptyTuning);
+        }
+        Ok(Tuning { strings })
+    }
+
+    /// Standard guitar tuning, E2 A2 D3 G3 B3 E4.
+    pub fn standard() -> Self {
+        use NoteLetter::*;
+        Self::from_notes(&[(E, 2), (A, 2), (D, 3), (G, 3), (B, 3), (E, 4)])
+    }
+
+    /// Standard guitar tuning with the sixth string lowered to D2.
+    pub fn drop_d() -> Self {
+        use NoteLetter::*;
+        Self::from_notes(&[(D, 2), (A, 2), (D, 3), (G, 3), (B, 3), (E, 4)])
+    }
+
+    /// The open guitar tuning D2 A2 D3 G3 A3 D4.
+    pub fn dadgad() -> Self {
+        use NoteLetter::*;
+        Self::from_notes(&[(D, 2), (A, 2), (D, 3), (G, 3), (A, 3), (D, 4)])
+    }
+
+    /// Four-string bass tuning, E1 A1 D2 G2.
+    pub fn bass() -> Self {
+        use NoteLetter::*;
+        Self::from_notes(&[(E, 1), (A, 1), (D, 2), (G, 2)])
+    }
+
+    /// Re-entrant ukulele tuning, G4 C4 E4 A4.
+    pub fn ukulele() -> Self {
+        use NoteLetter::*;
+        Self::from_notes(&[(G, 4), (C, 4), (E, 4), (A, 4)])
+    }
+
+    /// Seven-string guitar tuning, standard tuning with a low B1.
+    pub fn seven_string() -> Self {
+        use NoteLetter::*;
+        Self::from_notes(&[(B, 1), (E, 2), (A, 2), (D, 3), (G, 3), (B, 3), (E, 4)])
+    }
+
+    fn from_notes(notes: &[(NoteLetter, i16)]) -> Self {
+        Tuning {
+            strings: notes
+                .iter()
+                .map(|&(letter, octave)| note(letter, octave))
+                .collect(),
+        }
+    }
+
+    /// The open notes of the strings.
+    pub fn strings(&self) -> &[Note] {
+        &self.strings
+    }
+
+    /// The number of strings.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Whether the tuning has no strings, which [`Tuning::new`] never allows.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+impl fmt::Display for Tuning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<String> = self
+            .strings
+            .iter()
+            .map(|note| format!("{}{}", note.pitch, note.octave))
+            .collect();
+        write!(f, "{}", names.join(" "))
+    }
+}
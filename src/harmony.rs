@@ -0,0 +1,27 @@
+//! Harmonic function of chords within a key.
+//!
+//! ```
+//! use rust_music_theory::chord::Chord;
+//! use rust_music_theory::harmony::RomanNumeral;
+//! use rust_music_theory::note::{KeySignature, NoteLetter, Pitch};
+//!
+//! let key = KeySignature::new(Pitch::new(NoteLetter::C, 0));
+//! let chord = Chord::parse("D7/F#").unwrap();
+//! assert_eq!(RomanNumeral::analyze(&chord, &key).to_string(), "V65/V");
+//!
+//! let numeral = RomanNumeral::parse("V65/V").unwrap();
+//! assert_eq!(numeral.to_chord(&key).unwrap().canonical_symbol(), "D7/F#");
+//! ```
+
+mod chord_scale;
+mod errors;
+mod parser;
+mod progression;
+mod roman;
+mod voice_leading;
+
+pub use chord_scale::{ChordScale, ScaleTone, ToneRole};
+pub use errors::{HarmonyError, ProgressionError, VoiceLeadingError};
+pub use progression::{Bar, Progression, TimedChord};
+pub use roman::{AugmentedSixth, NumeralKind, RomanNumeral};
+pub use voice_leading::{VoiceLeader, VoiceLeading, MAX_VOICES};
@@ -0,0 +1,188 @@
+use crate::chord::{Chord, ChordTone};
+use crate::note::{Notes, Pitch};
+use crate::scale::{Direction, Mode, Scale};
+
+/// The part a scale tone plays over a chord.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToneRole {
+    /// A tone of the chord itself.
+    ChordTone,
+    /// A tone that can be held against the chord, such as a ninth or a raised eleventh.
+    Tension,
+    /// A tone a minor ninth above a chord tone, which clashes when sustained.
+    Avoid,
+}
+
+/// A tone of a chord scale with its degree above the chord root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScaleTone {
+    /// The tone, spelled as the chord spells it when it is a chord tone.
+    pub pitch: Pitch,
+    /// The tone's degree above the chord root, using 9, 11 and 13 for the second, fourth and sixth.
+    pub degree: ChordTone,
+    /// Whether the tone is a chord tone, a tension or an avoid note.
+    pub role: ToneRole,
+}
+
+/// A scale to play over a chord, rooted on the chord root.
+#[derive(Debug, Clone)]
+pub struct ChordScale {
+    /// The scale, in the chord's octave.
+    pub scale: Scale,
+    /// The scale's tones from the root up, each marked with its role over the chord.
+    pub tones: Vec<ScaleTone>,
+    /// Chord tones the scale replaces with a neighbouring tone, at most one altered fifth or
+    /// extension.
+    pub clashes: Vec<Pitch>,
+    score: u32,
+}
+
+impl ChordScale {
+    /// The scale tones that are tensions over the chord.
+    pub fn tensions(&self) -> Vec<Pitch> {
+        self.with_role(ToneRole::Tension)
+    }
+
+    /// The scale tones to avoid sustaining over the chord.
+    pub fn avoid_notes(&self) -> Vec<Pitch> {
+        self.with_role(ToneRole::Avoid)
+    }
+
+    fn with_role(&self, role: ToneRole) -> Vec<Pitch> {
+        self.tones
+            .iter()
+            .filter(|tone| tone.role == role)
+            .map(|tone| tone.pitch)
+            .collect()
+    }
+}
+
+impl Chord {
+    /// Rank the seven-note modes on the chord root by how well they fit the chord.
+    ///
+    /// Scales holding every chord tone come first, followed by scales that replace a single
+    /// altered fifth or extension, since no seven-note mode holds chords such as `C7(b9,#11)`.
+    /// Among these, scales rank higher with fewer tones contradicting a chord member, fewer
+    /// altered tensions that the chord does not ask for and fewer avoid notes, so `C7` prefers
+    /// Mixolydian, `C7#11` Lydian dominant and `C7alt` the altered scale. The flat ninth of a
+    /// dominant seventh chord is a tension rather than an avoid note.
+    pub fn available_scales(&self) -> Vec<ChordScale> {
+        let mut candidates: Vec<ChordScale> = Mode::heptatonic_modes()
+            .iter()
+            .filter_map(|&mode| chord_scale(self, mode))
+            .collect();
+        candidates.sort_by_key(|candidate| candidate.score);
+        candidates
+    }
+}
+
+fn chord_scale(chord: &Chord, mode: Mode) -> Option<ChordScale> {
+    let root = chord.root();
+    let scale = Scale::new(
+        mode.scale_type(),
+        root,
+        chord.octave(),
+        Some(mode),
+        Direction::Ascending,
+    )
+    .ok()?;
+    let scale_pitches: Vec<Pitch> = scale
+        .notes()
+        .iter()
+        .take(7)
+        .map(|note| note.pitch)
+        .collect();
+    let scale_set = scale_pitches
+        .iter()
+        .fold(0u16, |set, pitch| set | (1 << pitch.into_u8()));
+
+    // Pair each formula tone with its spelling, ignoring the inversion and any slash bass.
+    let chord_tones = chord.formula().tones();
+    let chord_pitches: Vec<Pitch> = chord_tones
+        .iter()
+        .map(|tone| {
+            Pitch::from_u8_with_letter(
+                (root.into_u8() as i16 + tone.semitones()).rem_euclid(12) as u8,
+                root.letter.offset(tone.letter_offset()),
+            )
+        })
+        .collect();
+    let mut clashes = Vec::new();
+    for (pitch, tone) in chord_pitches.iter().zip(chord_tones) {
+        if scale_set & (1 << pitch.into_u8()) == 0 {
+            let altered_fifth = tone.degree() == 5 && tone.alteration() != 0;
+            if !altered_fifth && !matches!(tone.degree(), 9 | 11 | 13) {
+                return None;
+            }
+            clashes.push(*pitch);
+        }
+    }
+    if clashes.len() > 1 {
+        return None;
+    }
+
+    let dominant = chord_tones.contains(&ChordTone::new(3, 0).ok()?)
+        && chord_tones.contains(&ChordTone::new(7, -1).ok()?);
+    let chord_set = chord_pitches
+        .iter()
+        .fold(0u16, |set, pitch| set | (1 << pitch.into_u8()));
+    let mut avoid_notes = 0;
+    let mut altered_tensions = 0;
+    let mut contradictions = 0;
+    let mut tones = Vec::with_capacity(7);
+    for &pitch in &scale_pitches {
+        let class = pitch.into_u8();
+        let above_root = (class + 12 - root.into_u8()) % 12;
+        if let Some(index) = chord_pitches.iter().position(|p| p.into_u8() == class) {
+            tones.push(ScaleTone {
+                pitch: chord_pitches[index],
+                degree: chord_tones[index],
+                role: ToneRole::ChordTone,
+            });
+            continue;
+        }
+
+        let degree = scale_degree(root, pitch)?;
+        let below = (class + 11) % 12;
+        let role = if chord_set & (1 << below) != 0 && !(dominant && above_root == 1) {
+            avoid_notes += 1;
+            ToneRole::Avoid
+        } else {
+            ToneRole::Tension
+        };
+        if chord_tones
+            .iter()
+            .any(|tone| tone.degree() == degree.degree())
+        {
+            // Another form of a chord member, such as the natural fifth over a flat five chord.
+            contradictions += 1;
+        } else if matches!(degree.degree(), 9 | 11 | 13) && degree.alteration() != 0 {
+            altered_tensions += 1;
+        }
+        tones.push(ScaleTone {
+            pitch,
+            degree,
+            role,
+        });
+    }
+    tones.sort_by_key(|tone| tone.degree.semitones().rem_euclid(12));
+
+    let score =
+        clashes.len() as u32 * 100 + contradictions * 5 + altered_tensions * 2 + avoid_notes;
+    Some(ChordScale {
+        scale,
+        tones,
+        clashes,
+        score,
+    })
+}
+
+/// The chord degree a scale tone's letter names above the root, with its alteration.
+fn scale_degree(root: Pitch, pitch: Pitch) -> Option<ChordTone> {
+    const DEGREES: [u8; 7] = [1, 9, 3, 11, 5, 13, 7];
+    let steps = (pitch.letter.index() - root.letter.index()).rem_euclid(7) as usize;
+    let natural = ChordTone::new(DEGREES[steps], 0).ok()?.semitones();
+    let semitones = pitch.into_u8() as i16 - root.into_u8() as i16;
+    let alteration = (semitones - natural + 6).rem_euclid(12) - 6;
+    ChordTone::new(DEGREES[steps], alteration as i8).ok()
+}
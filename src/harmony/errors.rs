@@ -0,0 +1,144 @@
+use crate::chord::ChordError;
+use crate::note::Note;
+use std::error;
+use std::fmt;
+
+/// An error while parsing a Roman numeral or building its chord.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HarmonyError {
+    EmptyNumeral,
+    UnexpectedToken { position: usize, token: String },
+    InvalidChord(ChordError),
+}
+
+impl fmt::Display for HarmonyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HarmonyError::EmptyNumeral => write!(f, "Roman numeral is empty"),
+            HarmonyError::UnexpectedToken { position, token } => {
+                write!(f, "Unexpected token {:?} at byte {}", token, position)
+            }
+            HarmonyError::InvalidChord(error) => write!(f, "Invalid chord: {}", error),
+        }
+    }
+}
+
+impl error::Error for HarmonyError {}
+
+impl From<ChordError> for HarmonyError {
+    fn from(error: ChordError) -> Self {
+        HarmonyError::InvalidChord(error)
+    }
+}
+
+/// An error while parsing a chord progression, with the byte position of the offending token.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgressionError {
+    EmptyProgression,
+    UnexpectedToken {
+        position: usize,
+        token: String,
+    },
+    InvalidChord {
+        position: usize,
+        symbol: String,
+        error: ChordError,
+    },
+    InvalidDuration {
+        position: usize,
+        duration: String,
+    },
+    InvalidTimeSignature {
+        position: usize,
+        signature: String,
+    },
+    NothingToRepeat {
+        position: usize,
+    },
+    UnclosedRepeat {
+        position: usize,
+    },
+    BarLength {
+        position: usize,
+        beats: f64,
+        expected: f64,
+    },
+}
+
+impl fmt::Display for ProgressionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProgressionError::EmptyProgression => write!(f, "Progression has no chords"),
+            ProgressionError::UnexpectedToken { position, token } => {
+                write!(f, "Unexpected token {:?} at byte {}", token, position)
+            }
+            ProgressionError::InvalidChord {
+                position,
+                symbol,
+                error,
+            } => write!(
+                f,
+                "Invalid chord {:?} at byte {}: {}",
+                symbol, position, error
+            ),
+            ProgressionError::InvalidDuration { position, duration } => {
+                write!(f, "Invalid duration {:?} at byte {}", duration, position)
+            }
+            ProgressionError::InvalidTimeSignature {
+                position,
+                signature,
+            } => write!(
+                f,
+                "Invalid time signature {:?} at byte {}",
+                signature, position
+            ),
+            ProgressionError::NothingToRepeat { position } => {
+                write!(f, "Nothing to repeat at byte {}", position)
+            }
+            ProgressionError::UnclosedRepeat { position } => {
+                write!(f, "Repeat opened at byte {} is never closed", position)
+            }
+            ProgressionError::BarLength {
+                position,
+                beats,
+                expected,
+            } => write!(
+                f,
+                "Bar at byte {} has {} beats, expected {}",
+                position, beats, expected
+            ),
+        }
+    }
+}
+
+impl error::Error for ProgressionError {}
+
+/// An error while leading voices through a sequence of chords.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VoiceLeadingError {
+    NoVoices,
+    EmptyRange { lowest: Note, highest: Note },
+    NoVoicing { index: usize },
+    TooManyVoices { voices: usize, max: usize },
+}
+
+impl fmt::Display for VoiceLeadingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VoiceLeadingError::NoVoices => write!(f, "Voice leading needs at least one voice"),
+            VoiceLeadingError::EmptyRange { lowest, highest } => write!(
+                f,
+                "Range from {}{} to {}{} is empty",
+                lowest, lowest.octave, highest, highest.octave
+            ),
+            VoiceLeadingError::NoVoicing { index } => {
+                write!(f, "Chord {} cannot be voiced in the range", index)
+            }
+            VoiceLeadingError::TooManyVoices { voices, max } => {
+                write!(f, "Cannot lead {} voices, at most {} fit", voices, max)
+            }
+        }
+    }
+}
+
+impl error::Error for VoiceLeadingError {}
@@ -0,0 +1,463 @@
+use crate::chord::{Chord, ChordExtension, SeventhQuality, Suspension, TriadQuality};
+use crate::harmony::progression::bar_beats;
+use crate::harmony::{AugmentedSixth, HarmonyError, NumeralKind, ProgressionError, RomanNumeral};
+
+const NUMERALS: [&str; 7] = ["I", "II", "III", "IV", "V", "VI", "VII"];
+
+const AUGMENTED_SIXTHS: [(&str, AugmentedSixth); 10] = [
+    ("Ger+6", AugmentedSixth::German),
+    ("Gr+6", AugmentedSixth::German),
+    ("Ger6", AugmentedSixth::German),
+    ("Ger", AugmentedSixth::German),
+    ("It+6", AugmentedSixth::Italian),
+    ("It6", AugmentedSixth::Italian),
+    ("It", AugmentedSixth::Italian),
+    ("Fr+6", AugmentedSixth::French),
+    ("Fr6", AugmentedSixth::French),
+    ("Fr", AugmentedSixth::French),
+];
+
+/// A figured-bass symbol with the chord member in the bass, whether it implies a seventh, and the
+/// extension it names. Slashed forms come first so `6/5` is not read as `6` applied to `5`.
+const FIGURES: [(&str, u8, bool, ChordExtension); 14] = [
+    ("6/5", 1, true, ChordExtension::Seventh),
+    ("4/3", 2, true, ChordExtension::Seventh),
+    ("4/2", 3, true, ChordExtension::Seventh),
+    ("6/4", 2, false, ChordExtension::Triad),
+    ("65", 1, true, ChordExtension::Seventh),
+    ("43", 2, true, ChordExtension::Seventh),
+    ("42", 3, true, ChordExtension::Seventh),
+    ("64", 2, false, ChordExtension::Triad),
+    ("13", 0, true, ChordExtension::Thirteenth),
+    ("11", 0, true, ChordExtension::Eleventh),
+    ("9", 0, true, ChordExtension::Ninth),
+    ("7", 0, true, ChordExtension::Seventh),
+    ("6", 1, false, ChordExtension::Triad),
+    ("2", 3, true, ChordExtension::Seventh),
+];
+
+struct Cursor<'a> {
+    input: &'a str,
+    position: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        if self.rest().starts_with(token) {
+            self.position += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_any(&mut self, tokens: &[&str]) -> bool {
+        tokens.iter().any(|token| self.eat(token))
+    }
+
+    fn unexpected(&self) -> HarmonyError {
+        let token = self
+            .rest()
+            .chars()
+            .next()
+            .map(String::from)
+            .unwrap_or_default();
+        HarmonyError::UnexpectedToken {
+            position: self.position,
+            token,
+        }
+    }
+}
+
+/// Parse a Roman numeral such as `ii7`, `bVII`, `V65/V`, `viiø7`, `N6` or `Ger+6`.
+pub(crate) fn parse_numeral(input: &str) -> Result<RomanNumeral, HarmonyError> {
+    let symbol = input.trim_end();
+    let start = symbol.len() - symbol.trim_start().len();
+    if start == symbol.len() {
+        return Err(HarmonyError::EmptyNumeral);
+    }
+
+    let mut cursor = Cursor {
+        input: symbol,
+        position: start,
+    };
+    let numeral = parse_applied(&mut cursor)?;
+    if !cursor.rest().is_empty() {
+        return Err(cursor.unexpected());
+    }
+    Ok(numeral)
+}
+
+fn parse_applied(cursor: &mut Cursor) -> Result<RomanNumeral, HarmonyError> {
+    let mut numeral = parse_single(cursor)?;
+    if cursor.eat("/") {
+        numeral.secondary = Some(Box::new(parse_applied(cursor)?));
+        numeral.chromatic = true;
+    }
+    Ok(numeral)
+}
+
+fn parse_single(cursor: &mut Cursor) -> Result<RomanNumeral, HarmonyError> {
+    if let Some(&(_, sixth)) = AUGMENTED_SIXTHS.iter().find(|(name, _)| cursor.eat(name)) {
+        let mut numeral = RomanNumeral::new(6, TriadQuality::Major);
+        numeral.kind = NumeralKind::AugmentedSixth(sixth);
+        numeral.chromatic = true;
+        return Ok(numeral);
+    }
+    if cursor.eat("N") {
+        let mut numeral = RomanNumeral::new(2, TriadQuality::Major);
+        numeral.kind = NumeralKind::Neapolitan;
+        numeral.accidental = -1;
+        numeral.chromatic = true;
+        if cursor.eat_any(&["6/4", "64"]) {
+            numeral.inversion = 2;
+        } else if cursor.eat("6") {
+            numeral.inversion = 1;
+        }
+        return Ok(numeral);
+    }
+
+    let mut accidental = 0i8;
+    loop {
+        if cursor.eat_any(&["b", "♭"]) {
+            accidental -= 1;
+        } else if cursor.eat_any(&["#", "♯"]) {
+            accidental += 1;
+        } else {
+            break;
+        }
+    }
+
+    let letters: String = cursor
+        .rest()
+        .chars()
+        .take_while(|c| matches!(c, 'I' | 'V' | 'i' | 'v'))
+        .collect();
+    let upper = letters.chars().all(|c| c.is_ascii_uppercase());
+    let lower = letters.chars().all(|c| c.is_ascii_lowercase());
+    let degree = NUMERALS
+        .iter()
+        .position(|numeral| numeral.eq_ignore_ascii_case(&letters))
+        .filter(|_| upper || lower)
+        .ok_or_else(|| cursor.unexpected())?;
+    cursor.position += letters.len();
+
+    let mut numeral = RomanNumeral::new(degree as u8 + 1, TriadQuality::Major);
+    numeral.accidental = accidental;
+    numeral.chromatic = accidental != 0;
+    if lower {
+        numeral.triad = TriadQuality::Minor;
+    }
+    let mut half_diminished = false;
+    if cursor.eat_any(&["°", "o", "dim"]) {
+        numeral.triad = TriadQuality::Diminished;
+    } else if cursor.eat_any(&["ø", "h"]) {
+        numeral.triad = TriadQuality::Diminished;
+        half_diminished = true;
+    } else if cursor.eat_any(&["+", "aug"]) {
+        numeral.triad = TriadQuality::Augmented;
+    }
+    let major_seventh_at = cursor.position;
+    let major_seventh = cursor.eat_any(&["maj", "M"]);
+
+    if cursor.eat("add69") {
+        numeral.extension = ChordExtension::SixNine;
+    } else if cursor.eat("add6") {
+        numeral.extension = ChordExtension::Sixth;
+    }
+    if cursor.eat("5") {
+        numeral.triad = TriadQuality::Power;
+    }
+
+    let mut has_seventh = false;
+    if let Some(&(_, inversion, seventh, extension)) =
+        FIGURES.iter().find(|(figure, ..)| cursor.eat(figure))
+    {
+        numeral.inversion = inversion;
+        has_seventh = seventh;
+        if seventh {
+            numeral.extension = extension;
+        }
+    }
+    if half_diminished && !has_seventh {
+        // The half-diminished symbol implies a seventh.
+        has_seventh = true;
+        numeral.extension = ChordExtension::Seventh;
+    }
+    if major_seventh && !has_seventh {
+        // A major seventh symbol needs a seventh to qualify.
+        cursor.position = major_seventh_at;
+        return Err(cursor.unexpected());
+    }
+    if has_seventh {
+        numeral.seventh = Some(if major_seventh {
+            SeventhQuality::Major
+        } else if numeral.triad == TriadQuality::Diminished && !half_diminished {
+            SeventhQuality::Diminished
+        } else {
+            SeventhQuality::Minor
+        });
+    }
+
+    if cursor.eat("sus2") {
+        numeral.suspension = Some(Suspension::Second);
+    } else if cursor.eat_any(&["sus4", "sus"]) {
+        numeral.suspension = Some(Suspension::Fourth);
+    }
+    Ok(numeral)
+}
+
+/// The chords of a parsed bar with their durations in beats, and the bar's time signature.
+pub(crate) type ParsedBar = ((u8, u8), Vec<(Chord, f64)>);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Token<'a> {
+    BarLine,
+    StartRepeat,
+    EndRepeat,
+    Word(&'a str),
+}
+
+/// Split a progression into bar lines, repeat signs and words, each with its byte position.
+/// Whitespace inside parentheses stays part of the word, so `C7(b9, #11)` is a single chord.
+fn tokenize(input: &str) -> Vec<(usize, Token<'_>)> {
+    let mut tokens = Vec::new();
+    let mut position = 0;
+    while position < input.len() {
+        let rest = &input[position..];
+        let c = rest.chars().next().unwrap_or(' ');
+        if c.is_whitespace() {
+            position += c.len_utf8();
+        } else if rest.starts_with("|:") {
+            tokens.push((position, Token::StartRepeat));
+            position += 2;
+        } else if rest.starts_with(":|") {
+            tokens.push((position, Token::EndRepeat));
+            position += 2;
+        } else if c == '|' {
+            tokens.push((position, Token::BarLine));
+            position += 1;
+        } else {
+            let mut depth = 0;
+            let mut end = position;
+            for (offset, c) in rest.char_indices() {
+                let at = &rest[offset..];
+                if c == '|' || at.starts_with(":|") || (c.is_whitespace() && depth == 0) {
+                    break;
+                }
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => {}
+                }
+                end = position + offset + c.len_utf8();
+            }
+            tokens.push((position, Token::Word(&input[position..end])));
+            position = end;
+        }
+    }
+    tokens
+}
+
+struct RawBar {
+    position: usize,
+    time_signature: (u8, u8),
+    chords: Vec<(Chord, Option<f64>)>,
+}
+
+/// Parse a progression such as `| Dm7 G7 | Cmaj7 % |` into bars with repeats written out.
+pub(crate) fn parse_progression(input: &str) -> Result<Vec<ParsedBar>, ProgressionError> {
+    let tokens = tokenize(input);
+    let barred = tokens
+        .iter()
+        .any(|(_, token)| !matches!(token, Token::Word(_)));
+
+    let mut bars: Vec<RawBar> = Vec::new();
+    let mut current = RawBar {
+        position: 0,
+        time_signature: (4, 4),
+        chords: Vec::new(),
+    };
+    let mut previous: Option<Chord> = None;
+    let mut repeat_from = 0;
+    let mut open_repeat: Option<usize> = None;
+
+    for &(position, token) in &tokens {
+        match token {
+            Token::Word(word) => {
+                if current.chords.is_empty() {
+                    current.position = position;
+                }
+                if is_time_signature(word) {
+                    if !current.chords.is_empty() {
+                        return Err(ProgressionError::UnexpectedToken {
+                            position,
+                            token: word.to_string(),
+                        });
+                    }
+                    current.time_signature = parse_time_signature(position, word)?;
+                    continue;
+                }
+                let (symbol, duration) = match word.rfind(':') {
+                    Some(colon) => {
+                        let duration = &word[colon + 1..];
+                        let beats = duration
+                            .parse::<f64>()
+                            .ok()
+                            .filter(|beats| beats.is_finite() && *beats > 0.0)
+                            .ok_or_else(|| ProgressionError::InvalidDuration {
+                                position: position + colon + 1,
+                                duration: duration.to_string(),
+                            })?;
+                        (&word[..colon], Some(beats))
+                    }
+                    None => (word, None),
+                };
+                let chord = if symbol == "%" {
+                    previous
+                        .clone()
+                        .ok_or(ProgressionError::NothingToRepeat { position })?
+                } else {
+                    Chord::parse(symbol).map_err(|error| ProgressionError::InvalidChord {
+                        position,
+                        symbol: symbol.to_string(),
+                        error,
+                    })?
+                };
+                previous = Some(chord.clone());
+                current.chords.push((chord, duration));
+                if !barred {
+                    let time_signature = current.time_signature;
+                    bars.push(current);
+                    current = RawBar {
+                        position,
+                        time_signature,
+                        chords: Vec::new(),
+                    };
+                }
+            }
+            Token::BarLine | Token::StartRepeat | Token::EndRepeat => {
+                let time_signature = current.time_signature;
+                if !current.chords.is_empty() {
+                    bars.push(current);
+                }
+                current = RawBar {
+                    position,
+                    time_signature,
+                    chords: Vec::new(),
+                };
+                match token {
+                    Token::StartRepeat => {
+                        if open_repeat.is_some() {
+                            return Err(ProgressionError::UnexpectedToken {
+                                position,
+                                token: "|:".to_string(),
+                            });
+                        }
+                        open_repeat = Some(position);
+                        repeat_from = bars.len();
+                    }
+                    Token::EndRepeat => {
+                        let repeated: Vec<RawBar> = bars[repeat_from..]
+                            .iter()
+                            .map(|bar| RawBar {
+                                position: bar.position,
+                                time_signature: bar.time_signature,
+                                chords: bar.chords.clone(),
+                            })
+                            .collect();
+                        bars.extend(repeated);
+                        open_repeat = None;
+                        repeat_from = bars.len();
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    if !current.chords.is_empty() {
+        bars.push(current);
+    }
+    if let Some(position) = open_repeat {
+        return Err(ProgressionError::UnclosedRepeat { position });
+    }
+    if bars.is_empty() {
+        return Err(ProgressionError::EmptyProgression);
+    }
+
+    bars.into_iter()
+        .map(|bar| {
+            let expected = bar_beats(bar.time_signature);
+            let chords = if barred {
+                share_bar(&bar, expected)?
+            } else {
+                bar.chords
+                    .into_iter()
+                    .map(|(chord, duration)| (chord, duration.unwrap_or(expected)))
+                    .collect()
+            };
+            Ok((bar.time_signature, chords))
+        })
+        .collect()
+}
+
+/// Share a bar's beats among its chords, giving those without a duration equal parts of the
+/// beats left over.
+fn share_bar(bar: &RawBar, expected: f64) -> Result<Vec<(Chord, f64)>, ProgressionError> {
+    let explicit: f64 = bar.chords.iter().filter_map(|(_, beats)| *beats).sum();
+    let implicit = bar
+        .chords
+        .iter()
+        .filter(|(_, beats)| beats.is_none())
+        .count();
+    let remaining = expected - explicit;
+    let too_long = if implicit > 0 {
+        remaining <= 1e-9
+    } else {
+        remaining.abs() > 1e-9
+    };
+    if too_long {
+        return Err(ProgressionError::BarLength {
+            position: bar.position,
+            beats: explicit,
+            expected,
+        });
+    }
+    Ok(bar
+        .chords
+        .iter()
+        .map(|(chord, beats)| (chord.clone(), beats.unwrap_or(remaining / implicit as f64)))
+        .collect())
+}
+
+fn is_time_signature(word: &str) -> bool {
+    let mut parts = word.splitn(2, '/');
+    let numerator = parts.next().unwrap_or("");
+    let denominator = parts.next().unwrap_or("");
+    [numerator, denominator]
+        .iter()
+        .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn parse_time_signature(position: usize, word: &str) -> Result<(u8, u8), ProgressionError> {
+    let invalid = || ProgressionError::InvalidTimeSignature {
+        position,
+        signature: word.to_string(),
+    };
+    let mut parts = word.splitn(2, '/');
+    let numerator: u8 = parts
+        .next()
+        .and_then(|part| part.parse().ok())
+        .filter(|&n| n > 0)
+        .ok_or_else(invalid)?;
+    let denominator: u8 = parts
+        .next()
+        .and_then(|part| part.parse().ok())
+        .filter(|d| [1, 2, 4, 8, 16, 32].contains(d))
+        .ok_or_else(invalid)?;
+    Ok((numerator, denominator))
+}
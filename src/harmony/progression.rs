@@ -0,0 +1,243 @@
+use crate::chord::Chord;
+use crate::harmony::parser::{parse_progression, ParsedBar};
+use crate::harmony::{ProgressionError, RomanNumeral};
+use crate::interval::Interval;
+use crate::note::{KeyFinder, KeySignature, Note, Notes, Spelling};
+use crate::scale::Direction;
+use std::fmt;
+use std::str::FromStr;
+
+/// A chord with its place in a progression, measured in quarter-note beats.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedChord {
+    /// The chord.
+    pub chord: Chord,
+    /// The bar the chord falls in, counting from 0.
+    pub bar: usize,
+    /// The beat the chord starts on, counting from the start of the progression.
+    pub start: f64,
+    /// How many beats the chord lasts.
+    pub beats: f64,
+}
+
+/// A bar of a progression.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bar {
+    /// The beat the bar starts on, counting from the start of the progression.
+    pub start: f64,
+    /// How many quarter-note beats the bar lasts.
+    pub beats: f64,
+    /// The numerator and denominator of the bar's time signature.
+    pub time_signature: (u8, u8),
+}
+
+/// A sequence of chords in bars, with repeats written out.
+///
+/// Progressions are parsed from lead-sheet text such as `| Dm7 G7 | Cmaj7 % |`. Chords in a bar
+/// share its beats evenly unless given a duration in beats, as in `G7:3`. `%` repeats the previous
+/// chord, `|:` and `:|` repeat the bars between them once, and a time signature such as `3/4` at
+/// the start of a bar applies from that bar on. Without bar lines, each chord lasts its duration
+/// or a whole bar.
+///
+/// The [`Notes`] of a progression are every chord's notes in order. To keep the timing when
+/// rendering to MIDI, use `MidiBuilder::add_progression`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Progression {
+    chords: Vec<TimedChord>,
+    bars: Vec<Bar>,
+}
+
+impl Progression {
+    /// Parse a progression from lead-sheet text.
+    pub fn parse(text: &str) -> Result<Self, ProgressionError> {
+        Ok(Self::from_bars(parse_progression(text)?))
+    }
+
+    /// Lay out bars of chords and their durations one after another.
+    pub(crate) fn from_bars(parsed: Vec<ParsedBar>) -> Self {
+        let mut chords = Vec::new();
+        let mut bars = Vec::with_capacity(parsed.len());
+        let mut beat = 0.0;
+        for (index, (time_signature, bar_chords)) in parsed.into_iter().enumerate() {
+            let start = beat;
+            for (chord, beats) in bar_chords {
+                chords.push(TimedChord {
+                    chord,
+                    bar: index,
+                    start: beat,
+                    beats,
+                });
+                beat += beats;
+            }
+            bars.push(Bar {
+                start,
+                beats: beat - start,
+                time_signature,
+            });
+        }
+        Progression { chords, bars }
+    }
+
+    /// The chords with their timing, in order.
+    pub fn chords(&self) -> &[TimedChord] {
+        &self.chords
+    }
+
+    /// The bars, in order.
+    pub fn bars(&self) -> &[Bar] {
+        &self.bars
+    }
+
+    /// Iterate over the chords with their timing.
+    pub fn iter(&self) -> std::slice::Iter<'_, TimedChord> {
+        self.chords.iter()
+    }
+
+    /// The length of the progression in quarter-note beats.
+    pub fn total_beats(&self) -> f64 {
+        self.bars.last().map_or(0.0, |bar| bar.start + bar.beats)
+    }
+
+    /// Move every chord by a spelled interval, keeping the timing.
+    pub fn transpose(&self, interval: Interval, direction: Direction) -> Self {
+        self.map_chords(|chord| chord.transpose(interval, direction))
+    }
+
+    /// Move every chord by a signed number of semitones, spelling the new roots with `spelling`.
+    pub fn transpose_semitones(&self, semitones: i16, spelling: &Spelling) -> Self {
+        self.map_chords(|chord| chord.transpose_semitones(semitones, spelling))
+    }
+
+    /// The function of each chord in a key.
+    pub fn analyze(&self, key: &KeySignature) -> Vec<RomanNumeral> {
+        self.chords
+            .iter()
+            .map(|timed| RomanNumeral::analyze(&timed.chord, key))
+            .collect()
+    }
+
+    /// The most likely major or minor key, weighting each chord's notes by its duration.
+    pub fn estimate_key(&self) -> Option<KeySignature> {
+        let weighted: Vec<(Note, f64)> = self
+            .chords
+            .iter()
+            .flat_map(|timed| {
+                timed
+                    .chord
+                    .notes()
+                    .into_iter()
+                    .map(move |note| (note, timed.beats))
+            })
+            .collect();
+        KeyFinder::default()
+            .rank_weighted(&weighted)
+            .into_iter()
+            .next()
+            .map(|candidate| candidate.key)
+    }
+
+    fn map_chords(&self, transform: impl Fn(&Chord) -> Chord) -> Self {
+        Progression {
+            chords: self
+                .chords
+                .iter()
+                .map(|timed| TimedChord {
+                    chord: transform(&timed.chord),
+                    ..timed.clone()
+                })
+                .collect(),
+            bars: self.bars.clone(),
+        }
+    }
+
+    /// Write one chord per bar without bar lines, giving each chord's duration unless it lasts
+    /// a whole bar.
+    fn fmt_unbarred(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut time_signature = (4, 4);
+        for (index, timed) in self.chords.iter().enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+            let bar = self.bars[timed.bar];
+            if bar.time_signature != time_signature {
+                time_signature = bar.time_signature;
+                write!(f, "{}/{} ", time_signature.0, time_signature.1)?;
+            }
+            if (timed.beats - bar_beats(time_signature)).abs() < 1e-9 {
+                write!(f, "{}", timed.chord)?;
+            } else {
+                write!(f, "{}:{}", timed.chord, timed.beats)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Notes for Progression {
+    fn notes(&self) -> Vec<Note> {
+        self.chords
+            .iter()
+            .flat_map(|timed| timed.chord.notes())
+            .collect()
+    }
+}
+
+impl<'a> IntoIterator for &'a Progression {
+    type Item = &'a TimedChord;
+    type IntoIter = std::slice::Iter<'a, TimedChord>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.chords.iter()
+    }
+}
+
+impl FromStr for Progression {
+    type Err = ProgressionError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text)
+    }
+}
+
+impl fmt::Display for Progression {
+    /// Write the progression back as text, with repeats written out and durations given only
+    /// where the chords of a bar do not share it evenly.
+    ///
+    /// Progressions parsed without bar lines whose chords do not each fill a bar are written
+    /// without bar lines too, so that the text parses back to the same progression.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let full = |bar: &Bar| (bar.beats - bar_beats(bar.time_signature)).abs() < 1e-9;
+        if !self.bars.iter().all(full) {
+            return self.fmt_unbarred(f);
+        }
+        let mut time_signature = (4, 4);
+        for (index, bar) in self.bars.iter().enumerate() {
+            write!(f, "|")?;
+            if bar.time_signature != time_signature {
+                time_signature = bar.time_signature;
+                write!(f, " {}/{}", time_signature.0, time_signature.1)?;
+            }
+            let chords: Vec<&TimedChord> = self
+                .chords
+                .iter()
+                .filter(|timed| timed.bar == index)
+                .collect();
+            let even = bar_beats(time_signature) / chords.len() as f64;
+            let shared = chords.iter().all(|timed| (timed.beats - even).abs() < 1e-9);
+            for timed in chords {
+                if shared {
+                    write!(f, " {}", timed.chord)?;
+                } else {
+                    write!(f, " {}:{}", timed.chord, timed.beats)?;
+                }
+            }
+            write!(f, " ")?;
+        }
+        write!(f, "|")
+    }
+}
+
+/// The number of quarter-note beats in a bar of a time signature.
+pub(crate) fn bar_beats(time_signature: (u8, u8)) -> f64 {
+    time_signature.0 as f64 * 4.0 / time_signature.1 as f64
+}
@@ -0,0 +1,392 @@
+use crate::chord::{Chord, ChordExtension, SeventhQuality, Suspension, TriadQuality};
+use crate::harmony::parser::parse_numeral;
+use crate::harmony::HarmonyError;
+use crate::note::{KeySignature, Notes, Pitch};
+use crate::scale::Mode;
+use std::fmt;
+use std::str::FromStr;
+
+/// The national names of the augmented sixth chords.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AugmentedSixth {
+    /// Lowered sixth, tonic and raised fourth.
+    Italian,
+    /// The Italian sixth with the second degree added.
+    French,
+    /// The Italian sixth with the lowered third degree added.
+    German,
+}
+
+/// Whether a numeral names a chord by its scale degree or by a traditional special name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumeralKind {
+    /// A chord named by the scale degree of its root, such as `ii7` or `bVI`.
+    Degree,
+    /// The major triad on the lowered second degree, usually in first inversion.
+    Neapolitan,
+    /// A predominant chord built around the augmented sixth above the lowered sixth degree.
+    AugmentedSixth(AugmentedSixth),
+}
+
+/// A chord's function in a key, written as a Roman numeral.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RomanNumeral {
+    /// How the numeral is named.
+    pub kind: NumeralKind,
+    /// The scale degree of the root, from 1 to 7.
+    pub degree: u8,
+    /// The chromatic alteration of the root against the key, such as -1 for `bVI`.
+    pub accidental: i8,
+    /// The quality of the chord's triad.
+    pub triad: TriadQuality,
+    /// The quality of the chord's seventh, if any.
+    pub seventh: Option<SeventhQuality>,
+    /// The highest extension of the chord.
+    pub extension: ChordExtension,
+    /// A suspension replacing the third, if any.
+    pub suspension: Option<Suspension>,
+    /// Which chord member is in the bass: 0 for the root, 1 for the third, 2 for the fifth and
+    /// 3 for the seventh.
+    pub inversion: u8,
+    /// The degree this chord tonicizes, as in `V/V`.
+    pub secondary: Option<Box<RomanNumeral>>,
+    /// Whether the chord uses pitches outside the key, as borrowed and applied chords do.
+    pub chromatic: bool,
+}
+
+impl RomanNumeral {
+    /// A root-position triad on a scale degree.
+    pub fn new(degree: u8, triad: TriadQuality) -> Self {
+        RomanNumeral {
+            kind: NumeralKind::Degree,
+            degree,
+            accidental: 0,
+            triad,
+            seventh: None,
+            extension: ChordExtension::Triad,
+            suspension: None,
+            inversion: 0,
+            secondary: None,
+            chromatic: false,
+        }
+    }
+
+    /// Parse a Roman numeral such as `ii7`, `bVII`, `iv6`, `V65/V`, `N6` or `Ger+6`.
+    ///
+    /// Case gives the triad quality, and `°` or `o`, `ø` or `h`, and `+` mark diminished,
+    /// half-diminished and augmented chords. A seventh is minor unless `M` or `maj` precedes the
+    /// figure, or diminished on a `°` triad. Figures may be written as `65` or `6/5`. Without a
+    /// key, only accidentals, applied numerals and special names are marked chromatic.
+    pub fn parse(numeral: &str) -> Result<Self, HarmonyError> {
+        parse_numeral(numeral)
+    }
+
+    /// Build the chord the numeral names in a key, in octave 4 and with its inversion set.
+    ///
+    /// In minor keys, minor and diminished chords on the seventh degree use the raised leading
+    /// tone while `VII` stays on the subtonic. Applied numerals are built in the major or minor
+    /// key of their target, and augmented sixths always stand on the sixth degree of the parallel
+    /// minor.
+    pub fn to_chord(&self, key: &KeySignature) -> Result<Chord, HarmonyError> {
+        let key = self.local_key(key);
+        let mut builder = Chord::builder(self.root_in(&key));
+        if let NumeralKind::AugmentedSixth(sixth) = self.kind {
+            builder = match sixth {
+                AugmentedSixth::Italian => builder.omit(5).add(6, 1)?,
+                AugmentedSixth::French => builder.omit(5).add(4, 1)?.add(6, 1)?,
+                AugmentedSixth::German => builder.add(6, 1)?,
+            };
+            return Ok(builder.build()?);
+        }
+
+        builder = builder.triad_quality(self.triad).extension(self.extension);
+        if let Some(seventh) = self.seventh {
+            builder = builder.seventh_quality(seventh);
+        }
+        if let Some(suspension) = self.suspension {
+            builder = builder.suspension(suspension);
+        }
+        Ok(builder.inversion(self.inversion).build()?)
+    }
+
+    /// Describe the function of a chord in a key.
+    ///
+    /// Chords outside the key are read, in order of preference, as augmented sixth chords,
+    /// Neapolitan sixths, applied dominants and leading-tone chords, and finally as chromatic
+    /// chords on an altered degree such as `bVI`. In minor keys the raised sixth and seventh
+    /// degrees count as diatonic.
+    pub fn analyze(chord: &Chord, key: &KeySignature) -> Self {
+        let scale = key.pitches();
+        let spec = chord.spec();
+        let root = chord.root();
+        let (degree, accidental) = degree_of(root, key);
+        let chromatic = chord
+            .notes()
+            .iter()
+            .any(|note| !is_diatonic(note.pitch, key));
+        let mut numeral = RomanNumeral {
+            kind: NumeralKind::Degree,
+            degree,
+            accidental,
+            triad: spec.triad_quality(),
+            seventh: spec.seventh_quality(),
+            extension: spec.extension(),
+            suspension: spec.suspension(),
+            inversion: bass_member(chord),
+            secondary: None,
+            chromatic,
+        };
+        if !chromatic {
+            return numeral;
+        }
+
+        let tonic = scale[0].into_u8();
+        let relative = |pitch: Pitch| (pitch.into_u8() + 12 - tonic) % 12;
+        let bass = chord.bass().unwrap_or(root);
+        let mut classes: Vec<u8> = chord
+            .notes()
+            .iter()
+            .map(|note| relative(note.pitch))
+            .collect();
+        classes.sort_unstable();
+        classes.dedup();
+        if relative(bass) == 8 {
+            let sixth = match classes.as_slice() {
+                [0, 6, 8] => Some(AugmentedSixth::Italian),
+                [0, 2, 6, 8] => Some(AugmentedSixth::French),
+                [0, 3, 6, 8] => Some(AugmentedSixth::German),
+                _ => None,
+            };
+            if let Some(sixth) = sixth {
+                let (degree, accidental) = degree_of(bass, key);
+                numeral.kind = NumeralKind::AugmentedSixth(sixth);
+                numeral.degree = degree;
+                numeral.accidental = accidental;
+                return numeral;
+            }
+        }
+
+        if degree == 2
+            && relative(root) == 1
+            && numeral.triad == TriadQuality::Major
+            && numeral.seventh.is_none()
+        {
+            numeral.kind = NumeralKind::Neapolitan;
+            return numeral;
+        }
+
+        if let Some((degree, target)) = applied_target(&numeral, root, key) {
+            numeral.degree = degree;
+            numeral.accidental = 0;
+            numeral.secondary = Some(Box::new(target));
+        }
+        numeral
+    }
+
+    /// The figured-bass inversion symbol, such as `6`, `64`, `65`, `43` or `42`.
+    pub fn figure(&self) -> String {
+        let has_seventh = self.seventh.is_some();
+        let figure = match (has_seventh, self.inversion) {
+            (false, 0) => "",
+            (false, 1) => "6",
+            (false, _) => "64",
+            (true, 0) => match self.extension {
+                ChordExtension::Ninth => "9",
+                ChordExtension::Eleventh => "11",
+                ChordExtension::Thirteenth => "13",
+                _ => "7",
+            },
+            (true, 1) => "65",
+            (true, 2) => "43",
+            (true, _) => "42",
+        };
+        figure.to_string()
+    }
+
+    /// The key the numeral is built in: the key itself, or the key its applied target tonicizes.
+    fn local_key(&self, key: &KeySignature) -> KeySignature {
+        match &self.secondary {
+            Some(target) => {
+                let tonic = target.root_in(&target.local_key(key));
+                let mode = match target.triad {
+                    TriadQuality::Minor | TriadQuality::Diminished => Some(Mode::Aeolian),
+                    _ => None,
+                };
+                KeySignature::new_with_mode(tonic, mode)
+            }
+            None => key.clone(),
+        }
+    }
+
+    fn root_in(&self, key: &KeySignature) -> Pitch {
+        let (scale, degree, accidental) = match self.kind {
+            NumeralKind::AugmentedSixth(_) => (key.parallel_minor().pitches(), 6, 0),
+            _ => (key.pitches(), self.degree, self.accidental),
+        };
+        let diatonic = scale[(degree as usize + 6) % 7];
+        let leading_tone = self.kind == NumeralKind::Degree
+            && key.is_minor()
+            && degree == 7
+            && matches!(self.triad, TriadQuality::Minor | TriadQuality::Diminished);
+        Pitch::new(
+            diatonic.letter,
+            diatonic.accidental + accidental + leading_tone as i8,
+        )
+    }
+
+    fn numeral(&self) -> String {
+        const NUMERALS: [&str; 7] = ["I", "II", "III", "IV", "V", "VI", "VII"];
+        let numeral = NUMERALS[(self.degree as usize + 6) % 7];
+        match self.triad {
+            TriadQuality::Minor | TriadQuality::Diminished => numeral.to_lowercase(),
+            _ => numeral.to_string(),
+        }
+    }
+
+    fn quality_symbol(&self) -> &'static str {
+        match (self.triad, self.seventh) {
+            (TriadQuality::Diminished, Some(SeventhQuality::Minor)) => "ø",
+            (TriadQuality::Diminished, _) => "°",
+            (TriadQuality::Augmented, Some(SeventhQuality::Major)) => "+M",
+            (TriadQuality::Augmented, _) => "+",
+            (_, Some(SeventhQuality::Major)) => "M",
+            _ => "",
+        }
+    }
+}
+
+impl fmt::Display for RomanNumeral {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            NumeralKind::AugmentedSixth(AugmentedSixth::Italian) => return write!(f, "It+6"),
+            NumeralKind::AugmentedSixth(AugmentedSixth::French) => return write!(f, "Fr+6"),
+            NumeralKind::AugmentedSixth(AugmentedSixth::German) => return write!(f, "Ger+6"),
+            NumeralKind::Neapolitan => return write!(f, "N{}", self.figure()),
+            NumeralKind::Degree => {}
+        }
+
+        let accidental = if self.accidental < 0 { "b" } else { "#" };
+        for _ in 0..self.accidental.unsigned_abs() {
+            write!(f, "{}", accidental)?;
+        }
+        write!(f, "{}{}", self.numeral(), self.quality_symbol())?;
+        match self.extension {
+            ChordExtension::Sixth => write!(f, "add6")?,
+            ChordExtension::SixNine => write!(f, "add69")?,
+            _ => {}
+        }
+        if self.triad == TriadQuality::Power {
+            write!(f, "5")?;
+        }
+        write!(f, "{}", self.figure())?;
+        match self.suspension {
+            Some(Suspension::Second) => write!(f, "sus2")?,
+            Some(Suspension::Fourth) => write!(f, "sus4")?,
+            None => {}
+        }
+        if let Some(target) = &self.secondary {
+            write!(f, "/{}", target)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RomanNumeral {
+    type Err = HarmonyError;
+
+    fn from_str(numeral: &str) -> Result<Self, Self::Err> {
+        Self::parse(numeral)
+    }
+}
+
+/// The scale degree a pitch's letter names in the key and its alteration against the key.
+///
+/// The raised seventh of a minor key is not counted as an alteration, so its leading-tone
+/// chord is `vii°` rather than `#vii°`. The raised sixth still is, as in `#vi°`; only
+/// [`is_diatonic`] accepts it as a tone of the key.
+pub(crate) fn degree_of(pitch: Pitch, key: &KeySignature) -> (u8, i8) {
+    let scale = key.pitches();
+    let steps = (pitch.letter.index() - scale[0].letter.index()).rem_euclid(7) as usize;
+    let diatonic = scale[steps];
+    let mut accidental = pitch.accidental - diatonic.accidental;
+    if key.is_minor() && steps == 6 && accidental == 1 {
+        accidental = 0;
+    }
+    (steps as u8 + 1, accidental)
+}
+
+/// Whether a pitch belongs to the key, counting the raised sixth and seventh of minor keys.
+pub(crate) fn is_diatonic(pitch: Pitch, key: &KeySignature) -> bool {
+    let (degree, accidental) = degree_of(pitch, key);
+    accidental == 0 || (key.is_minor() && degree == 6 && accidental == 1)
+}
+
+/// The triad the key builds on a degree, using the harmonic minor's raised seventh for the
+/// dominant and leading-tone triads of minor keys.
+pub(crate) fn diatonic_triad(degree: u8, key: &KeySignature) -> TriadQuality {
+    let scale = key.pitches();
+    let raise_seventh = key.is_minor() && matches!(degree, 5 | 7);
+    let pitch = |step: usize| {
+        let index = (degree as usize - 1 + step) % 7;
+        let class = scale[index].into_u8();
+        if raise_seventh && index == 6 {
+            (class + 1) % 12
+        } else {
+            class
+        }
+    };
+    let third = (pitch(2) + 12 - pitch(0)) % 12;
+    let fifth = (pitch(4) + 12 - pitch(0)) % 12;
+    match (third, fifth) {
+        (4, 7) => TriadQuality::Major,
+        (3, 7) => TriadQuality::Minor,
+        (3, 6) => TriadQuality::Diminished,
+        _ => TriadQuality::Augmented,
+    }
+}
+
+/// Read a chromatic dominant-type chord as `V` or `vii` of a diatonic major or minor triad.
+fn applied_target(
+    numeral: &RomanNumeral,
+    root: Pitch,
+    key: &KeySignature,
+) -> Option<(u8, RomanNumeral)> {
+    let dominant = numeral.triad == TriadQuality::Major
+        && matches!(numeral.seventh, None | Some(SeventhQuality::Minor))
+        && numeral.suspension.is_none();
+    let leading_tone = numeral.triad == TriadQuality::Diminished;
+    let (degree, letter_steps, semitones) = if dominant {
+        (5, -4, 5)
+    } else if leading_tone {
+        (7, 1, 1)
+    } else {
+        return None;
+    };
+
+    let target = Pitch::from_u8_with_letter(
+        (root.into_u8() + semitones) % 12,
+        root.letter.offset(letter_steps),
+    );
+    let (target_degree, target_accidental) = degree_of(target, key);
+    if target_degree == 1 || target_accidental != 0 {
+        return None;
+    }
+    let triad = diatonic_triad(target_degree, key);
+    if !matches!(triad, TriadQuality::Major | TriadQuality::Minor) {
+        return None;
+    }
+    Some((degree, RomanNumeral::new(target_degree, triad)))
+}
+
+/// Which chord member the bass holds, counting only the root, third, fifth and seventh.
+fn bass_member(chord: &Chord) -> u8 {
+    let tones = chord.formula().tones();
+    match tones.get(chord.inversion() as usize) {
+        Some(tone) if chord.inversion() > 0 => match tone.degree() {
+            2..=4 => 1,
+            5 => 2,
+            _ => 3,
+        },
+        _ => 0,
+    }
+}
@@ -0,0 +1,392 @@
+use crate::chord::{Chord, ChordTone};
+use crate::harmony::VoiceLeadingError;
+use crate::note::{Note, NoteLetter, Pitch};
+
+/// Penalty for moving parallel fifths or octaves between two voices.
+const PARALLEL_PENALTY: u32 = 1000;
+/// Penalty for moving a voice off a tone the next chord shares.
+const COMMON_TONE_PENALTY: u32 = 3;
+/// Penalty for doubling a third or seventh, which resolve in one direction.
+const DOUBLING_PENALTY: u32 = 4;
+/// The most voices led at once; the voicings to search grow exponentially with each voice.
+pub const MAX_VOICES: usize = 8;
+/// How many of the cheapest voicings of one chord are followed to the next.
+const BEAM_WIDTH: usize = 64;
+
+/// Chooses voicings for a sequence of chords that move each voice as little as possible.
+///
+/// The lowest voice always takes the chord's bass, so inversions and slash chords keep their
+/// bass. The upper voices stay in order without unisons, each within an octave of the voice
+/// below. When a chord has more tones than voices, the fifth is dropped first and then the root,
+/// and when it has fewer, roots and fifths are doubled before thirds and sevenths.
+///
+/// ```
+/// use rust_music_theory::chord::Chord;
+/// use rust_music_theory::harmony::VoiceLeader;
+///
+/// let chords: Vec<Chord> = ["C", "F", "G7", "C"]
+///     .iter()
+///     .map(|symbol| Chord::parse(symbol).unwrap())
+///     .collect();
+/// let leading = VoiceLeader::new(4).lead(&chords).unwrap();
+/// assert_eq!(leading.voices().len(), 4);
+/// assert_eq!(leading.voices()[0].len(), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoiceLeader {
+    voices: usize,
+    lowest: Note,
+    highest: Note,
+    avoid_parallels: bool,
+    keep_common_tones: bool,
+}
+
+/// The voices chosen for a sequence of chords.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VoiceLeading {
+    voices: Vec<Vec<Note>>,
+    motion: u32,
+}
+
+/// A chord tone the voices can take, with the rank in which it is kept.
+#[derive(Debug, Clone, Copy)]
+struct Tone {
+    pitch: Pitch,
+    class: u8,
+    priority: u8,
+    doubling_penalty: u32,
+}
+
+/// A candidate voicing as MIDI pitches from the bass up, with the tones they sound.
+#[derive(Debug, Clone)]
+struct Candidate {
+    pitches: Vec<u8>,
+    tones: Vec<usize>,
+    penalty: u32,
+}
+
+impl VoiceLeader {
+    /// Lead a number of voices between C3 and C6, avoiding parallel fifths and octaves and
+    /// holding common tones.
+    pub fn new(voices: usize) -> Self {
+        VoiceLeader {
+            voices,
+            lowest: Note::new(Pitch::new(NoteLetter::C, 0), 3),
+            highest: Note::new(Pitch::new(NoteLetter::C, 0), 6),
+            avoid_parallels: true,
+            keep_common_tones: true,
+        }
+    }
+
+    /// Keep every voice between two notes, inclusive.
+    pub fn range(mut self, lowest: Note, highest: Note) -> Self {
+        self.lowest = lowest;
+        self.highest = highest;
+        self
+    }
+
+    /// Whether to avoid parallel fifths and octaves where another voicing allows it.
+    pub fn avoid_parallels(mut self, avoid: bool) -> Self {
+        self.avoid_parallels = avoid;
+        self
+    }
+
+    /// Whether to prefer holding tones the next chord shares over moving less in total.
+    pub fn keep_common_tones(mut self, keep: bool) -> Self {
+        self.keep_common_tones = keep;
+        self
+    }
+
+    /// Choose a voicing for every chord, minimising the total motion of the voices.
+    ///
+    /// Only the cheapest paths so far are followed to each next chord, so chords with many
+    /// voicings may settle for a good path rather than the best.
+    ///
+    /// # Errors
+    ///
+    /// Fails without voices, with an empty range, with more voices than [`MAX_VOICES`] or than
+    /// the range has notes, or when a chord's bass and kept tones do not fit in the range.
+    pub fn lead(&self, chords: &[Chord]) -> Result<VoiceLeading, VoiceLeadingError> {
+        if self.voices == 0 {
+            return Err(VoiceLeadingError::NoVoices);
+        }
+        let lowest = self.lowest.midi_pitch();
+        let highest = self.highest.midi_pitch();
+        if lowest > highest {
+            return Err(VoiceLeadingError::EmptyRange {
+                lowest: self.lowest.clone(),
+                highest: self.highest.clone(),
+            });
+        }
+        let max = MAX_VOICES.min((highest - lowest) as usize + 1);
+        if self.voices > max {
+            return Err(VoiceLeadingError::TooManyVoices {
+                voices: self.voices,
+                max,
+            });
+        }
+
+        let mut tones = Vec::with_capacity(chords.len());
+        let mut candidates = Vec::with_capacity(chords.len());
+        for (index, chord) in chords.iter().enumerate() {
+            let chord_tones = self.chord_tones(chord);
+            let voicings = self.voicings(&chord_tones, lowest, highest);
+            if voicings.is_empty() {
+                return Err(VoiceLeadingError::NoVoicing { index });
+            }
+            tones.push(chord_tones);
+            candidates.push(voicings);
+        }
+        if candidates.is_empty() {
+            return Ok(VoiceLeading {
+                voices: vec![Vec::new(); self.voices],
+                motion: 0,
+            });
+        }
+
+        // Find the cheapest path through the candidates of every chord.
+        let center = (lowest as u32 + highest as u32) / 2;
+        let mut costs: Vec<u32> = candidates[0]
+            .iter()
+            .map(|candidate| candidate.penalty + spread(candidate, center))
+            .collect();
+        let mut back: Vec<Vec<usize>> = Vec::with_capacity(candidates.len());
+        for step in 1..candidates.len() {
+            let mut beam: Vec<usize> = (0..costs.len()).collect();
+            beam.sort_by_key(|&index| costs[index]);
+            beam.truncate(BEAM_WIDTH);
+            let mut next_costs = Vec::with_capacity(candidates[step].len());
+            let mut links = Vec::with_capacity(candidates[step].len());
+            for next in &candidates[step] {
+                let mut best = (usize::MAX, u32::MAX);
+                for &index in &beam {
+                    let previous = &candidates[step - 1][index];
+                    // Penalties only add to the distance, so skip paths that cannot win.
+                    if costs[index] + distance(previous, next) > best.1 {
+                        continue;
+                    }
+                    let cost = costs[index] + self.transition(previous, next, &tones[step]);
+                    if (cost, index) < (best.1, best.0) {
+                        best = (index, cost);
+                    }
+                }
+                let (from, cost) = best;
+                next_costs.push(cost + next.penalty);
+                links.push(from);
+            }
+            costs = next_costs;
+            back.push(links);
+        }
+
+        let mut choice = (0..costs.len()).min_by_key(|&i| costs[i]).unwrap_or(0);
+        let mut path = vec![choice; candidates.len()];
+        for step in (1..candidates.len()).rev() {
+            choice = back[step - 1][choice];
+            path[step - 1] = choice;
+        }
+
+        let mut voices = vec![Vec::with_capacity(chords.len()); self.voices];
+        let mut motion = 0;
+        for (step, &choice) in path.iter().enumerate() {
+            let candidate = &candidates[step][choice];
+            for (voice, (&midi, &tone)) in
+                candidate.pitches.iter().zip(&candidate.tones).enumerate()
+            {
+                voices[voice].push(Note::from_midi_with_pitch(
+                    tones[step][tone].pitch,
+                    midi as i16,
+                ));
+            }
+            if step > 0 {
+                motion += distance(&candidates[step - 1][path[step - 1]], candidate);
+            }
+        }
+        Ok(VoiceLeading { voices, motion })
+    }
+
+    /// The chord's tones with the bass first, then in the order they are kept.
+    fn chord_tones(&self, chord: &Chord) -> Vec<Tone> {
+        let root = chord.root();
+        let mut tones: Vec<Tone> = chord
+            .formula()
+            .tones()
+            .iter()
+            .map(|tone| {
+                let class = (root.into_u8() as i16 + tone.semitones()).rem_euclid(12) as u8;
+                Tone {
+                    pitch: Pitch::from_u8_with_letter(
+                        class,
+                        root.letter.offset(tone.letter_offset()),
+                    ),
+                    class,
+                    priority: priority(*tone),
+                    doubling_penalty: match tone.degree() {
+                        3 | 7 => DOUBLING_PENALTY,
+                        _ => 0,
+                    },
+                }
+            })
+            .collect();
+        tones.sort_by_key(|tone| tone.priority);
+
+        let bass = chord.bass().unwrap_or(root);
+        match tones.iter().position(|tone| tone.class == bass.into_u8()) {
+            Some(index) => {
+                let tone = tones.remove(index);
+                tones.insert(0, tone);
+            }
+            None => tones.insert(
+                0,
+                Tone {
+                    pitch: bass,
+                    class: bass.into_u8(),
+                    priority: 0,
+                    doubling_penalty: DOUBLING_PENALTY,
+                },
+            ),
+        }
+        tones
+    }
+
+    /// Every voicing with the bass in the lowest voice and all kept tones sounding.
+    ///
+    /// The upper voices span no more octaves than they need to sound every kept tone, though
+    /// always up to two octaves.
+    fn voicings(&self, tones: &[Tone], lowest: u8, highest: u8) -> Vec<Candidate> {
+        let kept = tones.len().min(self.voices);
+        let octaves = (self.voices - 1).div_ceil(kept);
+        let span = (12 * octaves as u8).max(24);
+        let mut voicings = Vec::new();
+        for bass in lowest..=highest {
+            if bass % 12 != tones[0].class {
+                continue;
+            }
+            let mut candidate = Candidate {
+                pitches: vec![bass],
+                tones: vec![0],
+                penalty: 0,
+            };
+            self.stack(&tones[..kept], highest, span, &mut candidate, &mut voicings);
+        }
+        voicings
+    }
+
+    fn stack(
+        &self,
+        tones: &[Tone],
+        highest: u8,
+        span: u8,
+        candidate: &mut Candidate,
+        voicings: &mut Vec<Candidate>,
+    ) {
+        if candidate.pitches.len() == self.voices {
+            if (0..tones.len()).all(|tone| candidate.tones.contains(&tone)) {
+                let mut done = candidate.clone();
+                for (index, tone) in tones.iter().enumerate() {
+                    let count = done.tones.iter().filter(|&&t| t == index).count() as u32;
+                    done.penalty += count.saturating_sub(1) * tone.doubling_penalty;
+                }
+                voicings.push(done);
+            }
+            return;
+        }
+        let below = *candidate.pitches.last().unwrap_or(&0);
+        // The voice above the bass may lie further off; the others stay within an octave.
+        let reach = if candidate.pitches.len() == 1 { 24 } else { 12 };
+        let mut top = highest.min(below.saturating_add(reach));
+        if let Some(&first) = candidate.pitches.get(1) {
+            top = top.min(first.saturating_add(span));
+        }
+        for midi in below + 1..=top {
+            if let Some(index) = tones.iter().position(|tone| tone.class == midi % 12) {
+                candidate.pitches.push(midi);
+                candidate.tones.push(index);
+                self.stack(tones, highest, span, candidate, voicings);
+                candidate.pitches.pop();
+                candidate.tones.pop();
+            }
+        }
+    }
+
+    fn transition(&self, previous: &Candidate, next: &Candidate, next_tones: &[Tone]) -> u32 {
+        let mut cost = distance(previous, next);
+        if self.keep_common_tones {
+            for (voice, (&from, &to)) in previous.pitches.iter().zip(&next.pitches).enumerate() {
+                let shared = next_tones.iter().any(|tone| tone.class == from % 12);
+                if voice > 0 && shared && from != to {
+                    cost += COMMON_TONE_PENALTY;
+                }
+            }
+        }
+        if self.avoid_parallels {
+            for lower in 0..self.voices {
+                for upper in lower + 1..self.voices {
+                    let before = previous.pitches[upper] as i16 - previous.pitches[lower] as i16;
+                    let after = next.pitches[upper] as i16 - next.pitches[lower] as i16;
+                    let perfect = matches!(before % 12, 0 | 7) && before % 12 == after % 12;
+                    let moved = previous.pitches[lower] != next.pitches[lower];
+                    if perfect && moved {
+                        cost += PARALLEL_PENALTY;
+                    }
+                }
+            }
+        }
+        cost
+    }
+}
+
+impl VoiceLeading {
+    /// The notes of every voice, from the bass up, with one note per chord.
+    pub fn voices(&self) -> &[Vec<Note>] {
+        &self.voices
+    }
+
+    /// The voicing of one chord, from the bass up.
+    pub fn chord(&self, index: usize) -> Option<Vec<Note>> {
+        self.voices
+            .iter()
+            .map(|voice| voice.get(index).cloned())
+            .collect()
+    }
+
+    /// The voicings of every chord in order, from the bass up.
+    pub fn chords(&self) -> Vec<Vec<Note>> {
+        let length = self.voices.first().map_or(0, Vec::len);
+        (0..length).filter_map(|index| self.chord(index)).collect()
+    }
+
+    /// The total distance the voices move, in semitones.
+    pub fn total_motion(&self) -> u32 {
+        self.motion
+    }
+}
+
+/// The rank in which a chord tone is kept when there are fewer voices than tones.
+fn priority(tone: ChordTone) -> u8 {
+    match (tone.degree(), tone.alteration()) {
+        (2, _) | (3, _) | (4, _) => 1,
+        (6, _) | (7, _) => 2,
+        (5, 0) => 5,
+        (1, _) => 4,
+        _ => 3,
+    }
+}
+
+/// How far a first voicing lies from the middle of the range and how widely it is spread.
+fn spread(candidate: &Candidate, center: u32) -> u32 {
+    candidate
+        .pitches
+        .iter()
+        .skip(1)
+        .map(|&midi| (midi as i32 - center as i32).unsigned_abs())
+        .sum()
+}
+
+fn distance(previous: &Candidate, next: &Candidate) -> u32 {
+    previous
+        .pitches
+        .iter()
+        .zip(&next.pitches)
+        .map(|(&from, &to)| (from as i32 - to as i32).unsigned_abs())
+        .sum()
+}
@@ -2,6 +2,7 @@
 
 mod errors;
 mod interval;
+mod parser;
 
 pub use errors::IntervalError;
 pub use interval::{Interval, Number, Quality, Step};
@@ -1,3 +1,4 @@
+use crate::interval::{Number, Quality};
 use std::error;
 use std::fmt;
 
@@ -5,11 +6,32 @@ use std::fmt;
 #[derive(Debug, Clone)]
 pub enum IntervalError {
     InvalidInterval,
+    InvalidQuality { quality: Quality, number: Number },
+    EmptyName,
+    UnknownQuality(String),
+    UnknownNumber(String),
 }
 
 impl fmt::Display for IntervalError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Invalid interval!")
+        match self {
+            IntervalError::InvalidInterval => write!(f, "Invalid interval!"),
+            IntervalError::InvalidQuality { quality, number } => write!(
+                f,
+                "Invalid interval: quality {} cannot be applied to number {}",
+                quality, number
+            ),
+            IntervalError::EmptyName => write!(f, "Interval name is empty"),
+            IntervalError::UnknownQuality(quality) if quality.is_empty() => {
+                write!(f, "Missing interval quality")
+            }
+            IntervalError::UnknownQuality(quality) => {
+                write!(f, "Unknown interval quality: {}", quality)
+            }
+            IntervalError::UnknownNumber(number) => {
+                write!(f, "Unknown interval number: {}", number)
+            }
+        }
     }
 }
 
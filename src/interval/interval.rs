@@ -1,7 +1,11 @@
 use crate::interval::errors::IntervalError;
+use crate::interval::parser::parse_interval;
 use crate::note::{Note, Pitch};
+use crate::scale::Direction;
+use std::convert::TryFrom;
 use std::fmt;
 use std::fmt::Display;
+use std::str::FromStr;
 use strum_macros::Display;
 
 /// The quality of an interval; major, minor, etc.
@@ -13,6 +17,10 @@ pub enum Quality {
     Minor,
     Augmented,
     Diminished,
+    /// Two semitones wider than the perfect or major form.
+    DoublyAugmented,
+    /// Two semitones narrower than the perfect form or three narrower than the major form.
+    DoublyDiminished,
 }
 
 impl Display for Quality {
@@ -23,6 +31,8 @@ impl Display for Quality {
             Quality::Minor => "m",
             Quality::Augmented => "A",
             Quality::Diminished => "d",
+            Quality::DoublyAugmented => "AA",
+            Quality::DoublyDiminished => "dd",
         };
         write!(f, "{}", string)
     }
@@ -41,6 +51,110 @@ pub enum Number {
     Octave,
 }
 
+impl Number {
+    /// The size of the number counted inclusively, where a unison is 1 and an octave is 8.
+    pub fn ordinal(self) -> u8 {
+        self.steps() + 1
+    }
+
+    /// The number of letter names spanned above the lower note.
+    pub(crate) fn steps(self) -> u8 {
+        match self {
+            Number::Unison => 0,
+            Number::Second => 1,
+            Number::Third => 2,
+            Number::Fourth => 3,
+            Number::Fifth => 4,
+            Number::Sixth => 5,
+            Number::Seventh => 6,
+            Number::Octave => 7,
+        }
+    }
+
+    /// The number of letter names the upper note moves, wrapping at the octave.
+    pub(crate) fn letter_steps(self) -> i16 {
+        (self.steps() % 7) as i16
+    }
+
+    /// The number spanning the given count of letter names, from zero (unison) to seven.
+    pub(crate) fn from_steps(steps: u8) -> Option<Self> {
+        match steps {
+            0 => Some(Number::Unison),
+            1 => Some(Number::Second),
+            2 => Some(Number::Third),
+            3 => Some(Number::Fourth),
+            4 => Some(Number::Fifth),
+            5 => Some(Number::Sixth),
+            6 => Some(Number::Seventh),
+            7 => Some(Number::Octave),
+            _ => None,
+        }
+    }
+
+    /// Whether the number takes perfect rather than major and minor qualities.
+    pub fn is_perfect(self) -> bool {
+        matches!(
+            self,
+            Number::Unison | Number::Fourth | Number::Fifth | Number::Octave
+        )
+    }
+
+    /// The semitone size of the perfect or major form of this number.
+    pub(crate) fn natural_semitones(self) -> i16 {
+        match self {
+            Number::Unison => 0,
+            Number::Second => 2,
+            Number::Third => 4,
+            Number::Fourth => 5,
+            Number::Fifth => 7,
+            Number::Sixth => 9,
+            Number::Seventh => 11,
+            Number::Octave => 12,
+        }
+    }
+
+    /// The semitone distance of `quality` from the natural size of this number, if the quality
+    /// applies to it.
+    pub(crate) fn quality_deviation(self, quality: Quality) -> Option<i16> {
+        match (self.is_perfect(), quality) {
+            (true, Quality::DoublyDiminished) => Some(-2),
+            (true, Quality::Diminished) => Some(-1),
+            (true, Quality::Perfect) => Some(0),
+            (false, Quality::DoublyDiminished) => Some(-3),
+            (false, Quality::Diminished) => Some(-2),
+            (false, Quality::Minor) => Some(-1),
+            (false, Quality::Major) => Some(0),
+            (_, Quality::Augmented) => Some(1),
+            (_, Quality::DoublyAugmented) => Some(2),
+            _ => None,
+        }
+    }
+
+    /// The quality of this number when it is `deviation` semitones away from its natural size.
+    pub(crate) fn quality_from_deviation(self, deviation: i16) -> Option<Quality> {
+        if self.is_perfect() {
+            match deviation {
+                -2 => Some(Quality::DoublyDiminished),
+                -1 => Some(Quality::Diminished),
+                0 => Some(Quality::Perfect),
+                1 => Some(Quality::Augmented),
+                2 => Some(Quality::DoublyAugmented),
+                _ => None,
+            }
+        } else {
+            match deviation {
+                -3 => Some(Quality::DoublyDiminished),
+                -2 => Some(Quality::Diminished),
+                -1 => Some(Quality::Minor),
+                0 => Some(Quality::Major),
+                1 => Some(Quality::Augmented),
+                2 => Some(Quality::DoublyAugmented),
+                _ => None,
+            }
+        }
+    }
+}
+
 impl Display for Number {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let string = match self {
@@ -79,6 +193,8 @@ pub struct Interval {
     pub number: Number,
     /// The step of the interval.
     pub step: Option<Step>,
+    /// The number of whole octaves added to the simple interval, e.g. one for a major ninth.
+    pub octaves: u8,
 }
 
 impl Interval {
@@ -89,6 +205,7 @@ impl Interval {
             quality,
             number,
             step,
+            octaves: 0,
         }
     }
 
@@ -96,7 +213,7 @@ impl Interval {
     ///
     /// # Errors
     ///
-    /// Fails if `sc` is greater than 12.
+    /// Fails if `semi_tones` is empty.
     pub fn from_semitones(semi_tones: &[u8]) -> Result<Vec<Self>, IntervalError> {
         let mut intervals: Vec<Interval> = vec![];
 
@@ -112,16 +229,83 @@ impl Interval {
         Ok(intervals)
     }
 
-    /// Create an interval based on the number of semitones from the root.
+    /// Create a simple interval from its number and quality, deriving the semitone count.
+    ///
+    /// This reaches spellings that [`Interval::from_semitone`] never produces, such as the
+    /// augmented second of the harmonic minor scale. Use [`Interval::compound`] for intervals
+    /// wider than an octave.
     ///
     /// # Errors
     ///
-    /// Fails if `sc` is greater than 12.
+    /// Fails if the quality does not apply to the number, such as a major fifth, or if the
+    /// interval would be narrower than a unison, such as a diminished unison.
+    pub fn from_number_and_quality(
+        number: Number,
+        quality: Quality,
+    ) -> Result<Self, IntervalError> {
+        let semitones = number
+            .quality_deviation(quality)
+            .map(|deviation| number.natural_semitones() + deviation)
+            .filter(|semitones| *semitones >= 0)
+            .ok_or(IntervalError::InvalidQuality { quality, number })?;
+        let semitone_count = semitones as u8;
+
+        Ok(Interval {
+            semitone_count,
+            quality,
+            number,
+            step: simple_step(semitone_count),
+            octaves: 0,
+        })
+    }
+
+    /// Every spelling of an interval spanning the given number of semitones, ordered from the
+    /// smallest number to the largest.
+    ///
+    /// Six semitones, for example, is spelled as a doubly augmented third, an augmented fourth, a
+    /// diminished fifth, or a doubly diminished sixth.
+    pub fn enharmonic_spellings(semitone_count: u8) -> Vec<Self> {
+        let max_steps = (semitone_count as u16 / 12 + 1) * 7;
+        (0..=max_steps)
+            .filter_map(|steps| {
+                let (number, octaves) = match (steps / 7, steps % 7) {
+                    (0, simple) => (simple, 0),
+                    (octaves, 0) => (7, octaves - 1),
+                    (octaves, simple) => (simple, octaves),
+                };
+                let number = Number::from_steps(number as u8)?;
+                let natural = number.natural_semitones() + 12 * octaves as i16;
+                let quality = number.quality_from_deviation(semitone_count as i16 - natural)?;
+                let simple = Self::from_number_and_quality(number, quality).ok()?;
+                simple.compound(octaves as u8).ok()
+            })
+            .collect()
+    }
+
+    /// Parse an interval name in short (`m3`, `A4`, `M9`, `T`) or long (`minor sixth`,
+    /// `perfect eleventh`, `tritone`) form.
+    ///
+    /// Short forms are case-sensitive so that `M3` and `m3` stay distinct. The tritone parses as
+    /// a diminished fifth, matching [`Interval::from_semitone`].
+    pub fn parse(name: &str) -> Result<Self, IntervalError> {
+        parse_interval(name)
+    }
+
+    /// Create an interval based on the number of semitones from the root.
+    ///
+    /// Counts above an octave produce compound intervals, so 14 semitones is a major ninth and 24
+    /// semitones is a perfect fifteenth.
     pub fn from_semitone(sc: u8) -> Result<Self, IntervalError> {
         let (number, quality, mut step): (Number, Quality, Option<Step>);
         step = None;
 
-        match sc {
+        let (octaves, simple) = match (sc / 12, sc % 12) {
+            (0, _) => (0, sc),
+            (octaves, 0) => (octaves - 1, 12),
+            (octaves, simple) => (octaves, simple),
+        };
+
+        match simple {
             0 => {
                 number = Number::Unison;
                 quality = Quality::Perfect;
@@ -182,43 +366,157 @@ impl Interval {
             }
         };
 
+        if octaves > 0 {
+            step = None;
+        }
+
         Ok(Interval {
             semitone_count: sc,
             number,
             quality,
             step,
+            octaves,
+        })
+    }
+
+    /// Widen this interval by whole octaves, e.g. a major second into a major ninth.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the resulting semitone count does not fit in a `u8`.
+    pub fn compound(self, octaves: u8) -> Result<Self, IntervalError> {
+        let semitone_count = octaves
+            .checked_mul(12)
+            .and_then(|added| self.semitone_count.checked_add(added))
+            .ok_or(IntervalError::InvalidInterval)?;
+        if octaves == 0 {
+            return Ok(self);
+        }
+
+        // A unison widened by an octave is spelled as an octave, keeping one spelling per size.
+        let (number, octaves) = match self.number {
+            Number::Unison => (Number::Octave, self.octaves + octaves - 1),
+            number => (number, self.octaves + octaves),
+        };
+        Ok(Interval {
+            semitone_count,
+            quality: self.quality,
+            number,
+            step: None,
+            octaves,
         })
     }
 
+    /// Reduce a compound interval to its simple form within an octave.
+    pub fn simple(self) -> Self {
+        if self.octaves == 0 {
+            return self;
+        }
+        let semitone_count = self.semitone_count - self.octaves * 12;
+        Interval {
+            semitone_count,
+            quality: self.quality,
+            number: self.number,
+            step: simple_step(semitone_count),
+            octaves: 0,
+        }
+    }
+
+    /// Whether the interval spans more than an octave.
+    pub fn is_compound(&self) -> bool {
+        self.octaves > 0
+    }
+
+    /// The size of the interval counted inclusively, e.g. 9 for a ninth and 15 for two octaves.
+    pub fn ordinal(&self) -> u16 {
+        self.number.ordinal() as u16 + 7 * self.octaves as u16
+    }
+
     /// Creates an interval by inverting the given interval
     /// e.g. Perfect fifth (C to G) becomes a perfect fourth (G to C)
+    ///
+    /// Compound intervals are reduced to their simple form first, so a major ninth inverts to a
+    /// minor seventh.
     pub fn invert(interval: &Self) -> Result<Self, IntervalError> {
-        match (interval.semitone_count, interval.quality, interval.number) {
-            (6, Quality::Diminished, Number::Fifth) => {
-                return Ok(Self::new(
-                    6,
-                    Quality::Augmented,
-                    Number::Fourth,
-                    Some(Step::Tritone),
-                ));
-            }
-            (6, Quality::Augmented, Number::Fourth) => {
-                return Ok(Self::new(
-                    6,
-                    Quality::Diminished,
-                    Number::Fifth,
-                    Some(Step::Tritone),
-                ));
-            }
-            _ => {}
-        }
+        let simple = interval.simple();
+        let semitone_count = 12u8
+            .checked_sub(simple.semitone_count)
+            .ok_or(IntervalError::InvalidInterval)?;
+        let number = match simple.number {
+            Number::Unison => Number::Octave,
+            Number::Second => Number::Seventh,
+            Number::Third => Number::Sixth,
+            Number::Fourth => Number::Fifth,
+            Number::Fifth => Number::Fourth,
+            Number::Sixth => Number::Third,
+            Number::Seventh => Number::Second,
+            Number::Octave => Number::Unison,
+        };
+        let quality = match simple.quality {
+            Quality::Perfect => Quality::Perfect,
+            Quality::Major => Quality::Minor,
+            Quality::Minor => Quality::Major,
+            Quality::Augmented => Quality::Diminished,
+            Quality::Diminished => Quality::Augmented,
+            Quality::DoublyAugmented => Quality::DoublyDiminished,
+            Quality::DoublyDiminished => Quality::DoublyAugmented,
+        };
+
+        Ok(Self::new(
+            semitone_count,
+            quality,
+            number,
+            simple_step(semitone_count),
+        ))
+    }
+
+    /// Measure the spelled interval from one note to another.
+    ///
+    /// The number comes from the distance between the note letters and octaves, and the quality
+    /// from the semitone distance, so C#4 to Bb4 is a diminished seventh while C#4 to A#4 is a
+    /// major sixth. Intervals wider than an octave are compound, and the returned direction is
+    /// `Descending` when the second note is written below the first.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the spelling needs a quality beyond doubly augmented or doubly diminished.
+    pub fn between(
+        first_note: &Note,
+        second_note: &Note,
+    ) -> Result<(Self, Direction), IntervalError> {
+        let steps = diatonic_position(second_note) - diatonic_position(first_note);
+        let semitones = chromatic_position(second_note) - chromatic_position(first_note);
+        let (steps, semitones, direction) = if steps < 0 || (steps == 0 && semitones < 0) {
+            (-steps, -semitones, Direction::Descending)
+        } else {
+            (steps, semitones, Direction::Ascending)
+        };
+
+        let (number, octaves) = match (steps / 7, steps % 7) {
+            (0, simple) => (simple, 0),
+            (octaves, 0) => (7, octaves - 1),
+            (octaves, simple) => (simple, octaves),
+        };
+        let number = Number::from_steps(number as u8).ok_or(IntervalError::InvalidInterval)?;
+        let octaves = u8::try_from(octaves).map_err(|_| IntervalError::InvalidInterval)?;
+        let deviation = semitones - 12 * octaves as i32 - number.natural_semitones() as i32;
+        let quality = number
+            .quality_from_deviation(deviation as i16)
+            .ok_or(IntervalError::InvalidInterval)?;
+        let semitone_count = u8::try_from(semitones).map_err(|_| IntervalError::InvalidInterval)?;
 
-        let inverted = match interval.semitone_count {
-            0 => 12,
-            12 => 0,
-            semitones => 12 - semitones,
+        let interval = Interval {
+            semitone_count,
+            quality,
+            number,
+            step: if octaves == 0 {
+                simple_step(semitone_count)
+            } else {
+                None
+            },
+            octaves,
         };
-        Self::from_semitone(inverted)
+        Ok((interval, direction))
     }
 
     /// Move the given note up by this interval.
@@ -229,7 +527,7 @@ impl Interval {
             Number::Unison => 0,
             _ if pitch.letter.index() <= first_note.pitch.letter.index() => 1,
             _ => 0,
-        };
+        } + self.octaves as i16;
 
         Note {
             octave: first_note.octave + excess_octave,
@@ -245,7 +543,7 @@ impl Interval {
             Number::Unison => 0,
             _ if pitch.letter.index() >= first_note.pitch.letter.index() => 1,
             _ => 0,
-        };
+        } + self.octaves as i16;
 
         Note {
             octave: first_note.octave - excess_octave,
@@ -297,26 +595,62 @@ impl Default for Interval {
             quality: Quality::Perfect,
             number: Number::Unison,
             step: None,
+            octaves: 0,
         }
     }
 }
 
+impl FromStr for Interval {
+    type Err = IntervalError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::parse(name)
+    }
+}
+
+fn diatonic_position(note: &Note) -> i32 {
+    note.octave as i32 * 7 + note.pitch.letter.index() as i32
+}
+
+fn chromatic_position(note: &Note) -> i32 {
+    use crate::note::NoteLetter::*;
+    let natural = match note.pitch.letter {
+        C => 0,
+        D => 2,
+        E => 4,
+        F => 5,
+        G => 7,
+        A => 9,
+        B => 11,
+    };
+    note.octave as i32 * 12 + natural + note.pitch.accidental as i32
+}
+
+fn simple_step(semitone_count: u8) -> Option<Step> {
+    match semitone_count {
+        1 => Some(Step::Half),
+        2 => Some(Step::Whole),
+        6 => Some(Step::Tritone),
+        _ => None,
+    }
+}
+
 impl Display for Interval {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Interval {
-                semitone_count: _,
                 quality: Quality::Diminished,
                 number: Number::Fifth,
-                step: _,
+                octaves: 0,
+                ..
             } => write!(f, "T"),
             Interval {
-                semitone_count: _,
                 quality: Quality::Augmented,
                 number: Number::Fourth,
-                step: _,
+                octaves: 0,
+                ..
             } => write!(f, "T"),
-            _ => write!(f, "{}{}", self.quality, self.number),
+            _ => write!(f, "{}{}", self.quality, self.ordinal()),
         }
     }
 }
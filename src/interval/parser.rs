@@ -0,0 +1,128 @@
+use crate::interval::{Interval, IntervalError, Number, Quality};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::convert::TryFrom;
+
+lazy_static! {
+    static ref SHORT_NAME: Regex = Regex::new(r"^(AA|A|P|M|m|dd|d)([1-9][0-9]*)$").unwrap();
+    static ref ORDINAL: Regex = Regex::new(r"^([1-9][0-9]*)(st|nd|rd|th)?$").unwrap();
+}
+
+/// Parse a short (`m3`, `A4`, `M9`) or long (`minor sixth`, `perfect 11th`) interval name.
+pub(crate) fn parse_interval(input: &str) -> Result<Interval, IntervalError> {
+    let name = input.trim();
+    if name.is_empty() {
+        return Err(IntervalError::EmptyName);
+    }
+    if matches!(name, "T" | "TT") || name.eq_ignore_ascii_case("tritone") {
+        return Interval::from_semitone(6);
+    }
+
+    if let Some(captures) = SHORT_NAME.captures(name) {
+        let quality = match &captures[1] {
+            "AA" => Quality::DoublyAugmented,
+            "A" => Quality::Augmented,
+            "P" => Quality::Perfect,
+            "M" => Quality::Major,
+            "m" => Quality::Minor,
+            "dd" => Quality::DoublyDiminished,
+            "d" => Quality::Diminished,
+            _ => unreachable!("matched by the short-name regex"),
+        };
+        let ordinal = parse_ordinal(&captures[2])?;
+        return build(quality, ordinal);
+    }
+
+    let tokens = long_name_tokens(name);
+    let (number_token, quality_tokens) = tokens
+        .split_last()
+        .ok_or_else(|| IntervalError::UnknownNumber(name.to_string()))?;
+    let ordinal = parse_ordinal(number_token)?;
+    let quality = match quality_tokens.join(" ").as_str() {
+        "" => {
+            // Unisons, fourths, fifths and octaves are perfect unless stated otherwise.
+            let (number, _) = split_ordinal(ordinal);
+            if number.is_perfect() {
+                Quality::Perfect
+            } else {
+                return Err(IntervalError::UnknownQuality(String::new()));
+            }
+        }
+        "perfect" | "perf" | "per" => Quality::Perfect,
+        "major" | "maj" => Quality::Major,
+        "minor" | "min" => Quality::Minor,
+        "augmented" | "aug" => Quality::Augmented,
+        "diminished" | "dim" => Quality::Diminished,
+        "doubly augmented" | "double augmented" | "doubly aug" | "double aug" => {
+            Quality::DoublyAugmented
+        }
+        "doubly diminished" | "double diminished" | "doubly dim" | "double dim" => {
+            Quality::DoublyDiminished
+        }
+        other => return Err(IntervalError::UnknownQuality(other.to_string())),
+    };
+    build(quality, ordinal)
+}
+
+/// Lowercase the name and split it into words, separating abbreviations such as `maj7`.
+fn long_name_tokens(name: &str) -> Vec<String> {
+    let mut text = String::new();
+    let mut previous_alphabetic = None;
+    for character in name.chars() {
+        if matches!(character, '-' | '_') || character.is_whitespace() {
+            text.push(' ');
+            previous_alphabetic = None;
+            continue;
+        }
+        let alphabetic = character.is_alphabetic();
+        if previous_alphabetic == Some(true) && !alphabetic {
+            text.push(' ');
+        }
+        text.extend(character.to_lowercase());
+        previous_alphabetic = Some(alphabetic);
+    }
+    text.split_whitespace().map(str::to_string).collect()
+}
+
+fn parse_ordinal(token: &str) -> Result<u16, IntervalError> {
+    if let Some(captures) = ORDINAL.captures(token) {
+        return captures[1]
+            .parse::<u16>()
+            .map_err(|_| IntervalError::UnknownNumber(token.to_string()));
+    }
+    let ordinal = match token {
+        "unison" | "prime" => 1,
+        "second" => 2,
+        "third" => 3,
+        "fourth" => 4,
+        "fifth" => 5,
+        "sixth" => 6,
+        "seventh" => 7,
+        "octave" | "eighth" => 8,
+        "ninth" => 9,
+        "tenth" => 10,
+        "eleventh" => 11,
+        "twelfth" => 12,
+        "thirteenth" => 13,
+        "fourteenth" => 14,
+        "fifteenth" => 15,
+        _ => return Err(IntervalError::UnknownNumber(token.to_string())),
+    };
+    Ok(ordinal)
+}
+
+/// Split an inclusive ordinal into a simple number and a count of added octaves.
+fn split_ordinal(ordinal: u16) -> (Number, u16) {
+    let steps = ordinal - 1;
+    match (steps / 7, steps % 7) {
+        (0, simple) => (Number::from_steps(simple as u8).unwrap(), 0),
+        (octaves, 0) => (Number::Octave, octaves - 1),
+        (octaves, simple) => (Number::from_steps(simple as u8).unwrap(), octaves),
+    }
+}
+
+fn build(quality: Quality, ordinal: u16) -> Result<Interval, IntervalError> {
+    let (number, octaves) = split_ordinal(ordinal);
+    let octaves = u8::try_from(octaves).map_err(|_| IntervalError::InvalidInterval)?;
+    Interval::from_number_and_quality(number, quality)?.compound(octaves)
+}
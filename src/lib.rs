@@ -78,9 +78,13 @@
 
 extern crate strum;
 pub mod chord;
+pub mod fretboard;
+pub mod harmony;
 pub mod interval;
 pub mod note;
+pub mod pitch_class_set;
 pub mod scale;
+pub mod tuning;
 
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
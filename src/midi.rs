@@ -55,6 +55,32 @@
 //!     .save("song.mid")?;
 //! ```
 //!
+//! # Import
+//!
+//! [`MidiImport`] reads format 0 and 1 files back into timed notes, spelled with the file's key
+//! signatures, along with its tempo map, meters, and control and program changes:
+//!
+//! ```ignore
+//! use rust_music_theory::midi::MidiImport;
+//! use rust_music_theory::note::{KeyFinder, Notes};
+//!
+//! let song = MidiImport::load("song.mid")?;
+//! let key = &KeyFinder::default().rank(&song.notes())[0].key;
+//! ```
+//!
+//! # Offline Rendering
+//!
+//! Without MIDI devices, a [`render::Synth`] renders builders and files to WAV:
+//!
+//! ```ignore
+//! use rust_music_theory::midi::render::{Instrument, SampleFormat, Synth, Waveform};
+//!
+//! Synth::new()
+//!     .instrument(Instrument::new(Waveform::Saw))
+//!     .render(&chords)
+//!     .save_wav("song.wav", SampleFormat::Int16)?;
+//! ```
+//!
 //! # Real-Time Playback (optional feature)
 //!
 //! With the `midi-playback` feature, you can play notes on connected MIDI devices:
@@ -77,6 +103,8 @@ mod duration;
 pub(crate) mod event;
 mod export;
 mod file;
+mod import;
+pub mod render;
 mod types;
 
 #[cfg(feature = "midi-playback")]
@@ -86,6 +114,11 @@ pub use builder::{MidiBuilder, DEFAULT_PPQ};
 pub use duration::Duration;
 pub use export::{MidiExport, ToMidi};
 pub use file::MidiFile;
+pub use import::{
+    ControlChange, ImportedTrack, KeySignatureChange, MidiImport, MidiImportError, ProgramChange,
+    TempoChange, TimeSignatureChange, TimedNote,
+};
+pub use render::{Audio, Envelope, Instrument, SampleFormat, Synth, Waveform};
 pub use types::{Channel, Velocity};
 
 #[cfg(feature = "midi-playback")]
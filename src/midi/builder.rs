@@ -2,7 +2,9 @@
 
 use crate::midi::event::MidiEvent;
 use crate::midi::{Duration, Velocity, Channel};
-use crate::note::Notes;
+use crate::harmony::Progression;
+use crate::note::{KeySignature, Note, Notes};
+use crate::tuning::TuningSystem;
 
 /// Default PPQ (Pulses Per Quarter Note).
 pub const DEFAULT_PPQ: u16 = 480;
@@ -14,6 +16,8 @@ pub struct MidiBuilder {
     pub(crate) cursor: u32,
     pub(crate) ppq: u16,
     pub(crate) channel: Channel,
+    pub(crate) tuning: Option<TuningSystem>,
+    pub(crate) bends: [i16; 16],
 }
 
 impl MidiBuilder {
@@ -24,6 +28,8 @@ impl MidiBuilder {
             cursor: 0,
             ppq: DEFAULT_PPQ,
             channel: Channel::new(0).unwrap(),
+            tuning: None,
+            bends: [0; 16],
         }
     }
 
@@ -34,6 +40,8 @@ impl MidiBuilder {
             cursor: 0,
             ppq,
             channel: Channel::new(0).unwrap(),
+            tuning: None,
+            bends: [0; 16],
         }
     }
 
@@ -47,6 +55,30 @@ impl MidiBuilder {
         self.ppq
     }
 
+    /// Tune the notes added from now on with a tuning system.
+    ///
+    /// Each note is played on the nearest key, with a pitch bend event inserted whenever the
+    /// bend changes, assuming the General MIDI bend range of two semitones. A channel has one
+    /// bend, so as in MPE the notes added together are spread across channels: the first stays
+    /// on the track's channel and each further note takes the next channel, wrapping past 15.
+    /// Leave room after a tuned track's channel for its largest chord.
+    pub fn tuning(&mut self, tuning: TuningSystem) -> &mut Self {
+        self.tuning = Some(tuning);
+        self
+    }
+
+    /// The key and pitch bend that sound a note in the builder's tuning.
+    fn tuned_key(&self, note: &Note) -> (u8, i16) {
+        let tuning = match &self.tuning {
+            Some(tuning) => tuning,
+            None => return (note.midi_pitch(), 0),
+        };
+        let key = 69.0 + 12.0 * (note.frequency(tuning) / 440.0).log2();
+        let nearest = key.round().clamp(0.0, 127.0);
+        let bend = ((key - nearest) / 2.0 * 8192.0).round().clamp(-8192.0, 8191.0);
+        (nearest as u8, bend as i16)
+    }
+
     /// Add notes from anything implementing the Notes trait.
     ///
     /// All notes are played simultaneously (chord-style) for the given duration.
@@ -55,19 +87,33 @@ impl MidiBuilder {
         let ticks = duration.to_ticks(self.ppq);
         let note_off_tick = self.cursor + ticks;
 
-        for note in notes.notes() {
-            let pitch = note.midi_pitch();
+        for (index, note) in notes.notes().iter().enumerate() {
+            let (pitch, bend) = self.tuned_key(note);
+            let channel = match self.tuning {
+                Some(_) => self.channel.offset(index as u8),
+                None => self.channel,
+            };
+
+            let current = &mut self.bends[channel.value() as usize];
+            if bend != *current {
+                *current = bend;
+                self.events.push(MidiEvent::PitchBend {
+                    tick: self.cursor,
+                    channel,
+                    value: bend,
+                });
+            }
 
             self.events.push(MidiEvent::NoteOn {
                 tick: self.cursor,
-                channel: self.channel,
+                channel,
                 pitch,
                 velocity,
             });
 
             self.events.push(MidiEvent::NoteOff {
                 tick: note_off_tick,
-                channel: self.channel,
+                channel,
                 pitch,
             });
         }
@@ -121,6 +167,102 @@ impl MidiBuilder {
         });
         self
     }
+
+    /// Insert a key signature change at the current position.
+    ///
+    /// Keys beyond seven sharps or flats are written as their enharmonic equivalents.
+    pub fn key_signature(&mut self, key: &KeySignature) -> &mut Self {
+        let (sharps_flats, minor) = key.to_midi_enharmonic();
+        self.events.push(MidiEvent::KeySignature {
+            tick: self.cursor,
+            sharps_flats,
+            minor,
+        });
+        self
+    }
+
+    /// Name the track, as shown in a DAW's track list.
+    pub fn track_name(&mut self, name: &str) -> &mut Self {
+        self.events.push(MidiEvent::TrackName {
+            tick: self.cursor,
+            name: name.to_string(),
+        });
+        self
+    }
+
+    /// Name the instrument playing the track.
+    pub fn instrument_name(&mut self, name: &str) -> &mut Self {
+        self.events.push(MidiEvent::InstrumentName {
+            tick: self.cursor,
+            name: name.to_string(),
+        });
+        self
+    }
+
+    /// Insert a marker at the current position, such as a section name.
+    pub fn marker(&mut self, text: &str) -> &mut Self {
+        self.events.push(MidiEvent::Marker {
+            tick: self.cursor,
+            text: text.to_string(),
+        });
+        self
+    }
+
+    /// Insert a cue point at the current position.
+    pub fn cue_point(&mut self, text: &str) -> &mut Self {
+        self.events.push(MidiEvent::CuePoint {
+            tick: self.cursor,
+            text: text.to_string(),
+        });
+        self
+    }
+
+    /// Insert a lyric syllable at the current position.
+    pub fn lyric(&mut self, text: &str) -> &mut Self {
+        self.events.push(MidiEvent::Lyric {
+            tick: self.cursor,
+            text: text.to_string(),
+        });
+        self
+    }
+
+    /// Insert a text event at the current position.
+    pub fn text(&mut self, text: &str) -> &mut Self {
+        self.events.push(MidiEvent::Text {
+            tick: self.cursor,
+            text: text.to_string(),
+        });
+        self
+    }
+
+    /// Add every chord of a progression at its beat, starting from the current position.
+    ///
+    /// Each chord's symbol is written as a text event where it starts, and a time signature
+    /// event is inserted wherever the progression's meter changes, starting from 4/4. The
+    /// cursor is left at the end of the progression.
+    pub fn add_progression(&mut self, progression: &Progression, velocity: Velocity) -> &mut Self {
+        let origin = self.cursor;
+        let ppq = self.ppq as f64;
+        let to_tick = |beat: f64| origin + (beat * ppq).round() as u32;
+
+        let mut meter = (4, 4);
+        for bar in progression.bars() {
+            if bar.time_signature != meter {
+                meter = bar.time_signature;
+                self.at_tick(to_tick(bar.start));
+                self.time_signature(meter.0, meter.1);
+            }
+        }
+        for timed in progression {
+            let start = to_tick(timed.start);
+            let end = to_tick(timed.start + timed.beats);
+            self.at_tick(start);
+            self.text(&timed.chord.to_string());
+            self.add(&timed.chord, Duration::Ticks(end - start), velocity);
+        }
+        self.at_tick(to_tick(progression.total_beats()));
+        self
+    }
 }
 
 impl Default for MidiBuilder {
@@ -265,6 +407,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn add_progression_places_chords_on_their_beats() {
+        let mut builder = MidiBuilder::new();
+        let progression = Progression::parse("| Dm7 G7 | 3/4 Cmaj7 |").unwrap();
+        let velocity = Velocity::new(100).unwrap();
+
+        builder.add_progression(&progression, velocity);
+
+        let note_ons: Vec<u32> = builder.events.iter()
+            .filter(|e| matches!(e, MidiEvent::NoteOn { .. }))
+            .map(|e| e.tick())
+            .collect();
+        assert_eq!(note_ons, vec![0, 0, 0, 0, 960, 960, 960, 960, 1920, 1920, 1920, 1920]);
+        let symbols: Vec<(u32, &str)> = builder.events.iter()
+            .filter_map(|e| match e {
+                MidiEvent::Text { tick, text } => Some((*tick, text.as_str())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(symbols, vec![(0, "Dm7"), (960, "G7"), (1920, "Cmaj7")]);
+        assert!(builder.events.iter().any(|e| matches!(
+            e,
+            MidiEvent::TimeSignature { tick: 1920, numerator: 3, denominator: 4 }
+        )));
+        assert_eq!(builder.cursor(), 3360);
+    }
+
     #[test]
     fn tempo_change_mid_track() {
         let mut builder = MidiBuilder::new();
@@ -284,4 +453,86 @@ mod tests {
         assert_eq!(tempo_events[0].tick(), 0);
         assert_eq!(tempo_events[1].tick(), 1920); // After whole note
     }
+
+    #[test]
+    fn tuning_bends_notes_off_the_equal_tempered_keys() {
+        use crate::tuning::TuningSystem;
+
+        let mut builder = MidiBuilder::new();
+        let velocity = Velocity::new(100).unwrap();
+        let a4: Note = "A4".parse().unwrap();
+        let e5: Note = "E5".parse().unwrap();
+
+        builder.tuning(TuningSystem::pythagorean());
+        builder.add(&a4, Duration::Quarter, velocity);
+        builder.add(&e5, Duration::Quarter, velocity);
+        builder.add(&a4, Duration::Quarter, velocity);
+
+        let bends: Vec<(u32, i16)> = builder.events.iter()
+            .filter_map(|e| match e {
+                MidiEvent::PitchBend { tick, value, .. } => Some((*tick, *value)),
+                _ => None,
+            })
+            .collect();
+        // The pure fifth E5 is about two cents sharp: 1.955 / 200 * 8192 = 80.
+        assert_eq!(bends, vec![(480, 80), (960, 0)]);
+        assert!(builder.events.contains(&MidiEvent::NoteOn {
+            tick: 480,
+            channel: builder.channel,
+            pitch: 76,
+            velocity,
+        }));
+    }
+
+    #[test]
+    fn tuning_spreads_chord_notes_across_channels() {
+        use crate::tuning::TuningSystem;
+
+        let mut builder = MidiBuilder::new();
+        let chord = Chord::new(Pitch::from(C), Quality::Major, Number::Triad);
+        let velocity = Velocity::new(100).unwrap();
+        let channel = |c| Channel::new(c).unwrap();
+
+        builder.tuning(TuningSystem::just_intonation(Pitch::from(C)));
+        builder.add(&chord, Duration::Quarter, velocity);
+
+        // With A4 at 440 Hz the just C is 264 Hz, 15.6 cents sharp. Its pure third E is 13.7
+        // cents lower, at 330 Hz, and its pure fifth G is 2 cents above the C's bend.
+        assert_eq!(builder.events, vec![
+            MidiEvent::PitchBend { tick: 0, channel: channel(0), value: 641 },
+            MidiEvent::NoteOn { tick: 0, channel: channel(0), pitch: 60, velocity },
+            MidiEvent::NoteOff { tick: 480, channel: channel(0), pitch: 60 },
+            MidiEvent::PitchBend { tick: 0, channel: channel(1), value: 80 },
+            MidiEvent::NoteOn { tick: 0, channel: channel(1), pitch: 64, velocity },
+            MidiEvent::NoteOff { tick: 480, channel: channel(1), pitch: 64 },
+            MidiEvent::PitchBend { tick: 0, channel: channel(2), value: 721 },
+            MidiEvent::NoteOn { tick: 0, channel: channel(2), pitch: 67, velocity },
+            MidiEvent::NoteOff { tick: 480, channel: channel(2), pitch: 67 },
+        ]);
+    }
+
+    #[test]
+    fn key_signature_and_text_events_at_cursor() {
+        use crate::note::NoteLetter;
+        use crate::scale::Mode;
+
+        let mut builder = MidiBuilder::new();
+        let chord = Chord::new(Pitch::from(C), Quality::Major, Number::Triad);
+        let velocity = Velocity::new(100).unwrap();
+
+        builder
+            .track_name("Piano")
+            .marker("A")
+            .add(&chord, Duration::Whole, velocity)
+            .marker("B")
+            .key_signature(&KeySignature::new_with_mode(Pitch::new(NoteLetter::C, 1), Some(Mode::Aeolian)))
+            .lyric("oh");
+
+        assert_eq!(builder.events[0], MidiEvent::TrackName { tick: 0, name: "Piano".to_string() });
+        assert_eq!(builder.events[1], MidiEvent::Marker { tick: 0, text: "A".to_string() });
+        assert!(builder.events.contains(&MidiEvent::Marker { tick: 1920, text: "B".to_string() }));
+        assert!(builder.events.contains(&MidiEvent::KeySignature { tick: 1920, sharps_flats: 4, minor: true }));
+        assert!(builder.events.contains(&MidiEvent::Lyric { tick: 1920, text: "oh".to_string() }));
+        assert_eq!(builder.cursor(), 1920);
+    }
 }
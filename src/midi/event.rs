@@ -18,6 +18,12 @@ pub enum MidiEvent {
         channel: Channel,
         pitch: u8,
     },
+    /// Pitch bend, from -8192 to 8191 with 0 at rest
+    PitchBend {
+        tick: u32,
+        channel: Channel,
+        value: i16,
+    },
     /// Tempo change (microseconds per beat)
     Tempo {
         tick: u32,
@@ -29,6 +35,42 @@ pub enum MidiEvent {
         numerator: u8,
         denominator: u8,
     },
+    /// Key signature change (sharps positive, flats negative)
+    KeySignature {
+        tick: u32,
+        sharps_flats: i8,
+        minor: bool,
+    },
+    /// Name of the track
+    TrackName {
+        tick: u32,
+        name: String,
+    },
+    /// Name of the instrument playing the track
+    InstrumentName {
+        tick: u32,
+        name: String,
+    },
+    /// Marker, such as a rehearsal letter or section name
+    Marker {
+        tick: u32,
+        text: String,
+    },
+    /// Cue point, such as a sound effect or an entrance
+    CuePoint {
+        tick: u32,
+        text: String,
+    },
+    /// Lyric syllable
+    Lyric {
+        tick: u32,
+        text: String,
+    },
+    /// Generic text, such as a chord symbol or a comment
+    Text {
+        tick: u32,
+        text: String,
+    },
 }
 
 impl MidiEvent {
@@ -37,8 +79,16 @@ impl MidiEvent {
         match self {
             MidiEvent::NoteOn { tick, .. } => *tick,
             MidiEvent::NoteOff { tick, .. } => *tick,
+            MidiEvent::PitchBend { tick, .. } => *tick,
             MidiEvent::Tempo { tick, .. } => *tick,
             MidiEvent::TimeSignature { tick, .. } => *tick,
+            MidiEvent::KeySignature { tick, .. } => *tick,
+            MidiEvent::TrackName { tick, .. } => *tick,
+            MidiEvent::InstrumentName { tick, .. } => *tick,
+            MidiEvent::Marker { tick, .. } => *tick,
+            MidiEvent::CuePoint { tick, .. } => *tick,
+            MidiEvent::Lyric { tick, .. } => *tick,
+            MidiEvent::Text { tick, .. } => *tick,
         }
     }
 
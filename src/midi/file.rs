@@ -5,18 +5,21 @@ use std::path::Path;
 
 use midly::{Format, Header, Smf, Timing, Track, TrackEvent, TrackEventKind, MidiMessage};
 use midly::num::{u4, u7, u15, u24, u28};
-use midly::MetaMessage;
+use midly::{MetaMessage, PitchBend};
 
 use crate::midi::{MidiBuilder, Channel};
 use crate::midi::event::MidiEvent;
+use crate::note::KeySignature;
 
 /// Combines multiple tracks into a complete MIDI file.
 #[derive(Debug, Clone)]
 pub struct MidiFile {
-    tracks: Vec<(MidiBuilder, Channel)>,
-    default_tempo: u16,
+    pub(crate) tracks: Vec<(MidiBuilder, Channel)>,
+    pub(crate) default_tempo: u16,
     default_time_sig: (u8, u8),
-    ppq: u16,
+    default_key_sig: Option<(i8, bool)>,
+    title: Option<String>,
+    pub(crate) ppq: u16,
 }
 
 impl MidiFile {
@@ -28,6 +31,8 @@ impl MidiFile {
             tracks: Vec::new(),
             default_tempo: 120,
             default_time_sig: (4, 4),
+            default_key_sig: None,
+            title: None,
             ppq: 480,
         }
     }
@@ -44,6 +49,20 @@ impl MidiFile {
         self
     }
 
+    /// Set the default key signature, which files otherwise leave out.
+    ///
+    /// Keys beyond seven sharps or flats are written as their enharmonic equivalents.
+    pub fn key_signature(mut self, key: &KeySignature) -> Self {
+        self.default_key_sig = Some(key.to_midi_enharmonic());
+        self
+    }
+
+    /// Set the title, written as the name of the tempo track.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
     /// Set the PPQ (Pulses Per Quarter Note).
     pub fn ppq(mut self, ppq: u16) -> Self {
         self.ppq = ppq;
@@ -76,7 +95,7 @@ impl MidiFile {
     }
 
     /// Convert to midly Smf structure.
-    fn to_smf(&self) -> Smf<'static> {
+    fn to_smf(&self) -> Smf<'_> {
         let header = Header::new(
             Format::Parallel,
             Timing::Metrical(u15::new(self.ppq)),
@@ -84,8 +103,15 @@ impl MidiFile {
 
         let mut smf = Smf::new(header);
 
-        // Track 0: Tempo and time signature
-        let tempo_track: Track = vec![
+        // Track 0: Title, tempo, time signature, and key signature
+        let mut tempo_track: Track = Vec::new();
+        if let Some(title) = &self.title {
+            tempo_track.push(TrackEvent {
+                delta: u28::new(0),
+                kind: TrackEventKind::Meta(MetaMessage::TrackName(title.as_bytes())),
+            });
+        }
+        tempo_track.extend([
             TrackEvent {
                 delta: u28::new(0),
                 kind: TrackEventKind::Meta(MetaMessage::Tempo(
@@ -101,11 +127,17 @@ impl MidiFile {
                     8,  // 32nd notes per quarter note
                 )),
             },
-            TrackEvent {
+        ]);
+        if let Some((sharps_flats, minor)) = self.default_key_sig {
+            tempo_track.push(TrackEvent {
                 delta: u28::new(0),
-                kind: TrackEventKind::Meta(MetaMessage::EndOfTrack),
-            },
-        ];
+                kind: TrackEventKind::Meta(MetaMessage::KeySignature(sharps_flats, minor)),
+            });
+        }
+        tempo_track.push(TrackEvent {
+            delta: u28::new(0),
+            kind: TrackEventKind::Meta(MetaMessage::EndOfTrack),
+        });
         smf.tracks.push(tempo_track);
 
         // Add note tracks
@@ -117,10 +149,13 @@ impl MidiFile {
         smf
     }
 
-    /// Convert a MidiBuilder to a midly Track.
-    fn builder_to_track(&self, builder: &MidiBuilder, channel: Channel) -> Track<'static> {
+    /// Convert a MidiBuilder to a midly Track, borrowing the builder's text.
+    ///
+    /// Channel messages go to the track's channel, offset by their own channel so that the
+    /// notes of a tuned chord keep their separate channels.
+    fn builder_to_track<'a>(&self, builder: &'a MidiBuilder, channel: Channel) -> Track<'a> {
         let mut track: Track = Vec::new();
-        let mut events = builder.events.clone();
+        let mut events: Vec<&MidiEvent> = builder.events.iter().collect();
 
         // Sort events by tick
         events.sort_by_key(|e| e.tick());
@@ -132,41 +167,78 @@ impl MidiFile {
             last_tick = event.tick();
 
             let track_event = match event {
-                MidiEvent::NoteOn { pitch, velocity, .. } => TrackEvent {
+                MidiEvent::NoteOn { channel: voice, pitch, velocity, .. } => TrackEvent {
                     delta: u28::new(delta),
                     kind: TrackEventKind::Midi {
-                        channel: u4::new(channel.value()),
+                        channel: u4::new(channel.offset(voice.value()).value()),
                         message: MidiMessage::NoteOn {
-                            key: u7::new(pitch),
+                            key: u7::new(*pitch),
                             vel: u7::new(velocity.value()),
                         },
                     },
                 },
-                MidiEvent::NoteOff { pitch, .. } => TrackEvent {
+                MidiEvent::NoteOff { channel: voice, pitch, .. } => TrackEvent {
                     delta: u28::new(delta),
                     kind: TrackEventKind::Midi {
-                        channel: u4::new(channel.value()),
+                        channel: u4::new(channel.offset(voice.value()).value()),
                         message: MidiMessage::NoteOff {
-                            key: u7::new(pitch),
+                            key: u7::new(*pitch),
                             vel: u7::new(0),
                         },
                     },
                 },
+                MidiEvent::PitchBend { channel: voice, value, .. } => TrackEvent {
+                    delta: u28::new(delta),
+                    kind: TrackEventKind::Midi {
+                        channel: u4::new(channel.offset(voice.value()).value()),
+                        message: MidiMessage::PitchBend {
+                            bend: PitchBend::from_int(*value),
+                        },
+                    },
+                },
                 MidiEvent::Tempo { microseconds_per_beat, .. } => TrackEvent {
                     delta: u28::new(delta),
                     kind: TrackEventKind::Meta(MetaMessage::Tempo(
-                        u24::new(microseconds_per_beat)
+                        u24::new(*microseconds_per_beat)
                     )),
                 },
                 MidiEvent::TimeSignature { numerator, denominator, .. } => TrackEvent {
                     delta: u28::new(delta),
                     kind: TrackEventKind::Meta(MetaMessage::TimeSignature(
-                        numerator,
-                        self.denominator_to_power(denominator),
+                        *numerator,
+                        self.denominator_to_power(*denominator),
                         24,
                         8,
                     )),
                 },
+                MidiEvent::KeySignature { sharps_flats, minor, .. } => TrackEvent {
+                    delta: u28::new(delta),
+                    kind: TrackEventKind::Meta(MetaMessage::KeySignature(*sharps_flats, *minor)),
+                },
+                MidiEvent::TrackName { name, .. } => TrackEvent {
+                    delta: u28::new(delta),
+                    kind: TrackEventKind::Meta(MetaMessage::TrackName(name.as_bytes())),
+                },
+                MidiEvent::InstrumentName { name, .. } => TrackEvent {
+                    delta: u28::new(delta),
+                    kind: TrackEventKind::Meta(MetaMessage::InstrumentName(name.as_bytes())),
+                },
+                MidiEvent::Marker { text, .. } => TrackEvent {
+                    delta: u28::new(delta),
+                    kind: TrackEventKind::Meta(MetaMessage::Marker(text.as_bytes())),
+                },
+                MidiEvent::CuePoint { text, .. } => TrackEvent {
+                    delta: u28::new(delta),
+                    kind: TrackEventKind::Meta(MetaMessage::CuePoint(text.as_bytes())),
+                },
+                MidiEvent::Lyric { text, .. } => TrackEvent {
+                    delta: u28::new(delta),
+                    kind: TrackEventKind::Meta(MetaMessage::Lyric(text.as_bytes())),
+                },
+                MidiEvent::Text { text, .. } => TrackEvent {
+                    delta: u28::new(delta),
+                    kind: TrackEventKind::Meta(MetaMessage::Text(text.as_bytes())),
+                },
             };
             track.push(track_event);
         }
@@ -275,4 +347,45 @@ mod tests {
             .count();
         assert_eq!(mtrk_count, 3);
     }
+
+    #[test]
+    fn title_and_key_signature_in_tempo_track() {
+        use crate::note::NoteLetter;
+
+        let file = MidiFile::new()
+            .title("Etude")
+            .key_signature(&KeySignature::new(Pitch::new(NoteLetter::B, -1)));
+        let smf = file.to_smf();
+        let kinds: Vec<_> = smf.tracks[0].iter().map(|e| e.kind).collect();
+
+        assert_eq!(kinds[0], TrackEventKind::Meta(MetaMessage::TrackName(b"Etude")));
+        assert_eq!(kinds[3], TrackEventKind::Meta(MetaMessage::KeySignature(-2, false)));
+        assert_eq!(kinds.len(), 5);
+        assert_eq!(MidiFile::new().to_smf().tracks[0].len(), 3);
+    }
+
+    #[test]
+    fn text_events_in_note_tracks() {
+        let mut track = MidiBuilder::new();
+        track
+            .track_name("Lead")
+            .instrument_name("Flute")
+            .marker("Verse")
+            .cue_point("Enter")
+            .text("C")
+            .lyric("la");
+        let file = MidiFile::new().track(track, Channel::new(0).unwrap());
+        let smf = file.to_smf();
+        let kinds: Vec<_> = smf.tracks[1].iter().map(|e| e.kind).collect();
+
+        assert_eq!(kinds, vec![
+            TrackEventKind::Meta(MetaMessage::TrackName(b"Lead")),
+            TrackEventKind::Meta(MetaMessage::InstrumentName(b"Flute")),
+            TrackEventKind::Meta(MetaMessage::Marker(b"Verse")),
+            TrackEventKind::Meta(MetaMessage::CuePoint(b"Enter")),
+            TrackEventKind::Meta(MetaMessage::Text(b"C")),
+            TrackEventKind::Meta(MetaMessage::Lyric(b"la")),
+            TrackEventKind::Meta(MetaMessage::EndOfTrack),
+        ]);
+    }
 }
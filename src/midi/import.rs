@@ -0,0 +1,505 @@
+//! Standard MIDI File import.
+
+use std::error;
+use std::fmt;
+use std::path::Path;
+
+use midly::{Format, MetaMessage, MidiMessage, Smf, Timing, TrackEventKind};
+
+use crate::midi::{Channel, Velocity};
+use crate::note::{KeySignature, Note, Notes, Spelling};
+
+/// An error while reading a Standard MIDI File.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MidiImportError {
+    /// The bytes are not a valid Standard MIDI File
+    Parse(String),
+    /// Format 2 files, whose tracks are independent sequences, are not supported
+    UnsupportedFormat,
+    /// Files timed in SMPTE frames rather than ticks per quarter note are not supported
+    UnsupportedTiming,
+    /// The file could not be read
+    Io(String),
+}
+
+impl fmt::Display for MidiImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MidiImportError::Parse(msg) => write!(f, "Invalid MIDI file: {}", msg),
+            MidiImportError::UnsupportedFormat => write!(f, "Format 2 MIDI files are not supported"),
+            MidiImportError::UnsupportedTiming => write!(f, "SMPTE-timed MIDI files are not supported"),
+            MidiImportError::Io(msg) => write!(f, "Cannot read MIDI file: {}", msg),
+        }
+    }
+}
+
+impl error::Error for MidiImportError {}
+
+/// A note read from a MIDI file, with its timing in ticks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimedNote {
+    /// The note, spelled in the key signature in force when it starts.
+    pub note: Note,
+    /// The tick where the note starts.
+    pub tick: u32,
+    /// The length of the note in ticks.
+    pub duration: u32,
+    pub channel: Channel,
+    pub velocity: Velocity,
+}
+
+/// A tempo change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TempoChange {
+    pub tick: u32,
+    pub microseconds_per_beat: u32,
+}
+
+impl TempoChange {
+    /// The tempo in beats per minute.
+    pub fn bpm(&self) -> f64 {
+        60_000_000.0 / self.microseconds_per_beat as f64
+    }
+}
+
+/// A time signature change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeSignatureChange {
+    pub tick: u32,
+    pub numerator: u8,
+    pub denominator: u8,
+}
+
+/// A key signature change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySignatureChange {
+    pub tick: u32,
+    pub key: KeySignature,
+}
+
+/// A control change, such as modulation (1), volume (7), or sustain (64).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ControlChange {
+    pub tick: u32,
+    pub channel: Channel,
+    pub controller: u8,
+    pub value: u8,
+}
+
+/// A program (instrument) change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgramChange {
+    pub tick: u32,
+    pub channel: Channel,
+    pub program: u8,
+}
+
+/// The contents of one track of a MIDI file.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ImportedTrack {
+    /// The track name meta event, if any.
+    pub name: Option<String>,
+    /// The notes, in order of their start.
+    pub notes: Vec<TimedNote>,
+    pub controls: Vec<ControlChange>,
+    pub programs: Vec<ProgramChange>,
+}
+
+/// A Standard MIDI File read into the crate's types.
+///
+/// Tempo, time signature, and key signature changes apply to the whole file whichever track
+/// they appear in, and are collected in order of their ticks.
+///
+/// ```ignore
+/// use rust_music_theory::midi::MidiImport;
+///
+/// let song = MidiImport::load("song.mid")?;
+/// for note in &song.tracks[1].notes {
+///     println!("{} at beat {}", note.note, song.beats(note.tick));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MidiImport {
+    /// Pulses (ticks) per quarter note.
+    pub ppq: u16,
+    pub tracks: Vec<ImportedTrack>,
+    pub tempos: Vec<TempoChange>,
+    pub time_signatures: Vec<TimeSignatureChange>,
+    pub key_signatures: Vec<KeySignatureChange>,
+}
+
+impl MidiImport {
+    /// Read a format 0 or 1 Standard MIDI File from bytes.
+    ///
+    /// Notes are spelled with the key signature in force when they start, and with sharps
+    /// before the first key signature. A note still held at the end of its track ends there.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MidiImportError> {
+        let smf = Smf::parse(bytes).map_err(|e| MidiImportError::Parse(e.to_string()))?;
+        if smf.header.format == Format::Sequential {
+            return Err(MidiImportError::UnsupportedFormat);
+        }
+        let ppq = match smf.header.timing {
+            Timing::Metrical(ppq) => ppq.as_int(),
+            Timing::Timecode(..) => return Err(MidiImportError::UnsupportedTiming),
+        };
+
+        // Meta events are global, so gather them from every track before reading notes.
+        let mut tempos = Vec::new();
+        let mut time_signatures = Vec::new();
+        let mut key_signatures = Vec::new();
+        for track in &smf.tracks {
+            let mut tick = 0u32;
+            for event in track {
+                tick = advance(tick, event)?;
+                match event.kind {
+                    TrackEventKind::Meta(MetaMessage::Tempo(tempo)) => tempos.push(TempoChange {
+                        tick,
+                        microseconds_per_beat: tempo.as_int(),
+                    }),
+                    TrackEventKind::Meta(MetaMessage::TimeSignature(numerator, power, ..)) => {
+                        time_signatures.push(TimeSignatureChange {
+                            tick,
+                            numerator,
+                            denominator: 1u8.checked_shl(power as u32).unwrap_or(4),
+                        })
+                    }
+                    TrackEventKind::Meta(MetaMessage::KeySignature(sharps_flats, minor)) => {
+                        key_signatures.push(KeySignatureChange {
+                            tick,
+                            key: KeySignature::from_midi(sharps_flats, minor),
+                        })
+                    }
+                    _ => {}
+                }
+            }
+        }
+        tempos.sort_by_key(|t| t.tick);
+        time_signatures.sort_by_key(|t| t.tick);
+        key_signatures.sort_by_key(|k| k.tick);
+
+        let mut import = MidiImport {
+            ppq,
+            tracks: Vec::with_capacity(smf.tracks.len()),
+            tempos,
+            time_signatures,
+            key_signatures,
+        };
+        for track in &smf.tracks {
+            let imported = import.read_track(track)?;
+            import.tracks.push(imported);
+        }
+        Ok(import)
+    }
+
+    /// Read a format 0 or 1 Standard MIDI File.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, MidiImportError> {
+        let bytes = std::fs::read(path).map_err(|e| MidiImportError::Io(e.to_string()))?;
+        Self::from_bytes(&bytes)
+    }
+
+    fn read_track(&self, track: &[midly::TrackEvent]) -> Result<ImportedTrack, MidiImportError> {
+        let mut imported = ImportedTrack::default();
+        // Sounding notes as (channel, key, start tick, velocity), matched first in first out.
+        let mut held: Vec<(u8, u8, u32, u8)> = Vec::new();
+        let mut tick = 0u32;
+
+        for event in track {
+            tick = advance(tick, event)?;
+            match event.kind {
+                TrackEventKind::Meta(MetaMessage::TrackName(name)) => {
+                    imported.name = Some(String::from_utf8_lossy(name).into_owned());
+                }
+                TrackEventKind::Midi { channel, message } => {
+                    let channel = channel.as_int();
+                    match message {
+                        MidiMessage::NoteOn { key, vel } if vel.as_int() > 0 => {
+                            held.push((channel, key.as_int(), tick, vel.as_int()));
+                        }
+                        MidiMessage::NoteOn { key, .. } | MidiMessage::NoteOff { key, .. } => {
+                            let key = key.as_int();
+                            if let Some(index) = held.iter().position(|h| h.0 == channel && h.1 == key) {
+                                let (_, _, start, velocity) = held.remove(index);
+                                imported.notes.push(self.timed_note(channel, key, start, tick, velocity));
+                            }
+                        }
+                        MidiMessage::Controller { controller, value } => {
+                            imported.controls.push(ControlChange {
+                                tick,
+                                channel: Channel::new(channel).unwrap(),
+                                controller: controller.as_int(),
+                                value: value.as_int(),
+                            });
+                        }
+                        MidiMessage::ProgramChange { program } => {
+                            imported.programs.push(ProgramChange {
+                                tick,
+                                channel: Channel::new(channel).unwrap(),
+                                program: program.as_int(),
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        for (channel, key, start, velocity) in held {
+            imported.notes.push(self.timed_note(channel, key, start, tick, velocity));
+        }
+        imported.notes.sort_by_key(|n| (n.tick, n.note.midi_pitch()));
+        Ok(imported)
+    }
+
+    fn timed_note(&self, channel: u8, key: u8, start: u32, end: u32, velocity: u8) -> TimedNote {
+        let spelling = match self.key_at(start) {
+            Some(key) => Spelling::Key(key.clone()),
+            None => Spelling::Sharps,
+        };
+        TimedNote {
+            note: Note::from_midi_with_pitch(spelling.spell(key % 12), key as i16),
+            tick: start,
+            duration: end - start,
+            channel: Channel::new(channel).unwrap(),
+            velocity: Velocity::new(velocity).unwrap(),
+        }
+    }
+
+    /// The key signature in force at a tick, if any.
+    pub fn key_at(&self, tick: u32) -> Option<&KeySignature> {
+        self.key_signatures.iter().rev().find(|k| k.tick <= tick).map(|k| &k.key)
+    }
+
+    /// The time signature in force at a tick, 4/4 before the first.
+    pub fn time_signature_at(&self, tick: u32) -> (u8, u8) {
+        self.time_signatures
+            .iter()
+            .rev()
+            .find(|t| t.tick <= tick)
+            .map_or((4, 4), |t| (t.numerator, t.denominator))
+    }
+
+    /// The number of quarter-note beats before a tick.
+    pub fn beats(&self, tick: u32) -> f64 {
+        tick as f64 / self.ppq.max(1) as f64
+    }
+
+    /// The time in seconds at a tick, following the tempo changes from 120 BPM.
+    pub fn seconds(&self, tick: u32) -> f64 {
+        let mut seconds = 0.0;
+        let mut from = 0u32;
+        let mut microseconds_per_beat = 500_000u32;
+        for tempo in self.tempos.iter().take_while(|t| t.tick <= tick) {
+            seconds += self.beats(tempo.tick - from) * microseconds_per_beat as f64 / 1_000_000.0;
+            from = tempo.tick;
+            microseconds_per_beat = tempo.microseconds_per_beat;
+        }
+        seconds + self.beats(tick - from) * microseconds_per_beat as f64 / 1_000_000.0
+    }
+}
+
+/// The absolute tick of an event after the previous one, rejecting tracks too long to count.
+fn advance(tick: u32, event: &midly::TrackEvent) -> Result<u32, MidiImportError> {
+    tick.checked_add(event.delta.as_int())
+        .ok_or_else(|| MidiImportError::Parse("track is longer than 2^32 ticks".to_string()))
+}
+
+impl Notes for ImportedTrack {
+    fn notes(&self) -> Vec<Note> {
+        self.notes.iter().map(|n| n.note.clone()).collect()
+    }
+}
+
+impl Notes for MidiImport {
+    /// Every note of every track in order of their start.
+    fn notes(&self) -> Vec<Note> {
+        let mut notes: Vec<&TimedNote> = self.tracks.iter().flat_map(|t| &t.notes).collect();
+        notes.sort_by_key(|n| (n.tick, n.note.midi_pitch()));
+        notes.into_iter().map(|n| n.note.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use midly::num::{u4, u7, u15, u24, u28};
+    use midly::{Header, TrackEvent};
+
+    fn event(delta: u32, kind: TrackEventKind<'static>) -> TrackEvent<'static> {
+        TrackEvent { delta: u28::new(delta), kind }
+    }
+
+    fn midi(channel: u8, message: MidiMessage) -> TrackEventKind<'static> {
+        TrackEventKind::Midi { channel: u4::new(channel), message }
+    }
+
+    fn on(key: u8, vel: u8) -> MidiMessage {
+        MidiMessage::NoteOn { key: u7::new(key), vel: u7::new(vel) }
+    }
+
+    fn off(key: u8) -> MidiMessage {
+        MidiMessage::NoteOff { key: u7::new(key), vel: u7::new(64) }
+    }
+
+    fn bytes(format: Format, timing: Timing, tracks: Vec<Vec<TrackEvent<'static>>>) -> Vec<u8> {
+        let mut smf = Smf::new(Header::new(format, timing));
+        smf.tracks = tracks;
+        let mut bytes = Vec::new();
+        smf.write(&mut bytes).unwrap();
+        bytes
+    }
+
+    fn single_track(events: Vec<TrackEvent<'static>>) -> Vec<u8> {
+        bytes(Format::SingleTrack, Timing::Metrical(u15::new(96)), vec![events])
+    }
+
+    #[test]
+    fn format_0_notes_with_zero_velocity_note_offs() {
+        let file = single_track(vec![
+            event(0, TrackEventKind::Meta(MetaMessage::TrackName(b"Piano"))),
+            event(0, midi(2, on(60, 100))),
+            event(0, midi(2, on(64, 90))),
+            event(96, midi(2, on(60, 0))),
+            event(48, midi(2, off(64))),
+            event(0, TrackEventKind::Meta(MetaMessage::EndOfTrack)),
+        ]);
+        let import = MidiImport::from_bytes(&file).unwrap();
+
+        assert_eq!(import.ppq, 96);
+        assert_eq!(import.tracks.len(), 1);
+        let track = &import.tracks[0];
+        assert_eq!(track.name.as_deref(), Some("Piano"));
+        assert_eq!(track.notes.len(), 2);
+        assert_eq!(track.notes[0].note, "C4".parse::<Note>().unwrap());
+        assert_eq!((track.notes[0].tick, track.notes[0].duration), (0, 96));
+        assert_eq!(track.notes[0].channel, Channel::new(2).unwrap());
+        assert_eq!(track.notes[0].velocity, Velocity::new(100).unwrap());
+        assert_eq!(track.notes[1].note, "E4".parse::<Note>().unwrap());
+        assert_eq!(track.notes[1].duration, 144);
+    }
+
+    #[test]
+    fn repeated_keys_pair_first_in_first_out() {
+        let file = single_track(vec![
+            event(0, midi(0, on(62, 80))),
+            event(10, midi(0, on(62, 70))),
+            event(10, midi(0, off(62))),
+            event(30, midi(0, off(62))),
+            event(0, midi(1, on(62, 60))),
+            event(20, TrackEventKind::Meta(MetaMessage::EndOfTrack)),
+        ]);
+        let notes = &MidiImport::from_bytes(&file).unwrap().tracks[0].notes;
+
+        let timing: Vec<(u32, u32, u8)> = notes.iter()
+            .map(|n| (n.tick, n.duration, n.velocity.value()))
+            .collect();
+        // The note on channel 1 is never released and ends with its track.
+        assert_eq!(timing, vec![(0, 20, 80), (10, 40, 70), (50, 20, 60)]);
+    }
+
+    #[test]
+    fn notes_spelled_in_key_signature() {
+        let file = single_track(vec![
+            event(0, midi(0, on(63, 100))),
+            event(0, midi(0, on(70, 100))),
+            event(10, midi(0, off(63))),
+            event(0, midi(0, off(70))),
+            event(0, TrackEventKind::Meta(MetaMessage::KeySignature(-3, false))),
+            event(0, midi(0, on(63, 100))),
+            event(0, midi(0, on(68, 100))),
+            event(10, midi(0, off(63))),
+            event(0, midi(0, off(68))),
+            event(0, TrackEventKind::Meta(MetaMessage::KeySignature(4, true))),
+            event(0, midi(0, on(68, 100))),
+            event(10, midi(0, off(68))),
+        ]);
+        let import = MidiImport::from_bytes(&file).unwrap();
+        let names: Vec<String> = import.notes().iter().map(|n| format!("{}{}", n.pitch, n.octave)).collect();
+
+        assert_eq!(names, vec!["D#4", "A#4", "Eb4", "Ab4", "G#4"]);
+        assert_eq!(import.key_signatures.len(), 2);
+        assert_eq!(import.key_at(5), None);
+        assert_eq!(import.key_at(10), Some(&KeySignature::from_midi(-3, false)));
+        assert!(import.key_at(25).unwrap().is_minor());
+    }
+
+    #[test]
+    fn controls_programs_and_meter() {
+        let file = single_track(vec![
+            event(0, TrackEventKind::Meta(MetaMessage::TimeSignature(6, 3, 24, 8))),
+            event(0, midi(9, MidiMessage::ProgramChange { program: u7::new(25) })),
+            event(48, midi(9, MidiMessage::Controller { controller: u7::new(64), value: u7::new(127) })),
+            event(48, TrackEventKind::Meta(MetaMessage::TimeSignature(2, 2, 24, 8))),
+        ]);
+        let import = MidiImport::from_bytes(&file).unwrap();
+        let track = &import.tracks[0];
+
+        assert_eq!(track.programs, vec![ProgramChange { tick: 0, channel: Channel::drums(), program: 25 }]);
+        assert_eq!(
+            track.controls,
+            vec![ControlChange { tick: 48, channel: Channel::drums(), controller: 64, value: 127 }]
+        );
+        assert_eq!(import.time_signature_at(0), (6, 8));
+        assert_eq!(import.time_signature_at(95), (6, 8));
+        assert_eq!(import.time_signature_at(96), (2, 4));
+    }
+
+    #[test]
+    fn tempo_map_across_tracks() {
+        let file = bytes(
+            Format::Parallel,
+            Timing::Metrical(u15::new(480)),
+            vec![
+                vec![
+                    event(0, TrackEventKind::Meta(MetaMessage::Tempo(u24::new(1_000_000)))),
+                    event(960, TrackEventKind::Meta(MetaMessage::Tempo(u24::new(250_000)))),
+                ],
+                vec![event(0, midi(0, on(60, 100))), event(1920, midi(0, off(60)))],
+            ],
+        );
+        let import = MidiImport::from_bytes(&file).unwrap();
+
+        assert_eq!(import.tracks.len(), 2);
+        assert!(import.tracks[0].notes.is_empty());
+        assert_eq!(import.tempos[0].bpm(), 60.0);
+        assert_eq!(import.tempos[1].bpm(), 240.0);
+        assert_eq!(import.seconds(480), 1.0);
+        assert_eq!(import.seconds(960), 2.0);
+        assert_eq!(import.seconds(1920), 2.5);
+        assert_eq!(import.beats(1920), 4.0);
+    }
+
+    #[test]
+    fn seconds_default_to_120_bpm() {
+        let file = single_track(vec![event(0, midi(0, on(60, 100)))]);
+        let import = MidiImport::from_bytes(&file).unwrap();
+        assert_eq!(import.seconds(192), 1.0);
+    }
+
+    #[test]
+    fn overlong_tracks_are_rejected() {
+        let max = (1 << 28) - 1;
+        let text = (0..20).map(|_| event(max, TrackEventKind::Meta(MetaMessage::Text(b"x")))).collect();
+        let file = single_track(text);
+        assert_eq!(
+            MidiImport::from_bytes(&file),
+            Err(MidiImportError::Parse("track is longer than 2^32 ticks".to_string()))
+        );
+
+        let fits = (0..15).map(|_| event(max, TrackEventKind::Meta(MetaMessage::Text(b"x")))).collect();
+        assert!(MidiImport::from_bytes(&single_track(fits)).is_ok());
+    }
+
+    #[test]
+    fn unsupported_files() {
+        assert!(matches!(MidiImport::from_bytes(b"not a midi file"), Err(MidiImportError::Parse(_))));
+        let sequential = bytes(Format::Sequential, Timing::Metrical(u15::new(96)), vec![vec![]]);
+        assert_eq!(MidiImport::from_bytes(&sequential), Err(MidiImportError::UnsupportedFormat));
+        let timecode = bytes(
+            Format::SingleTrack,
+            Timing::Timecode(midly::Fps::Fps25, 40),
+            vec![vec![]],
+        );
+        assert_eq!(MidiImport::from_bytes(&timecode), Err(MidiImportError::UnsupportedTiming));
+        assert!(matches!(MidiImport::load("does/not/exist.mid"), Err(MidiImportError::Io(_))));
+    }
+}
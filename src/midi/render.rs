@@ -0,0 +1,28 @@
+//! Offline audio rendering without MIDI devices.
+//!
+//! A [`Synth`] plays the notes of a [`MidiBuilder`](crate::midi::MidiBuilder),
+//! [`MidiFile`](crate::midi::MidiFile), or any [`Notes`](crate::note::Notes) with simple
+//! oscillators and ADSR envelopes, producing [`Audio`] that can be saved as a WAV file.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use rust_music_theory::chord::{Chord, Quality, Number};
+//! use rust_music_theory::note::{Pitch, PitchSymbol::*};
+//! use rust_music_theory::midi::{Duration, Velocity};
+//! use rust_music_theory::midi::render::{Envelope, Instrument, SampleFormat, Synth, Waveform};
+//!
+//! let chord = Chord::new(Pitch::from(C), Quality::Major, Number::Triad);
+//! let synth = Synth::new()
+//!     .instrument(Instrument::new(Waveform::Triangle).envelope(Envelope::new(0.02, 0.1, 0.6, 0.3)));
+//! synth.render_notes(&chord, Duration::Whole, Velocity::new(100).unwrap())
+//!     .save_wav("chord.wav", SampleFormat::Int16)?;
+//! ```
+
+mod audio;
+mod instrument;
+mod synth;
+
+pub use audio::{Audio, SampleFormat};
+pub use instrument::{Envelope, Instrument, Waveform};
+pub use synth::Synth;
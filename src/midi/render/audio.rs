@@ -0,0 +1,210 @@
+//! Rendered audio and WAV encoding.
+
+use std::convert::TryFrom;
+use std::io;
+use std::path::Path;
+
+/// How samples are stored in a WAV file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SampleFormat {
+    /// 16-bit integer PCM, clipping samples outside -1 to 1
+    Int16,
+    /// 32-bit IEEE float
+    Float32,
+}
+
+/// Mono audio samples from -1 to 1 at a sample rate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Audio {
+    sample_rate: u32,
+    samples: Vec<f32>,
+}
+
+impl Audio {
+    /// Create audio from samples at a sample rate in Hz.
+    pub fn new(sample_rate: u32, samples: Vec<f32>) -> Self {
+        Self { sample_rate, samples }
+    }
+
+    /// Get the sample rate in Hz.
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Get the samples.
+    pub fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    /// The length in seconds.
+    pub fn duration(&self) -> f64 {
+        self.samples.len() as f64 / self.sample_rate as f64
+    }
+
+    /// The largest absolute sample.
+    pub fn peak(&self) -> f32 {
+        self.samples.iter().fold(0.0, |peak, sample| peak.max(sample.abs()))
+    }
+
+    /// Scale the samples so that the largest reaches a peak, leaving silence unchanged.
+    pub fn normalize(&mut self, peak: f32) -> &mut Self {
+        let current = self.peak();
+        if current > 0.0 {
+            let scale = peak / current;
+            for sample in &mut self.samples {
+                *sample *= scale;
+            }
+        }
+        self
+    }
+
+    /// Encode as a mono WAV file.
+    ///
+    /// Fails with `InvalidInput` if the audio is too long or the sample rate too high for the
+    /// 32-bit sizes of a RIFF file, which hold at most 4 GiB.
+    pub fn to_wav(&self, format: SampleFormat) -> io::Result<Vec<u8>> {
+        let (tag, bits) = match format {
+            SampleFormat::Int16 => (1u16, 16u16),
+            SampleFormat::Float32 => (3u16, 32u16),
+        };
+        let block_align = bits / 8;
+        // Non-PCM formats carry an extension size and a fact chunk with the sample count.
+        let extended = format == SampleFormat::Float32;
+        let fmt_size: u32 = if extended { 18 } else { 16 };
+        let fact_size: u32 = if extended { 12 } else { 0 };
+        let too_large = || {
+            io::Error::new(io::ErrorKind::InvalidInput, "audio is too large for a WAV file")
+        };
+        let data_size = riff_size(self.samples.len() as u64 * block_align as u64)
+            .ok_or_else(too_large)?;
+        let file_size = riff_size(4 + 8 + fmt_size as u64 + fact_size as u64 + 8 + data_size as u64)
+            .ok_or_else(too_large)?;
+        let byte_rate = riff_size(self.sample_rate as u64 * block_align as u64)
+            .ok_or_else(too_large)?;
+
+        let mut bytes = Vec::with_capacity(8 + file_size as usize);
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&file_size.to_le_bytes());
+        bytes.extend_from_slice(b"WAVE");
+
+        bytes.extend_from_slice(b"fmt ");
+        bytes.extend_from_slice(&fmt_size.to_le_bytes());
+        bytes.extend_from_slice(&tag.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&self.sample_rate.to_le_bytes());
+        bytes.extend_from_slice(&byte_rate.to_le_bytes());
+        bytes.extend_from_slice(&block_align.to_le_bytes());
+        bytes.extend_from_slice(&bits.to_le_bytes());
+        if extended {
+            bytes.extend_from_slice(&0u16.to_le_bytes());
+            bytes.extend_from_slice(b"fact");
+            bytes.extend_from_slice(&4u32.to_le_bytes());
+            bytes.extend_from_slice(&(self.samples.len() as u32).to_le_bytes());
+        }
+
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&data_size.to_le_bytes());
+        for sample in &self.samples {
+            match format {
+                SampleFormat::Int16 => {
+                    let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16;
+                    bytes.extend_from_slice(&value.to_le_bytes());
+                }
+                SampleFormat::Float32 => bytes.extend_from_slice(&sample.to_le_bytes()),
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Save as a mono WAV file.
+    pub fn save_wav<P: AsRef<Path>>(&self, path: P, format: SampleFormat) -> io::Result<()> {
+        std::fs::write(path, self.to_wav(format)?)
+    }
+}
+
+/// A size that fits in a RIFF chunk header.
+fn riff_size(size: u64) -> Option<u32> {
+    u32::try_from(size).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+    }
+
+    fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+        u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+    }
+
+    #[test]
+    fn int16_wav_header_and_samples() {
+        let audio = Audio::new(8000, vec![0.0, 1.0, -1.0, 2.0]);
+        let wav = audio.to_wav(SampleFormat::Int16).unwrap();
+
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32_at(&wav, 4) as usize, wav.len() - 8);
+        assert_eq!(&wav[8..16], b"WAVEfmt ");
+        assert_eq!(u32_at(&wav, 16), 16);
+        assert_eq!(u16_at(&wav, 20), 1); // PCM
+        assert_eq!(u16_at(&wav, 22), 1); // mono
+        assert_eq!(u32_at(&wav, 24), 8000);
+        assert_eq!(u32_at(&wav, 28), 16000);
+        assert_eq!(u16_at(&wav, 32), 2);
+        assert_eq!(u16_at(&wav, 34), 16);
+        assert_eq!(&wav[36..40], b"data");
+        assert_eq!(u32_at(&wav, 40), 8);
+        let samples: Vec<i16> = wav[44..]
+            .chunks(2)
+            .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        assert_eq!(samples, vec![0, 32767, -32767, 32767]); // clipped
+    }
+
+    #[test]
+    fn float32_wav_header_and_samples() {
+        let audio = Audio::new(48000, vec![0.5, -0.25]);
+        let wav = audio.to_wav(SampleFormat::Float32).unwrap();
+
+        assert_eq!(u32_at(&wav, 4) as usize, wav.len() - 8);
+        assert_eq!(u32_at(&wav, 16), 18);
+        assert_eq!(u16_at(&wav, 20), 3); // IEEE float
+        assert_eq!(u32_at(&wav, 28), 192000);
+        assert_eq!(u16_at(&wav, 34), 32);
+        assert_eq!(&wav[38..42], b"fact");
+        assert_eq!(u32_at(&wav, 46), 2);
+        assert_eq!(&wav[50..54], b"data");
+        assert_eq!(u32_at(&wav, 54), 8);
+        assert_eq!(f32::from_le_bytes([wav[58], wav[59], wav[60], wav[61]]), 0.5);
+        assert_eq!(f32::from_le_bytes([wav[62], wav[63], wav[64], wav[65]]), -0.25);
+    }
+
+    #[test]
+    fn wav_sizes_past_the_riff_limit_are_rejected() {
+        assert_eq!(riff_size(u32::MAX as u64), Some(u32::MAX));
+        assert_eq!(riff_size(u32::MAX as u64 + 1), None);
+        // 2^30 float samples fill 4 GiB, one byte past the largest RIFF size.
+        assert_eq!(riff_size((1u64 << 30) * 4), None);
+
+        let audio = Audio::new(u32::MAX, vec![0.0]);
+        let error = audio.to_wav(SampleFormat::Float32).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(Audio::new(u32::MAX / 2, vec![0.0]).to_wav(SampleFormat::Int16).is_ok());
+    }
+
+    #[test]
+    fn normalize_scales_to_peak() {
+        let mut audio = Audio::new(44100, vec![0.1, -0.4, 0.2]);
+        assert_eq!(audio.peak(), 0.4);
+        audio.normalize(0.8);
+        assert!((audio.peak() - 0.8).abs() < 1e-6);
+        assert!((audio.samples()[0] - 0.2).abs() < 1e-6);
+        assert_eq!(audio.duration(), 3.0 / 44100.0);
+
+        let mut silence = Audio::new(44100, vec![0.0; 4]);
+        silence.normalize(1.0);
+        assert_eq!(silence.samples(), &[0.0; 4]);
+    }
+}
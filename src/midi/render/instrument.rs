@@ -0,0 +1,208 @@
+//! Oscillators, envelopes, and the instruments built from them.
+
+use std::f64::consts::PI;
+
+use crate::midi::Velocity;
+
+/// The shape of an oscillator's wave.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Waveform {
+    /// Pure tone
+    Sine,
+    /// Bright ramp rising through each cycle
+    Saw,
+    /// Hollow tone alternating between two levels
+    Square,
+    /// Soft tone rising and falling linearly
+    Triangle,
+}
+
+impl Waveform {
+    /// The wave's value from -1 to 1 at a phase from 0 to 1 through a cycle.
+    pub fn sample(&self, phase: f64) -> f64 {
+        match self {
+            Waveform::Sine => (2.0 * PI * phase).sin(),
+            Waveform::Saw => 2.0 * phase - 1.0,
+            Waveform::Square => if phase < 0.5 { 1.0 } else { -1.0 },
+            Waveform::Triangle => 1.0 - 4.0 * (phase - 0.5).abs(),
+        }
+    }
+}
+
+/// An ADSR envelope shaping a note's loudness over time.
+///
+/// Times are in seconds and the sustain level runs from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Envelope {
+    pub attack: f64,
+    pub decay: f64,
+    pub sustain: f64,
+    pub release: f64,
+}
+
+impl Envelope {
+    /// Create an envelope from attack, decay, and release times and a sustain level.
+    pub fn new(attack: f64, decay: f64, sustain: f64, release: f64) -> Self {
+        Self {
+            attack: attack.max(0.0),
+            decay: decay.max(0.0),
+            sustain: sustain.clamp(0.0, 1.0),
+            release: release.max(0.0),
+        }
+    }
+
+    /// The level from 0 to 1 at a time after the note starts, for a note held for a time.
+    ///
+    /// The level rises to 1 over the attack, falls to the sustain level over the decay, and
+    /// after the note is released falls from wherever it reached to 0 over the release.
+    pub fn level(&self, time: f64, held: f64) -> f64 {
+        if time < 0.0 {
+            return 0.0;
+        }
+        if time < held {
+            return self.held_level(time);
+        }
+        if self.release <= 0.0 {
+            return 0.0;
+        }
+        let released = self.held_level(held);
+        released * (1.0 - (time - held) / self.release).max(0.0)
+    }
+
+    fn held_level(&self, time: f64) -> f64 {
+        if time < self.attack {
+            time / self.attack
+        } else if time < self.attack + self.decay {
+            1.0 - (1.0 - self.sustain) * (time - self.attack) / self.decay
+        } else {
+            self.sustain
+        }
+    }
+}
+
+impl Default for Envelope {
+    /// A short attack and release around a fairly full sustain.
+    fn default() -> Self {
+        Self::new(0.01, 0.1, 0.7, 0.2)
+    }
+}
+
+/// An oscillator with an envelope, gain, and velocity sensitivity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Instrument {
+    waveform: Waveform,
+    envelope: Envelope,
+    gain: f64,
+    velocity_sensitivity: f64,
+}
+
+impl Instrument {
+    /// Create an instrument with the default envelope, full gain, and full velocity sensitivity.
+    pub fn new(waveform: Waveform) -> Self {
+        Self {
+            waveform,
+            envelope: Envelope::default(),
+            gain: 1.0,
+            velocity_sensitivity: 1.0,
+        }
+    }
+
+    /// Set the envelope.
+    pub fn envelope(mut self, envelope: Envelope) -> Self {
+        self.envelope = envelope;
+        self
+    }
+
+    /// Set the gain, where 1 is unchanged.
+    pub fn gain(mut self, gain: f64) -> Self {
+        self.gain = gain.max(0.0);
+        self
+    }
+
+    /// Set how much velocity affects loudness, from 0 where every note is equally loud to 1
+    /// where loudness is proportional to velocity.
+    pub fn velocity_sensitivity(mut self, sensitivity: f64) -> Self {
+        self.velocity_sensitivity = sensitivity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// The release time, after which the instrument is silent.
+    pub(crate) fn release(&self) -> f64 {
+        self.envelope.release
+    }
+
+    /// The instrument's output for a note at a phase through its cycle, a time after it starts,
+    /// and held for a time.
+    pub(crate) fn sample(&self, phase: f64, time: f64, held: f64) -> f64 {
+        self.waveform.sample(phase) * self.envelope.level(time, held)
+    }
+
+    /// The peak amplitude of a note played at a velocity.
+    pub(crate) fn amplitude(&self, velocity: Velocity) -> f64 {
+        let velocity = velocity.value() as f64 / 127.0;
+        self.gain * (1.0 - self.velocity_sensitivity + self.velocity_sensitivity * velocity)
+    }
+}
+
+impl Default for Instrument {
+    fn default() -> Self {
+        Self::new(Waveform::Sine)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn waveforms_span_minus_one_to_one() {
+        assert!(close(Waveform::Sine.sample(0.25), 1.0));
+        assert!(close(Waveform::Sine.sample(0.75), -1.0));
+        assert!(close(Waveform::Saw.sample(0.0), -1.0));
+        assert!(close(Waveform::Saw.sample(0.5), 0.0));
+        assert!(close(Waveform::Square.sample(0.1), 1.0));
+        assert!(close(Waveform::Square.sample(0.6), -1.0));
+        assert!(close(Waveform::Triangle.sample(0.0), -1.0));
+        assert!(close(Waveform::Triangle.sample(0.5), 1.0));
+        assert!(close(Waveform::Triangle.sample(0.25), 0.0));
+    }
+
+    #[test]
+    fn envelope_stages() {
+        let envelope = Envelope::new(0.1, 0.2, 0.5, 0.4);
+        assert!(close(envelope.level(0.0, 1.0), 0.0));
+        assert!(close(envelope.level(0.05, 1.0), 0.5));
+        assert!(close(envelope.level(0.1, 1.0), 1.0));
+        assert!(close(envelope.level(0.2, 1.0), 0.75));
+        assert!(close(envelope.level(0.5, 1.0), 0.5));
+        assert!(close(envelope.level(1.2, 1.0), 0.25));
+        assert!(close(envelope.level(1.4, 1.0), 0.0));
+        assert!(close(envelope.level(2.0, 1.0), 0.0));
+    }
+
+    #[test]
+    fn envelope_released_during_attack() {
+        let envelope = Envelope::new(0.2, 0.0, 1.0, 0.1);
+        assert!(close(envelope.level(0.1, 0.1), 0.5));
+        assert!(close(envelope.level(0.15, 0.1), 0.25));
+    }
+
+    #[test]
+    fn velocity_sensitivity_scales_amplitude() {
+        let soft = Velocity::new(0).unwrap();
+        let loud = Velocity::max();
+        let sensitive = Instrument::new(Waveform::Sine).gain(0.5);
+        assert!(close(sensitive.amplitude(loud), 0.5));
+        assert!(close(sensitive.amplitude(soft), 0.0));
+
+        let half = sensitive.velocity_sensitivity(0.5);
+        assert!(close(half.amplitude(soft), 0.25));
+
+        let flat = sensitive.velocity_sensitivity(0.0);
+        assert!(close(flat.amplitude(soft), flat.amplitude(loud)));
+    }
+}
@@ -0,0 +1,334 @@
+//! Offline synthesizer turning MIDI events into audio.
+
+use crate::midi::event::MidiEvent;
+use crate::midi::render::{Audio, Instrument};
+use crate::midi::{Channel, Duration, MidiBuilder, MidiFile, Velocity};
+use crate::note::Notes;
+
+/// Default sample rate in Hz.
+const DEFAULT_SAMPLE_RATE: u32 = 44_100;
+
+/// Default tempo in BPM, matching [`MidiFile`].
+const DEFAULT_TEMPO: u16 = 120;
+
+/// Renders notes to audio with an instrument per channel.
+///
+/// Each note is an oscillator shaped by its channel's envelope, and the notes are mixed at the
+/// synth's gain. Tempo changes are followed, and pitch bends retune the notes that start after
+/// them, assuming the General MIDI bend range of two semitones.
+#[derive(Debug, Clone)]
+pub struct Synth {
+    sample_rate: u32,
+    gain: f64,
+    instrument: Instrument,
+    channels: [Option<Instrument>; 16],
+}
+
+/// A note ready to render, with times in seconds.
+struct Voice {
+    start: f64,
+    end: f64,
+    frequency: f64,
+    amplitude: f64,
+    instrument: Instrument,
+}
+
+impl Synth {
+    /// Create a synth at 44.1 kHz with a sine instrument on every channel.
+    pub fn new() -> Self {
+        Self {
+            sample_rate: DEFAULT_SAMPLE_RATE,
+            gain: 0.25,
+            instrument: Instrument::default(),
+            channels: [None; 16],
+        }
+    }
+
+    /// Set the sample rate in Hz.
+    pub fn sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate.max(1);
+        self
+    }
+
+    /// Set the gain applied to the mix, 0.25 by default to leave room for chords.
+    pub fn gain(mut self, gain: f64) -> Self {
+        self.gain = gain.max(0.0);
+        self
+    }
+
+    /// Set the instrument for channels without one of their own.
+    pub fn instrument(mut self, instrument: Instrument) -> Self {
+        self.instrument = instrument;
+        self
+    }
+
+    /// Set the instrument for a channel.
+    pub fn channel(mut self, channel: Channel, instrument: Instrument) -> Self {
+        self.channels[channel.value() as usize] = Some(instrument);
+        self
+    }
+
+    /// Render a builder's notes on their channels at 120 BPM, following its tempo changes.
+    pub fn render(&self, builder: &MidiBuilder) -> Audio {
+        self.mix(&self.voices(&[(builder, None)], builder.ppq, DEFAULT_TEMPO))
+    }
+
+    /// Render every track of a file on its channel, starting at the file's tempo.
+    pub fn render_file(&self, file: &MidiFile) -> Audio {
+        let tracks: Vec<(&MidiBuilder, Option<Channel>)> = file.tracks.iter()
+            .map(|(builder, channel)| (builder, Some(*channel)))
+            .collect();
+        self.mix(&self.voices(&tracks, file.ppq, file.default_tempo))
+    }
+
+    /// Render notes played together for a duration at 120 BPM.
+    pub fn render_notes<N: Notes>(&self, notes: &N, duration: Duration, velocity: Velocity) -> Audio {
+        let mut builder = MidiBuilder::new();
+        builder.add(notes, duration, velocity);
+        self.render(&builder)
+    }
+
+    fn instrument_for(&self, channel: Channel) -> Instrument {
+        self.channels[channel.value() as usize].unwrap_or(self.instrument)
+    }
+
+    /// The notes of tracks on the channels of their events, offset by each track's channel.
+    fn voices(&self, tracks: &[(&MidiBuilder, Option<Channel>)], ppq: u16, tempo: u16) -> Vec<Voice> {
+        let mut events: Vec<(MidiEvent, Option<Channel>)> = Vec::new();
+        for (builder, channel) in tracks {
+            let mut track = builder.events.clone();
+            track.sort_by_key(|e| e.tick());
+            events.extend(track.into_iter().map(|e| (e, *channel)));
+        }
+        // A stable sort keeps each track's own order at equal ticks.
+        events.sort_by_key(|(e, _)| e.tick());
+
+        let clock = TempoMap::new(&events, ppq, tempo);
+        let mut voices = Vec::new();
+        let mut held: Vec<(u8, u8, u32, Velocity, i16)> = Vec::new();
+        let mut bends = [0i16; 16];
+        let mut last_tick = 0;
+
+        for (event, track_channel) in &events {
+            last_tick = last_tick.max(event.tick());
+            let on_track = |channel: Channel| track_channel.map_or(channel, |track| track.offset(channel.value()));
+            match event {
+                MidiEvent::PitchBend { channel, value, .. } => {
+                    bends[on_track(*channel).value() as usize] = *value;
+                }
+                MidiEvent::NoteOn { tick, channel, pitch, velocity } if velocity.value() > 0 => {
+                    let channel = on_track(*channel);
+                    held.push((channel.value(), *pitch, *tick, *velocity, bends[channel.value() as usize]));
+                }
+                MidiEvent::NoteOn { tick, channel, pitch, .. } | MidiEvent::NoteOff { tick, channel, pitch } => {
+                    let channel = on_track(*channel);
+                    if let Some(index) = held.iter().position(|h| h.0 == channel.value() && h.1 == *pitch) {
+                        let (_, _, start, velocity, bend) = held.remove(index);
+                        voices.push(self.voice(&clock, channel, *pitch, (start, *tick), velocity, bend));
+                    }
+                }
+                _ => {}
+            }
+        }
+        // Notes never released end with the last event.
+        for (channel, pitch, start, velocity, bend) in held {
+            let channel = Channel::new(channel).unwrap();
+            voices.push(self.voice(&clock, channel, pitch, (start, last_tick), velocity, bend));
+        }
+        voices
+    }
+
+    fn voice(
+        &self,
+        clock: &TempoMap,
+        channel: Channel,
+        pitch: u8,
+        (start, end): (u32, u32),
+        velocity: Velocity,
+        bend: i16,
+    ) -> Voice {
+        let instrument = self.instrument_for(channel);
+        let semitones = pitch as f64 - 69.0 + bend as f64 / 8192.0 * 2.0;
+        Voice {
+            start: clock.seconds(start),
+            end: clock.seconds(end),
+            frequency: 440.0 * 2f64.powf(semitones / 12.0),
+            amplitude: instrument.amplitude(velocity) * self.gain,
+            instrument,
+        }
+    }
+
+    fn mix(&self, voices: &[Voice]) -> Audio {
+        let rate = self.sample_rate as f64;
+        let length = voices.iter()
+            .map(|v| v.end + v.instrument.release())
+            .fold(0.0, f64::max);
+        let mut samples = vec![0f32; (length * rate).round() as usize];
+
+        for voice in voices {
+            let first = (voice.start * rate).round() as usize;
+            let last = (((voice.end + voice.instrument.release()) * rate).round() as usize).min(samples.len());
+            let held = voice.end - voice.start;
+            for (index, sample) in samples.iter_mut().enumerate().take(last).skip(first) {
+                let time = index as f64 / rate - voice.start;
+                let phase = (time * voice.frequency).fract();
+                *sample += (voice.instrument.sample(phase, time, held) * voice.amplitude) as f32;
+            }
+        }
+
+        Audio::new(self.sample_rate, samples)
+    }
+}
+
+impl Default for Synth {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Converts ticks to seconds through the tempo changes.
+struct TempoMap {
+    /// Tick, seconds at that tick, and seconds per tick from then on.
+    segments: Vec<(u32, f64, f64)>,
+}
+
+impl TempoMap {
+    fn new(events: &[(MidiEvent, Option<Channel>)], ppq: u16, tempo: u16) -> Self {
+        let ppq = ppq.max(1) as f64;
+        let per_tick = |microseconds: u32| microseconds as f64 / 1_000_000.0 / ppq;
+        let mut segments = vec![(0, 0.0, per_tick(MidiEvent::bpm_to_microseconds(tempo.max(1))))];
+        for (event, _) in events {
+            if let MidiEvent::Tempo { tick, microseconds_per_beat } = event {
+                let (from, seconds, rate) = *segments.last().unwrap();
+                let at = seconds + (tick - from) as f64 * rate;
+                if *tick == from {
+                    segments.pop();
+                }
+                segments.push((*tick, at, per_tick(*microseconds_per_beat)));
+            }
+        }
+        Self { segments }
+    }
+
+    fn seconds(&self, tick: u32) -> f64 {
+        let index = self.segments.partition_point(|s| s.0 <= tick).max(1) - 1;
+        let (from, seconds, rate) = self.segments[index];
+        seconds + (tick - from) as f64 * rate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::midi::render::{Envelope, Waveform};
+    use crate::note::Note;
+    use crate::tuning::TuningSystem;
+
+    fn note(name: &str) -> Note {
+        name.parse().unwrap()
+    }
+
+    fn velocity() -> Velocity {
+        Velocity::new(100).unwrap()
+    }
+
+    fn organ() -> Instrument {
+        Instrument::new(Waveform::Sine).envelope(Envelope::new(0.0, 0.0, 1.0, 0.0))
+    }
+
+    #[test]
+    fn render_length_includes_release() {
+        let synth = Synth::new().sample_rate(1000);
+        let audio = synth.render_notes(&note("A4"), Duration::Quarter, velocity());
+        // A quarter note at 120 BPM lasts half a second, then releases for 0.2 seconds.
+        assert_eq!(audio.sample_rate(), 1000);
+        assert_eq!(audio.samples().len(), 700);
+        assert!(audio.peak() > 0.0);
+        assert!(audio.peak() <= 0.25);
+    }
+
+    #[test]
+    fn render_sine_at_note_frequency() {
+        let synth = Synth::new().sample_rate(8000).gain(1.0).instrument(organ());
+        let mut builder = MidiBuilder::new();
+        builder.tempo(60).add(&note("A4"), Duration::Quarter, Velocity::max());
+        let audio = synth.render(&builder);
+
+        assert_eq!(audio.samples().len(), 8000);
+        let rising = audio.samples().windows(2)
+            .filter(|pair| pair[0] < 0.0 && pair[1] >= 0.0)
+            .count();
+        assert!((439..=440).contains(&rising));
+        assert!((audio.peak() - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn voices_follow_tempo_changes() {
+        let synth = Synth::new();
+        let mut builder = MidiBuilder::new();
+        builder
+            .add(&note("C4"), Duration::Quarter, velocity())
+            .tempo(60)
+            .add(&note("D4"), Duration::Quarter, velocity())
+            .add(&note("E4"), Duration::Quarter, velocity());
+
+        let voices = synth.voices(&[(&builder, None)], builder.ppq, 120);
+        let times: Vec<(f64, f64)> = voices.iter().map(|v| (v.start, v.end)).collect();
+        assert_eq!(times, vec![(0.0, 0.5), (0.5, 1.5), (1.5, 2.5)]);
+    }
+
+    #[test]
+    fn voices_use_channel_instruments_and_velocity() {
+        let quiet = Instrument::new(Waveform::Square).gain(0.5);
+        let synth = Synth::new().gain(1.0).channel(Channel::new(1).unwrap(), quiet);
+        let mut lead = MidiBuilder::new();
+        lead.add(&note("C5"), Duration::Half, Velocity::max());
+        let mut bass = MidiBuilder::new();
+        bass.add(&note("C3"), Duration::Half, Velocity::max());
+        let file = MidiFile::new()
+            .tempo(60)
+            .track(lead.clone(), Channel::new(0).unwrap())
+            .track(bass.clone(), Channel::new(1).unwrap());
+
+        let tracks = [(&lead, Some(Channel::new(0).unwrap())), (&bass, Some(Channel::new(1).unwrap()))];
+        let voices = synth.voices(&tracks, file.ppq, file.default_tempo);
+        assert_eq!(voices.len(), 2);
+        assert_eq!(voices[0].instrument, Instrument::default());
+        assert_eq!(voices[0].amplitude, 1.0);
+        assert_eq!(voices[1].instrument, quiet);
+        assert_eq!(voices[1].amplitude, 0.5);
+        assert_eq!(voices[1].end, 2.0);
+
+        let audio = synth.render_file(&file);
+        assert_eq!(audio.samples().len(), 44_100 * 2 + 8820);
+    }
+
+    #[test]
+    fn voices_apply_pitch_bend() {
+        let synth = Synth::new();
+        let mut builder = MidiBuilder::new();
+        builder
+            .tuning(TuningSystem::pythagorean())
+            .add(&note("A4"), Duration::Quarter, velocity())
+            .add(&note("E5"), Duration::Quarter, velocity());
+
+        let voices = synth.voices(&[(&builder, None)], builder.ppq, 120);
+        assert!((voices[0].frequency - 440.0).abs() < 1e-9);
+        // The bend is quantised to 1/4096 of a semitone.
+        assert!((voices[1].frequency - 660.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn unreleased_notes_end_with_the_last_event() {
+        let synth = Synth::new();
+        let mut builder = MidiBuilder::new();
+        builder.add(&note("C4"), Duration::Whole, velocity());
+        builder.events.retain(|e| !matches!(e, MidiEvent::NoteOff { .. }));
+        builder.tempo(120);
+
+        let voices = synth.voices(&[(&builder, None)], builder.ppq, 120);
+        assert_eq!(voices.len(), 1);
+        assert_eq!(voices[0].end, 2.0);
+        assert!(synth.render(&MidiBuilder::new()).samples().is_empty());
+    }
+}
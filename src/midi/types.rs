@@ -40,6 +40,11 @@ impl Channel {
     pub fn value(&self) -> u8 {
         self.0
     }
+
+    /// The channel a number of channels after this one, wrapping around past 15.
+    pub(crate) fn offset(self, by: u8) -> Self {
+        Self((self.0 + by % 16) % 16)
+    }
 }
 
 #[cfg(test)]
@@ -93,4 +98,11 @@ mod tests {
         let ch = Channel::new(5).unwrap();
         assert_eq!(ch.value(), 5);
     }
+
+    #[test]
+    fn channel_offset_wraps() {
+        assert_eq!(Channel(5).offset(2), Channel(7));
+        assert_eq!(Channel(14).offset(3), Channel(1));
+        assert_eq!(Channel(0).offset(255), Channel(15));
+    }
 }
@@ -5,9 +5,13 @@ mod note;
 mod pitch;
 mod pitch_symbol;
 mod key_signature;
+pub(crate) mod key_finding;
+mod spelling;
 
 pub use errors::NoteError;
 pub use note::{Note, Notes};
 pub use pitch::{Pitch, NoteLetter};
 pub use pitch_symbol::PitchSymbol;
 pub use key_signature::KeySignature;
+pub use key_finding::{KeyCandidate, KeyFinder, KeyProfile};
+pub use spelling::Spelling;
@@ -5,11 +5,15 @@ use std::fmt;
 #[derive(Debug, Clone)]
 pub enum NoteError {
     InvalidPitch,
+    InvalidOctave,
 }
 
 impl fmt::Display for NoteError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Invalid Pitch Class!")
+        match self {
+            NoteError::InvalidPitch => write!(f, "Invalid Pitch Class!"),
+            NoteError::InvalidOctave => write!(f, "Invalid Octave!"),
+        }
     }
 }
 
@@ -0,0 +1,152 @@
+use crate::note::{KeySignature, Note};
+use crate::scale::Mode;
+
+/// Krumhansl and Kessler's probe-tone ratings for C major.
+const KRUMHANSL_KESSLER_MAJOR: [f64; 12] = [
+    6.35, 2.23, 3.48, 2.33, 4.38, 4.09, 2.52, 5.19, 2.39, 3.66, 2.29, 2.88,
+];
+
+/// Krumhansl and Kessler's probe-tone ratings for C minor.
+const KRUMHANSL_KESSLER_MINOR: [f64; 12] = [
+    6.33, 2.68, 3.52, 5.38, 2.60, 3.53, 2.54, 4.75, 3.98, 2.69, 3.34, 3.17,
+];
+
+/// Temperley's pitch-class probabilities for C major, drawn from the Kostka-Payne corpus.
+const TEMPERLEY_MAJOR: [f64; 12] = [
+    0.748, 0.060, 0.488, 0.082, 0.670, 0.460, 0.096, 0.715, 0.104, 0.366, 0.057, 0.400,
+];
+
+/// Temperley's pitch-class probabilities for C minor, drawn from the Kostka-Payne corpus.
+const TEMPERLEY_MINOR: [f64; 12] = [
+    0.712, 0.084, 0.474, 0.618, 0.049, 0.460, 0.105, 0.747, 0.404, 0.067, 0.133, 0.330,
+];
+
+/// A set of major and minor key profiles to correlate pitch-class weights against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyProfile {
+    /// The probe-tone profiles of Krumhansl and Kessler (1982).
+    #[default]
+    KrumhanslKessler,
+    /// The corpus-derived profiles of Temperley (2001).
+    Temperley,
+}
+
+impl KeyProfile {
+    /// The weights of the major profile, starting from the tonic.
+    pub fn major(self) -> [f64; 12] {
+        match self {
+            KeyProfile::KrumhanslKessler => KRUMHANSL_KESSLER_MAJOR,
+            KeyProfile::Temperley => TEMPERLEY_MAJOR,
+        }
+    }
+
+    /// The weights of the minor profile, starting from the tonic.
+    pub fn minor(self) -> [f64; 12] {
+        match self {
+            KeyProfile::KrumhanslKessler => KRUMHANSL_KESSLER_MINOR,
+            KeyProfile::Temperley => TEMPERLEY_MINOR,
+        }
+    }
+}
+
+/// A candidate key and how well the material correlates with its profile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyCandidate {
+    /// The candidate key, with `None` for major and `Some(Mode::Aeolian)` for minor.
+    pub key: KeySignature,
+    /// The Pearson correlation between the material and the key's profile, from -1 to 1.
+    pub correlation: f64,
+}
+
+/// Estimates the key of a passage with the Krumhansl-Schmuckler algorithm.
+#[derive(Debug, Clone, Default)]
+pub struct KeyFinder {
+    profile: KeyProfile,
+}
+
+impl KeyFinder {
+    /// Create a key finder using the given profiles.
+    pub fn new(profile: KeyProfile) -> Self {
+        KeyFinder { profile }
+    }
+
+    /// Rank all 24 major and minor keys for notes that each count once.
+    pub fn rank(&self, notes: &[Note]) -> Vec<KeyCandidate> {
+        let weighted: Vec<(Note, f64)> = notes.iter().map(|note| (note.clone(), 1.0)).collect();
+        self.rank_weighted(&weighted)
+    }
+
+    /// Rank all 24 major and minor keys for notes weighted by duration or any other measure.
+    pub fn rank_weighted(&self, notes: &[(Note, f64)]) -> Vec<KeyCandidate> {
+        let mut weights = [0.0; 12];
+        for (note, weight) in notes {
+            weights[note.pitch.into_u8() as usize] += weight;
+        }
+        self.rank_pitch_classes(&weights)
+    }
+
+    /// Rank all 24 major and minor keys for a total weight per pitch class, where index 0 is C.
+    ///
+    /// Returns no candidates when every pitch class has the same weight.
+    pub fn rank_pitch_classes(&self, weights: &[f64; 12]) -> Vec<KeyCandidate> {
+        let mut candidates = Vec::with_capacity(24);
+        for tonic in 0..12 {
+            for &minor in [false, true].iter() {
+                let profile = if minor {
+                    self.profile.minor()
+                } else {
+                    self.profile.major()
+                };
+                let rotated: Vec<f64> = (0..12)
+                    .map(|pitch_class| profile[(pitch_class + 12 - tonic) % 12])
+                    .collect();
+                let correlation = match pearson(weights, &rotated) {
+                    Some(correlation) => correlation,
+                    None => return Vec::new(),
+                };
+                candidates.push(KeyCandidate {
+                    key: conventional_key(tonic as u8, minor),
+                    correlation,
+                });
+            }
+        }
+        candidates.sort_by(|a, b| b.correlation.partial_cmp(&a.correlation).unwrap());
+        candidates
+    }
+
+    /// The most likely key for the notes, if they favour any pitch class.
+    pub fn find(&self, notes: &[Note]) -> Option<KeySignature> {
+        self.rank(notes)
+            .into_iter()
+            .next()
+            .map(|candidate| candidate.key)
+    }
+}
+
+/// Spell a key with at most six sharps or five flats, so Db major wins over C# major.
+pub(crate) fn conventional_key(tonic: u8, minor: bool) -> KeySignature {
+    let major_tonic = if minor { (tonic + 3) % 12 } else { tonic };
+    let fifths = (major_tonic as i16 * 7 + 5).rem_euclid(12) - 5;
+    let mode = if minor { Some(Mode::Aeolian) } else { None };
+    KeySignature::from_fifths(fifths, mode)
+}
+
+fn pearson(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    let count = xs.len() as f64;
+    let mean_x = xs.iter().sum::<f64>() / count;
+    let mean_y = ys.iter().sum::<f64>() / count;
+    let mut covariance = 0.0;
+    let mut variance_x = 0.0;
+    let mut variance_y = 0.0;
+    for (x, y) in xs.iter().zip(ys) {
+        covariance += (x - mean_x) * (y - mean_y);
+        variance_x += (x - mean_x).powi(2);
+        variance_y += (y - mean_y).powi(2);
+    }
+    let denominator = (variance_x * variance_y).sqrt();
+    if denominator > f64::EPSILON {
+        Some(covariance / denominator)
+    } else {
+        None
+    }
+}
@@ -1,32 +1,22 @@
 use crate::note::{NoteLetter, Pitch, PitchSymbol};
-use crate::scale::Mode;
-use lazy_static::lazy_static;
-use std::collections::HashMap;
-
-lazy_static! {
-    static ref KEY_SIGNATURE_SPELLINGS: HashMap<(NoteLetter, i8), Vec<PitchSymbol>> = {
-        let mut m = HashMap::new();
-        m.insert((NoteLetter::C, 0), vec![PitchSymbol::C, PitchSymbol::D, PitchSymbol::E, PitchSymbol::F, PitchSymbol::G, PitchSymbol::A, PitchSymbol::B]);
-        m.insert((NoteLetter::G, 0), vec![PitchSymbol::G, PitchSymbol::A, PitchSymbol::B, PitchSymbol::C, PitchSymbol::D, PitchSymbol::E, PitchSymbol::Fs]);
-        m.insert((NoteLetter::D, 0), vec![PitchSymbol::D, PitchSymbol::E, PitchSymbol::Fs, PitchSymbol::G, PitchSymbol::A, PitchSymbol::B, PitchSymbol::Cs]);
-        m.insert((NoteLetter::A, 0), vec![PitchSymbol::A, PitchSymbol::B, PitchSymbol::Cs, PitchSymbol::D, PitchSymbol::E, PitchSymbol::Fs, PitchSymbol::Gs]);
-        m.insert((NoteLetter::E, 0), vec![PitchSymbol::E, PitchSymbol::Fs, PitchSymbol::Gs, PitchSymbol::A, PitchSymbol::B, PitchSymbol::Cs, PitchSymbol::Ds]);
-        m.insert((NoteLetter::B, 0), vec![PitchSymbol::B, PitchSymbol::Cs, PitchSymbol::Ds, PitchSymbol::E, PitchSymbol::Fs, PitchSymbol::Gs, PitchSymbol::As]);
-        m.insert((NoteLetter::F, 0), vec![PitchSymbol::F, PitchSymbol::G, PitchSymbol::A, PitchSymbol::Bb, PitchSymbol::C, PitchSymbol::D, PitchSymbol::E]);
-        m.insert((NoteLetter::B, -1), vec![PitchSymbol::Bb, PitchSymbol::C, PitchSymbol::D, PitchSymbol::Eb, PitchSymbol::F, PitchSymbol::G, PitchSymbol::A]);
-        m.insert((NoteLetter::E, -1), vec![PitchSymbol::Eb, PitchSymbol::F, PitchSymbol::G, PitchSymbol::Ab, PitchSymbol::Bb, PitchSymbol::C, PitchSymbol::D]);
-        m.insert((NoteLetter::A, -1), vec![PitchSymbol::Ab, PitchSymbol::Bb, PitchSymbol::C, PitchSymbol::Db, PitchSymbol::Eb, PitchSymbol::F, PitchSymbol::G]);
-        m.insert((NoteLetter::D, -1), vec![PitchSymbol::Db, PitchSymbol::Eb, PitchSymbol::F, PitchSymbol::Gb, PitchSymbol::Ab, PitchSymbol::Bb, PitchSymbol::C]);
-        m.insert((NoteLetter::G, -1), vec![PitchSymbol::Gb, PitchSymbol::Ab, PitchSymbol::Bb, PitchSymbol::Cb, PitchSymbol::Db, PitchSymbol::Eb, PitchSymbol::F]);
-        m.insert((NoteLetter::F, 1), vec![PitchSymbol::Fs, PitchSymbol::Gs, PitchSymbol::As, PitchSymbol::B, PitchSymbol::Cs, PitchSymbol::Ds, PitchSymbol::Es]);
-        m.insert((NoteLetter::C, 1), vec![PitchSymbol::Cs, PitchSymbol::Ds, PitchSymbol::Es, PitchSymbol::Fs, PitchSymbol::Gs, PitchSymbol::As, PitchSymbol::Bs]);
-        m.insert((NoteLetter::C, -1), vec![PitchSymbol::Cb, PitchSymbol::Db, PitchSymbol::Eb, PitchSymbol::Fb, PitchSymbol::Gb, PitchSymbol::Ab, PitchSymbol::Bb]);
-        m
-    };
-}
+use crate::scale::{Mode, ScaleType};
+
+/// The letters in the order their sharps are written; flats use the reverse order.
+const SHARP_ORDER: [NoteLetter; 7] = [
+    NoteLetter::F,
+    NoteLetter::C,
+    NoteLetter::G,
+    NoteLetter::D,
+    NoteLetter::A,
+    NoteLetter::E,
+    NoteLetter::B,
+];
+
+/// Distance on the circle of fifths from a major tonic to each degree of its scale.
+const DEGREE_FIFTHS: [i16; 7] = [0, 2, 4, -1, 1, 3, 5];
 
 /// A key signature.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct KeySignature {
     /// The tonic of the key signature.
     pub tonic: Pitch,
@@ -37,10 +27,7 @@ pub struct KeySignature {
 impl KeySignature {
     /// Create a new key signature.
     pub fn new(tonic: Pitch) -> Self {
-        KeySignature {
-            tonic,
-            mode: None,
-        }
+        KeySignature { tonic, mode: None }
     }
 
     /// Create a new key signature with a mode.
@@ -48,106 +35,223 @@ impl KeySignature {
         KeySignature { tonic, mode }
     }
 
-    fn relative_major_key(
-        &self,
-        semitones_down: u8,
-        letter_steps_down: i16,
-    ) -> (NoteLetter, i8) {
-        let pitch_class = (self.tonic.into_u8() + 12 - semitones_down) % 12;
-        let letter = self.tonic.letter.offset(-letter_steps_down);
-        let pitch = Pitch::from_u8_with_letter(pitch_class, letter);
-        (pitch.letter, pitch.accidental)
+    /// Create the key with `fifths` sharps (positive) or flats (negative) whose tonic is the
+    /// given mode of that signature.
+    ///
+    /// Counts beyond seven produce theoretical keys, so eight sharps is G# major.
+    pub fn from_fifths(fifths: i16, mode: Option<Mode>) -> Self {
+        let major_tonic = SHARP_ORDER[(fifths as i32 + 1).rem_euclid(7) as usize];
+        let letter = major_tonic.offset(signature_degree(mode) as i16);
+        let tonic = Pitch::new(letter, accidental_for(fifths, letter));
+        KeySignature { tonic, mode }
+    }
+
+    /// Create a key from the values of a MIDI key signature meta event.
+    pub fn from_midi(sharps_flats: i8, minor: bool) -> Self {
+        let mode = if minor { Some(Mode::Aeolian) } else { None };
+        Self::from_fifths(sharps_flats as i16, mode)
+    }
+
+    /// The values of a MIDI key signature meta event, if the signature fits in seven accidentals.
+    pub fn to_midi(&self) -> Option<(i8, bool)> {
+        let fifths = self.fifths();
+        if (-7..=7).contains(&fifths) {
+            Some((fifths as i8, self.is_minor()))
+        } else {
+            None
+        }
+    }
+
+    /// The values of a MIDI key signature meta event, writing signatures beyond seven
+    /// accidentals as their enharmonic equivalents, so eight sharps is written as four flats.
+    pub fn to_midi_enharmonic(&self) -> (i8, bool) {
+        let fifths = match self.fifths() {
+            fifths if (-7..=7).contains(&fifths) => fifths,
+            fifths => (fifths + 6).rem_euclid(12) - 6,
+        };
+        (fifths as i8, self.is_minor())
+    }
+
+    /// The number of sharps (positive) or flats (negative) in the signature.
+    pub fn fifths(&self) -> i16 {
+        let letter_fifths = SHARP_ORDER
+            .iter()
+            .position(|&letter| letter == self.tonic.letter)
+            .unwrap() as i16
+            - 1;
+        letter_fifths + 7 * self.tonic.accidental as i16
+            - DEGREE_FIFTHS[signature_degree(self.mode)]
+    }
+
+    /// The number of sharps in the signature.
+    pub fn sharps(&self) -> u8 {
+        self.fifths().clamp(0, u8::MAX as i16) as u8
+    }
+
+    /// The number of flats in the signature.
+    pub fn flats(&self) -> u8 {
+        (-self.fifths()).clamp(0, u8::MAX as i16) as u8
+    }
+
+    /// The altered letters of the signature in the order they are written, such as F#, C#, G#.
+    ///
+    /// Theoretical keys alter each letter once, with double accidentals where needed.
+    pub fn accidentals(&self) -> Vec<Pitch> {
+        let fifths = self.fifths();
+        let count = fifths.unsigned_abs().min(7) as usize;
+        let order: Vec<NoteLetter> = if fifths >= 0 {
+            SHARP_ORDER.to_vec()
+        } else {
+            SHARP_ORDER.iter().rev().copied().collect()
+        };
+        order[..count]
+            .iter()
+            .map(|&letter| Pitch::new(letter, accidental_for(fifths, letter)))
+            .collect()
+    }
+
+    /// The seven pitches of the signature, starting from the tonic.
+    pub fn pitches(&self) -> Vec<Pitch> {
+        let fifths = self.fifths();
+        (0..7)
+            .map(|step| {
+                let letter = self.tonic.letter.offset(step);
+                Pitch::new(letter, accidental_for(fifths, letter))
+            })
+            .collect()
+    }
+
+    /// Whether the key is a minor key, using the natural, harmonic or melodic minor.
+    pub fn is_minor(&self) -> bool {
+        matches!(
+            self.mode,
+            Some(Mode::Aeolian) | Some(Mode::HarmonicMinor) | Some(Mode::MelodicMinor)
+        )
+    }
+
+    /// The major key sharing this signature.
+    pub fn relative_major(&self) -> Self {
+        Self::from_fifths(self.fifths(), None)
+    }
+
+    /// The minor key sharing this signature.
+    pub fn relative_minor(&self) -> Self {
+        Self::from_fifths(self.fifths(), Some(Mode::Aeolian))
+    }
+
+    /// The major key on the same tonic.
+    pub fn parallel_major(&self) -> Self {
+        Self::new(self.tonic)
+    }
+
+    /// The minor key on the same tonic.
+    pub fn parallel_minor(&self) -> Self {
+        Self::new_with_mode(self.tonic, Some(Mode::Aeolian))
+    }
+
+    /// The neighbouring key a fifth higher, with one more sharp or one fewer flat.
+    pub fn dominant(&self) -> Self {
+        Self::from_fifths(self.fifths() + 1, self.mode)
+    }
+
+    /// The neighbouring key a fifth lower, with one more flat or one fewer sharp.
+    pub fn subdominant(&self) -> Self {
+        Self::from_fifths(self.fifths() - 1, self.mode)
+    }
+
+    /// Spell a pitch with the letter the signature gives it, falling back to
+    /// [`KeySignature::get_preferred_spelling`] for chromatic pitches.
+    pub fn spell(&self, pitch: Pitch) -> Pitch {
+        let fifths = self.fifths();
+        SHARP_ORDER
+            .iter()
+            .map(|&letter| Pitch::new(letter, accidental_for(fifths, letter)))
+            .find(|candidate| candidate.into_u8() == pitch.into_u8())
+            .unwrap_or_else(|| Pitch::from(self.get_preferred_spelling(pitch)))
     }
 
     pub fn get_preferred_spelling(&self, pitch: Pitch) -> PitchSymbol {
         use PitchSymbol::*;
-        
-        // Determine the key to use for spelling based on mode
-        let (key_tonic_letter, key_accidental) = match self.mode {
-            Some(Mode::Aeolian) => {
-                // Aeolian (minor) is the 6th degree, 9 semitones above its relative major
-                self.relative_major_key(9, 5)
-            },
-            Some(Mode::Dorian) => {
-                // Dorian is the 2nd degree, 2 semitones above its relative major  
-                self.relative_major_key(2, 1)
-            },
-            Some(Mode::Phrygian) => {
-                // Phrygian is the 3rd degree, 4 semitones above its relative major
-                self.relative_major_key(4, 2)
-            },
-            Some(Mode::Lydian) => {
-                // Lydian is the 4th degree, 5 semitones above its relative major
-                self.relative_major_key(5, 3)
-            },
-            Some(Mode::Mixolydian) => {
-                // Mixolydian is the 5th degree, 7 semitones above its relative major
-                self.relative_major_key(7, 4)
-            },
-            Some(Mode::Locrian) => {
-                // Locrian is the 7th degree, 11 semitones above its relative major
-                self.relative_major_key(11, 6)
-            },
-            _ => {
-                // For major (Ionian) or no mode, use the tonic as is
-                (self.tonic.letter, self.tonic.accidental)
-            }
-        };
-        
-        // Get the key signature accidentals for the determined key
-        if let Some(key_accidentals) = KEY_SIGNATURE_SPELLINGS.get(&(key_tonic_letter, key_accidental)) {
-            // Check if this pitch has a preferred spelling in this key
-            for &accidental in key_accidentals {
-                if Pitch::from(accidental).into_u8() == pitch.into_u8() {
-                    return accidental;
+
+        // Prefer the letter the signature itself gives this pitch class
+        let fifths = self.fifths();
+        for &letter in SHARP_ORDER.iter() {
+            let candidate = Pitch::new(letter, accidental_for(fifths, letter));
+            if candidate.into_u8() == pitch.into_u8() {
+                if let Some(symbol) = pitch_symbol(candidate) {
+                    return symbol;
                 }
             }
         }
 
-        // For C major and its modes, we prefer sharp spellings
-        if self.tonic.letter == NoteLetter::C && self.tonic.accidental == 0 {
-            match pitch.into_u8() {
-                0 => C,
-                1 => Cs,  // C♯
-                2 => D,
-                3 => Ds,  // D♯
-                4 => E,
-                5 => F,
-                6 => Fs,  // F♯
-                7 => G,
-                8 => Gs,  // G♯
-                9 => A,
-                10 => As, // A♯
-                11 => B,
-                _ => unreachable!(),
-            }
-        } else {
-            // For other keys, follow traditional rules
-            let is_sharp_key = matches!(
-                self.tonic.letter,
-                NoteLetter::G
-                    | NoteLetter::D
-                    | NoteLetter::A
-                    | NoteLetter::E
-                    | NoteLetter::B
-            );
-
-            // Use sharps for sharp keys and leading tones, flats for flat keys
-            match pitch.into_u8() {
-                0 => C,
-                1 => if is_sharp_key { Cs } else { Db },  // C♯/D♭
-                2 => D,
-                3 => if is_sharp_key { Ds } else { Eb },  // D♯/E♭
-                4 => E,
-                5 => F,
-                6 => if is_sharp_key { Fs } else { Gb },  // F♯/G♭
-                7 => G,
-                8 => if is_sharp_key { Gs } else { Ab },  // G♯/A♭
-                9 => A,
-                10 => if is_sharp_key { As } else { Bb }, // A♯/B♭
-                11 => B,
-                _ => unreachable!(),
-            }
+        // Chromatic pitches take sharps in sharp keys and flats in flat keys; keys without
+        // accidentals, like C major and its modes, prefer sharps.
+        let is_sharp_key = fifths >= 0;
+        match pitch.into_u8() {
+            0 => C,
+            1 => if is_sharp_key { Cs } else { Db },  // C♯/D♭
+            2 => D,
+            3 => if is_sharp_key { Ds } else { Eb },  // D♯/E♭
+            4 => E,
+            5 => F,
+            6 => if is_sharp_key { Fs } else { Gb },  // F♯/G♭
+            7 => G,
+            8 => if is_sharp_key { Gs } else { Ab },  // G♯/A♭
+            9 => A,
+            10 => if is_sharp_key { As } else { Bb }, // A♯/B♭
+            11 => B,
+            _ => unreachable!(),
         }
     }
 }
+
+/// The degree of the major scale on which a mode's tonic sits when reading its key signature.
+fn signature_degree(mode: Option<Mode>) -> usize {
+    match mode {
+        Some(mode) if mode.scale_type() == ScaleType::Diatonic => mode.rotation(),
+        // Harmonic and melodic minor are written with the natural minor signature.
+        Some(Mode::HarmonicMinor) | Some(Mode::MelodicMinor) => 5,
+        _ => 0,
+    }
+}
+
+/// The accidental a signature of `fifths` sharps or flats applies to `letter`.
+fn accidental_for(fifths: i16, letter: NoteLetter) -> i8 {
+    let position = SHARP_ORDER.iter().position(|&l| l == letter).unwrap() as i16;
+    let accidental = if fifths >= 0 {
+        (fifths - position + 6).div_euclid(7)
+    } else {
+        -(-fifths - (6 - position) + 6).div_euclid(7)
+    };
+    accidental.clamp(i8::MIN as i16, i8::MAX as i16) as i8
+}
+
+fn pitch_symbol(pitch: Pitch) -> Option<PitchSymbol> {
+    use NoteLetter as L;
+    use PitchSymbol::*;
+    let symbol = match (pitch.letter, pitch.accidental) {
+        (L::C, -1) => Cb,
+        (L::C, 0) => C,
+        (L::C, 1) => Cs,
+        (L::D, -1) => Db,
+        (L::D, 0) => D,
+        (L::D, 1) => Ds,
+        (L::E, -1) => Eb,
+        (L::E, 0) => E,
+        (L::E, 1) => Es,
+        (L::F, -1) => Fb,
+        (L::F, 0) => F,
+        (L::F, 1) => Fs,
+        (L::G, -1) => Gb,
+        (L::G, 0) => G,
+        (L::G, 1) => Gs,
+        (L::A, -1) => Ab,
+        (L::A, 0) => A,
+        (L::A, 1) => As,
+        (L::B, -1) => Bb,
+        (L::B, 0) => B,
+        (L::B, 1) => Bs,
+        _ => return None,
+    };
+    Some(symbol)
+}
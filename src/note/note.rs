@@ -1,6 +1,10 @@
-use crate::note::Pitch;
+use crate::interval::Interval;
+use crate::note::{NoteError, Pitch, Spelling};
+use crate::scale::Direction;
 use std::fmt;
 use std::fmt::Formatter;
+use std::ops::{Add, Sub};
+use std::str::FromStr;
 
 /// A note.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -35,6 +39,80 @@ impl Note {
         let midi_value = (self.octave as i32 + 1) * 12 + natural + self.pitch.accidental as i32;
         midi_value.clamp(0, 127) as u8
     }
+
+    /// The note with a given spelling at a MIDI pitch number of the same pitch class.
+    pub(crate) fn from_midi_with_pitch(pitch: Pitch, midi: i16) -> Self {
+        let octave = (midi - pitch.semitones_from_c()).div_euclid(12) - 1;
+        Note::new(pitch, octave)
+    }
+
+    /// Move the note by a spelled interval, keeping the letter distance of the interval.
+    ///
+    /// Transposing E4 up a major third gives G#4, while transposing it down gives C4.
+    pub fn transpose(&self, interval: Interval, direction: Direction) -> Note {
+        match direction {
+            Direction::Ascending => interval.second_note_from(self.clone()),
+            Direction::Descending => interval.second_note_down_from(self.clone()),
+        }
+    }
+
+    /// Move the note by a signed number of semitones, spelling the result with `spelling`.
+    pub fn transpose_semitones(&self, semitones: i16, spelling: &Spelling) -> Note {
+        let target =
+            self.pitch.semitones_from_c() as i32 + self.octave as i32 * 12 + semitones as i32;
+        let pitch = spelling.spell(target.rem_euclid(12) as u8);
+        let octave = (target - pitch.semitones_from_c() as i32).div_euclid(12);
+        Note::new(pitch, octave.clamp(i16::MIN as i32, i16::MAX as i32) as i16)
+    }
+}
+
+impl Add<Interval> for Note {
+    type Output = Note;
+
+    fn add(self, interval: Interval) -> Note {
+        self.transpose(interval, Direction::Ascending)
+    }
+}
+
+impl Add<Interval> for &Note {
+    type Output = Note;
+
+    fn add(self, interval: Interval) -> Note {
+        self.transpose(interval, Direction::Ascending)
+    }
+}
+
+impl Sub<Interval> for Note {
+    type Output = Note;
+
+    fn sub(self, interval: Interval) -> Note {
+        self.transpose(interval, Direction::Descending)
+    }
+}
+
+impl Sub<Interval> for &Note {
+    type Output = Note;
+
+    fn sub(self, interval: Interval) -> Note {
+        self.transpose(interval, Direction::Descending)
+    }
+}
+
+impl FromStr for Note {
+    type Err = NoteError;
+
+    /// Parse a note in scientific pitch notation such as `Eb4` or `F#-1`.
+    ///
+    /// The octave defaults to 4 when it is omitted.
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let string = string.trim();
+        let (pitch, pitch_match) = Pitch::from_regex(string)?;
+        let octave = match &string[pitch_match.end()..] {
+            "" => 4,
+            octave => octave.parse().map_err(|_| NoteError::InvalidOctave)?,
+        };
+        Ok(Note::new(pitch, octave))
+    }
 }
 
 impl fmt::Display for Note {
@@ -68,6 +146,18 @@ pub trait Notes {
     }
 }
 
+impl Notes for Note {
+    fn notes(&self) -> Vec<Note> {
+        vec![self.clone()]
+    }
+}
+
+impl Notes for Vec<Note> {
+    fn notes(&self) -> Vec<Note> {
+        self.clone()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
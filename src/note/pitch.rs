@@ -1,12 +1,14 @@
 use crate::interval::Interval;
 use crate::note::errors::NoteError;
 use crate::note::pitch_symbol::PitchSymbol;
+use crate::note::Spelling;
 use crate::scale::{Direction, Mode};
 use lazy_static::lazy_static;
 use regex::{Match, Regex};
 use std::collections::HashMap;
 use std::fmt;
 use std::hash::Hash;
+use std::ops::{Add, Sub};
 use std::str::FromStr;
 use strum_macros::EnumIter;
 
@@ -187,36 +189,49 @@ impl Pitch {
         (base as i16 + self.accidental as i16).rem_euclid(12) as u8
     }
 
+    /// The written distance in semitones above the C of the same octave, which is negative for
+    /// C-flat and above 11 for B-sharp.
+    pub(crate) fn semitones_from_c(self) -> i16 {
+        use NoteLetter::*;
+        let natural = match self.letter {
+            C => 0,
+            D => 2,
+            E => 4,
+            F => 5,
+            G => 7,
+            A => 9,
+            B => 11,
+        };
+        natural + self.accidental as i16
+    }
+
+    /// Move the pitch by a spelled interval in the given direction.
+    pub fn transpose(self, interval: Interval, direction: Direction) -> Self {
+        match direction {
+            Direction::Ascending => Self::from_interval(self, interval),
+            Direction::Descending => Self::from_interval_down(self, interval),
+        }
+    }
+
+    /// Move the pitch by a signed number of semitones, spelling the result with `spelling`.
+    pub fn transpose_semitones(self, semitones: i16, spelling: &Spelling) -> Self {
+        spelling.spell((self.into_u8() as i16 + semitones).rem_euclid(12) as u8)
+    }
+
     /// Create a pitch by moving up the given pitch by an interval.
     pub fn from_interval(pitch: Self, interval: Interval) -> Self {
         let current_pitch = pitch.into_u8();
-        let new_pitch = current_pitch + interval.semitone_count;
-        let letter_steps = match interval.number {
-            crate::interval::Number::Unison | crate::interval::Number::Octave => 0,
-            crate::interval::Number::Second => 1,
-            crate::interval::Number::Third => 2,
-            crate::interval::Number::Fourth => 3,
-            crate::interval::Number::Fifth => 4,
-            crate::interval::Number::Sixth => 5,
-            crate::interval::Number::Seventh => 6,
-        };
+        let new_pitch = (current_pitch as u16 + interval.semitone_count as u16) % 12;
+        let letter_steps = interval.number.letter_steps();
 
-        Self::from_u8_with_letter(new_pitch, pitch.letter.offset(letter_steps))
+        Self::from_u8_with_letter(new_pitch as u8, pitch.letter.offset(letter_steps))
     }
 
     /// Create a pitch by moving down the given pitch by an interval.
     pub fn from_interval_down(pitch: Self, interval: Interval) -> Self {
         let current_pitch = pitch.into_u8();
-        let new_pitch = (12 + (current_pitch as i16 - interval.semitone_count as i16)) % 12;
-        let letter_steps = match interval.number {
-            crate::interval::Number::Unison | crate::interval::Number::Octave => 0,
-            crate::interval::Number::Second => 1,
-            crate::interval::Number::Third => 2,
-            crate::interval::Number::Fourth => 3,
-            crate::interval::Number::Fifth => 4,
-            crate::interval::Number::Sixth => 5,
-            crate::interval::Number::Seventh => 6,
-        };
+        let new_pitch = (current_pitch as i16 - interval.semitone_count as i16).rem_euclid(12);
+        let letter_steps = interval.number.letter_steps();
 
         Self::from_u8_with_letter(new_pitch as u8, pitch.letter.offset(-letter_steps))
     }
@@ -326,6 +341,22 @@ impl fmt::Display for Pitch {
     }
 }
 
+impl Add<Interval> for Pitch {
+    type Output = Pitch;
+
+    fn add(self, interval: Interval) -> Pitch {
+        self.transpose(interval, Direction::Ascending)
+    }
+}
+
+impl Sub<Interval> for Pitch {
+    type Output = Pitch;
+
+    fn sub(self, interval: Interval) -> Pitch {
+        self.transpose(interval, Direction::Descending)
+    }
+}
+
 impl FromStr for Pitch {
     type Err = NoteError;
 
@@ -0,0 +1,30 @@
+use crate::note::{KeySignature, Pitch};
+use crate::scale::Direction;
+
+/// How to spell a pitch class that was reached by counting semitones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Spelling {
+    /// Spell black keys with sharps (C#, D#, F#, G#, A#).
+    Sharps,
+    /// Spell black keys with flats (Db, Eb, Gb, Ab, Bb).
+    Flats,
+    /// Spell pitches the way the given key signature prefers.
+    Key(KeySignature),
+}
+
+impl Spelling {
+    /// Spell a pitch class, where 0 is C and 11 is B.
+    pub fn spell(&self, pitch_class: u8) -> Pitch {
+        match self {
+            Spelling::Sharps => Pitch::from_u8(pitch_class),
+            Spelling::Flats => Pitch::from_u8_with_direction(pitch_class, Direction::Descending),
+            Spelling::Key(key) => key.spell(Pitch::from_u8(pitch_class)),
+        }
+    }
+}
+
+impl From<KeySignature> for Spelling {
+    fn from(key: KeySignature) -> Self {
+        Spelling::Key(key)
+    }
+}
@@ -0,0 +1,21 @@
+//! Pitch-class sets and twelve-tone rows for post-tonal analysis.
+//!
+//! ```
+//! use rust_music_theory::chord::Chord;
+//! use rust_music_theory::pitch_class_set::PitchClassSet;
+//!
+//! let set = PitchClassSet::from_notes(&Chord::parse("Cm").unwrap());
+//! assert_eq!(set.prime_form(), [0, 3, 7]);
+//! assert_eq!(set.forte_number().to_string(), "3-11");
+//! assert_eq!(set.interval_vector(), [0, 0, 1, 1, 1, 0]);
+//! ```
+
+mod errors;
+mod forte;
+mod pitch_class_set;
+mod tone_row;
+
+pub use errors::PitchClassSetError;
+pub use forte::ForteNumber;
+pub use pitch_class_set::PitchClassSet;
+pub use tone_row::{RowForm, RowLabel, ToneRow};
@@ -0,0 +1,44 @@
+use std::error;
+use std::fmt;
+
+/// An error while building a pitch-class set or tone row or reading their names.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PitchClassSetError {
+    InvalidPitchClass(u8),
+    InvalidForteNumber(String),
+    RowLength(usize),
+    RepeatedPitchClass(u8),
+    InvalidRowLabel(String),
+}
+
+impl fmt::Display for PitchClassSetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PitchClassSetError::InvalidPitchClass(class) => {
+                write!(f, "Invalid pitch class: {}, expected 0 to 11", class)
+            }
+            PitchClassSetError::InvalidForteNumber(name) => {
+                write!(f, "Unknown Forte number: {}", name)
+            }
+            PitchClassSetError::RowLength(length) => {
+                write!(
+                    f,
+                    "A twelve-tone row needs 12 pitch classes, got {}",
+                    length
+                )
+            }
+            PitchClassSetError::RepeatedPitchClass(class) => {
+                write!(f, "Pitch class {} appears more than once in the row", class)
+            }
+            PitchClassSetError::InvalidRowLabel(label) => {
+                write!(
+                    f,
+                    "Invalid row label: {}, expected a form such as P0 or RI7",
+                    label
+                )
+            }
+        }
+    }
+}
+
+impl error::Error for PitchClassSetError {}
@@ -0,0 +1,248 @@
+use crate::pitch_class_set::{PitchClassSet, PitchClassSetError};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// The set classes of three to six pitch classes in Forte's catalogue, in order: each one's
+/// ordinal, whether it is Z-related to another class, and a member of the class. Larger set
+/// classes share the number of their complements.
+const CATALOGUE: [(u8, bool, &str); 129] = [
+    (1, false, "012"),
+    (2, false, "013"),
+    (3, false, "014"),
+    (4, false, "015"),
+    (5, false, "016"),
+    (6, false, "024"),
+    (7, false, "025"),
+    (8, false, "026"),
+    (9, false, "027"),
+    (10, false, "036"),
+    (11, false, "037"),
+    (12, false, "048"),
+    (1, false, "0123"),
+    (2, false, "0124"),
+    (3, false, "0134"),
+    (4, false, "0125"),
+    (5, false, "0126"),
+    (6, false, "0127"),
+    (7, false, "0145"),
+    (8, false, "0156"),
+    (9, false, "0167"),
+    (10, false, "0235"),
+    (11, false, "0135"),
+    (12, false, "0236"),
+    (13, false, "0136"),
+    (14, false, "0237"),
+    (15, true, "0146"),
+    (16, false, "0157"),
+    (17, false, "0347"),
+    (18, false, "0147"),
+    (19, false, "0148"),
+    (20, false, "0158"),
+    (21, false, "0246"),
+    (22, false, "0247"),
+    (23, false, "0257"),
+    (24, false, "0248"),
+    (25, false, "0268"),
+    (26, false, "0358"),
+    (27, false, "0258"),
+    (28, false, "0369"),
+    (29, true, "0137"),
+    (1, false, "01234"),
+    (2, false, "01235"),
+    (3, false, "01245"),
+    (4, false, "01236"),
+    (5, false, "01237"),
+    (6, false, "01256"),
+    (7, false, "01267"),
+    (8, false, "02346"),
+    (9, false, "01246"),
+    (10, false, "01346"),
+    (11, false, "02347"),
+    (12, true, "01356"),
+    (13, false, "01248"),
+    (14, false, "01257"),
+    (15, false, "01268"),
+    (16, false, "01347"),
+    (17, true, "01348"),
+    (18, true, "01457"),
+    (19, false, "01367"),
+    (20, false, "01568"),
+    (21, false, "01458"),
+    (22, false, "01478"),
+    (23, false, "02357"),
+    (24, false, "01357"),
+    (25, false, "02358"),
+    (26, false, "02458"),
+    (27, false, "01358"),
+    (28, false, "02368"),
+    (29, false, "01368"),
+    (30, false, "01468"),
+    (31, false, "01369"),
+    (32, false, "01469"),
+    (33, false, "02468"),
+    (34, false, "02469"),
+    (35, false, "02479"),
+    (36, true, "01247"),
+    (37, true, "03458"),
+    (38, true, "01258"),
+    (1, false, "012345"),
+    (2, false, "012346"),
+    (3, true, "012356"),
+    (4, true, "012456"),
+    (5, false, "012367"),
+    (6, true, "012567"),
+    (7, false, "012678"),
+    (8, false, "023457"),
+    (9, false, "012357"),
+    (10, true, "013457"),
+    (11, true, "012457"),
+    (12, true, "012467"),
+    (13, true, "013467"),
+    (14, false, "013458"),
+    (15, false, "012458"),
+    (16, false, "014568"),
+    (17, true, "012478"),
+    (18, false, "012578"),
+    (19, true, "013478"),
+    (20, false, "014589"),
+    (21, false, "023468"),
+    (22, false, "012468"),
+    (23, true, "023568"),
+    (24, true, "013468"),
+    (25, true, "013568"),
+    (26, true, "013578"),
+    (27, false, "013469"),
+    (28, true, "013569"),
+    (29, true, "013689"),
+    (30, false, "013679"),
+    (31, false, "014579"),
+    (32, false, "024579"),
+    (33, false, "023579"),
+    (34, false, "013579"),
+    (35, false, "02468T"),
+    (36, true, "012347"),
+    (37, true, "012348"),
+    (38, true, "012378"),
+    (39, true, "023458"),
+    (40, true, "012358"),
+    (41, true, "012368"),
+    (42, true, "012369"),
+    (43, true, "012568"),
+    (44, true, "012569"),
+    (45, true, "023469"),
+    (46, true, "012469"),
+    (47, true, "012479"),
+    (48, true, "012579"),
+    (49, true, "013479"),
+    (50, true, "014679"),
+];
+
+lazy_static! {
+    /// Every set class keyed by the number its members share, with its Forte number.
+    static ref BY_SET_CLASS: HashMap<u16, ForteNumber> = {
+        let mut numbers = HashMap::new();
+        let mut add = |set: PitchClassSet, ordinal: u8, z: bool| {
+            let number = ForteNumber {
+                cardinality: set.len() as u8,
+                ordinal,
+                z,
+            };
+            numbers.insert(set.set_class(), number);
+            // Complements share the number; a hexachord's complement is itself or its Z-pair.
+            let complement = set.complement();
+            numbers.entry(complement.set_class()).or_insert(ForteNumber {
+                cardinality: complement.len() as u8,
+                ordinal,
+                z,
+            });
+        };
+        add(PitchClassSet::from_mask(0), 1, false);
+        add(PitchClassSet::from_mask(1), 1, false);
+        for interval in 1..=6 {
+            add(PitchClassSet::from_mask(1 | 1 << interval), interval, false);
+        }
+        for &(ordinal, z, classes) in CATALOGUE.iter() {
+            add(PitchClassSet::from_mask(parse_classes(classes)), ordinal, z);
+        }
+        numbers
+    };
+
+    /// A member of every set class keyed by its Forte number.
+    static ref BY_NUMBER: HashMap<ForteNumber, PitchClassSet> = BY_SET_CLASS
+        .iter()
+        .map(|(&set_class, &number)| (number, PitchClassSet::from_mask(set_class)))
+        .collect();
+}
+
+fn parse_classes(classes: &str) -> u16 {
+    classes
+        .chars()
+        .filter_map(|class| match class {
+            'T' => Some(10),
+            'E' => Some(11),
+            digit => digit.to_digit(10),
+        })
+        .fold(0, |mask, class| mask | 1 << class)
+}
+
+/// A set class's name in Allen Forte's catalogue, such as 3-11 or 4-Z15.
+///
+/// Set classes of seven to nine pitch classes share the ordinal of their complements, and the
+/// empty, single-note, dyad, and aggregate classes are numbered 0-1, 1-1, 2-1 to 2-6, and so on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ForteNumber {
+    /// The number of pitch classes.
+    pub cardinality: u8,
+    /// The position in the catalogue among set classes of the same size.
+    pub ordinal: u8,
+    /// Whether the class shares its interval vector with another, marked with a Z.
+    pub z: bool,
+}
+
+impl ForteNumber {
+    /// Parse a Forte number such as `4-Z15` or `3-11`.
+    ///
+    /// # Errors
+    ///
+    /// Fails unless the text names a set class in the catalogue. The Z may be left out.
+    pub fn parse(text: &str) -> Result<Self, PitchClassSetError> {
+        let invalid = || PitchClassSetError::InvalidForteNumber(text.to_string());
+        let (cardinality, ordinal) = text.trim().split_once('-').ok_or_else(invalid)?;
+        let ordinal = ordinal.trim_start_matches(['Z', 'z']);
+        let cardinality = cardinality.parse::<u8>().map_err(|_| invalid())?;
+        let ordinal = ordinal.parse::<u8>().map_err(|_| invalid())?;
+        BY_NUMBER
+            .keys()
+            .copied()
+            .find(|number| number.cardinality == cardinality && number.ordinal == ordinal)
+            .ok_or_else(invalid)
+    }
+
+    /// The Forte number of a set's class.
+    pub(crate) fn of(set: &PitchClassSet) -> Self {
+        BY_SET_CLASS[&set.set_class()]
+    }
+
+    /// The prime form of the set class, by Forte's rule, as a set.
+    pub fn set_class(&self) -> PitchClassSet {
+        let member = BY_NUMBER[self];
+        PitchClassSet::new(&member.forte_prime_form()).unwrap_or(member)
+    }
+}
+
+impl fmt::Display for ForteNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let z = if self.z { "Z" } else { "" };
+        write!(f, "{}-{}{}", self.cardinality, z, self.ordinal)
+    }
+}
+
+impl FromStr for ForteNumber {
+    type Err = PitchClassSetError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text)
+    }
+}
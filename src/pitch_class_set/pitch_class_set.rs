@@ -0,0 +1,243 @@
+use crate::note::{Notes, Pitch};
+use crate::pitch_class_set::{ForteNumber, PitchClassSetError};
+use std::fmt;
+
+/// How ties between orderings of the same span are broken when finding normal and prime forms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Packing {
+    /// Forte's rule: the smallest intervals from the first pitch class, reading from the left.
+    Forte,
+    /// Rahn's rule: the smallest intervals from the first pitch class, reading from the right.
+    Rahn,
+}
+
+/// An unordered set of pitch classes, numbered from C = 0 to B = 11.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PitchClassSet {
+    classes: u16,
+}
+
+impl PitchClassSet {
+    /// A set from pitch-class numbers, ignoring duplicates.
+    ///
+    /// # Errors
+    ///
+    /// Fails when a number is above 11.
+    pub fn new(classes: &[u8]) -> Result<Self, PitchClassSetError> {
+        let mut set = PitchClassSet { classes: 0 };
+        for &class in classes {
+            if class > 11 {
+                return Err(PitchClassSetError::InvalidPitchClass(class));
+            }
+            set.classes |= 1 << class;
+        }
+        Ok(set)
+    }
+
+    /// The set of the pitch classes of some pitches.
+    pub fn from_pitches(pitches: &[Pitch]) -> Self {
+        PitchClassSet {
+            classes: pitches
+                .iter()
+                .fold(0, |classes, pitch| classes | 1 << pitch.into_u8()),
+        }
+    }
+
+    /// The set of the pitch classes of the notes of a chord, scale, or other collection.
+    pub fn from_notes<N: Notes + ?Sized>(notes: &N) -> Self {
+        let pitches: Vec<Pitch> = notes.notes().iter().map(|note| note.pitch).collect();
+        Self::from_pitches(&pitches)
+    }
+
+    pub(crate) fn from_mask(classes: u16) -> Self {
+        PitchClassSet {
+            classes: classes & 0xfff,
+        }
+    }
+
+    /// The pitch classes in ascending order.
+    pub fn pitch_classes(&self) -> Vec<u8> {
+        (0..12).filter(|&class| self.contains(class)).collect()
+    }
+
+    /// Whether the set holds a pitch class.
+    pub fn contains(&self, class: u8) -> bool {
+        class < 12 && self.classes & 1 << class != 0
+    }
+
+    /// The number of pitch classes, the set's cardinality.
+    pub fn len(&self) -> usize {
+        self.classes.count_ones() as usize
+    }
+
+    /// Whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.classes == 0
+    }
+
+    /// The set transposed up by a number of semitones, Tn.
+    pub fn transpose(&self, semitones: u8) -> Self {
+        let n = semitones % 12;
+        Self::from_mask(self.classes << n | self.classes >> (12 - n))
+    }
+
+    /// The set inverted around C and then transposed up by a number of semitones, TnI.
+    pub fn invert(&self, semitones: u8) -> Self {
+        let classes = self
+            .pitch_classes()
+            .iter()
+            .fold(0, |classes, &class| classes | 1 << ((12 - class) % 12));
+        Self::from_mask(classes).transpose(semitones)
+    }
+
+    /// The pitch classes the set leaves out.
+    pub fn complement(&self) -> Self {
+        Self::from_mask(!self.classes)
+    }
+
+    /// The set in its most compact ascending ordering, by Rahn's rule.
+    ///
+    /// Of the rotations with the smallest span, this keeps the one whose intervals from the first
+    /// pitch class are smallest reading from the right.
+    pub fn normal_form(&self) -> Vec<u8> {
+        normal_form(&self.pitch_classes(), Packing::Rahn)
+    }
+
+    /// The normal form of the set or its inversion transposed to start on 0, by Rahn's rule, as
+    /// used by most current texts.
+    pub fn prime_form(&self) -> Vec<u8> {
+        prime_form(&self.pitch_classes(), Packing::Rahn)
+    }
+
+    /// The prime form by Forte's rule, which packs intervals from the left.
+    ///
+    /// It differs from [`prime_form`](Self::prime_form) only for set classes 5-20, 6-Z29, 6-31,
+    /// 7-Z18, 7-20, and 8-26.
+    pub fn forte_prime_form(&self) -> Vec<u8> {
+        prime_form(&self.pitch_classes(), Packing::Forte)
+    }
+
+    /// The set class's name in Forte's catalogue, such as 3-11 or 4-Z15.
+    pub fn forte_number(&self) -> ForteNumber {
+        ForteNumber::of(self)
+    }
+
+    /// How many pairs of pitch classes lie each interval class apart, from 1 to 6.
+    pub fn interval_vector(&self) -> [u8; 6] {
+        let classes = self.pitch_classes();
+        let mut vector = [0; 6];
+        for (index, &low) in classes.iter().enumerate() {
+            for &high in &classes[index + 1..] {
+                let interval = high - low;
+                vector[interval.min(12 - interval) as usize - 1] += 1;
+            }
+        }
+        vector
+    }
+
+    /// Whether the sets belong to the same set class, related by Tn or TnI.
+    pub fn is_equivalent(&self, other: &Self) -> bool {
+        self.set_class() == other.set_class()
+    }
+
+    /// Whether the sets share an interval vector without belonging to the same set class.
+    pub fn is_z_related(&self, other: &Self) -> bool {
+        self.interval_vector() == other.interval_vector() && !self.is_equivalent(other)
+    }
+
+    /// Whether every pitch class of the set is in another.
+    pub fn is_subset_of(&self, other: &Self) -> bool {
+        self.classes & !other.classes == 0
+    }
+
+    /// Whether the set holds every pitch class of another.
+    pub fn is_superset_of(&self, other: &Self) -> bool {
+        other.is_subset_of(self)
+    }
+
+    /// Whether some transposition or inversion of the set is a subset of another, so that the
+    /// set's class is found within it.
+    pub fn is_abstract_subset_of(&self, other: &Self) -> bool {
+        self.transformations()
+            .iter()
+            .any(|transformed| transformed.is_subset_of(other))
+    }
+
+    /// A single number shared by every set in the set class.
+    pub(crate) fn set_class(&self) -> u16 {
+        self.transformations()
+            .iter()
+            .map(|set| set.classes)
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn transformations(&self) -> Vec<Self> {
+        (0..12)
+            .flat_map(|n| vec![self.transpose(n), self.invert(n)])
+            .collect()
+    }
+}
+
+impl fmt::Display for PitchClassSet {
+    /// Write the pitch classes in ascending order with T and E for 10 and 11, as in `{0,4,7,T}`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let classes: Vec<String> = self
+            .pitch_classes()
+            .iter()
+            .map(|&class| class_name(class))
+            .collect();
+        write!(f, "{{{}}}", classes.join(","))
+    }
+}
+
+/// A pitch class as a single character, with T and E for 10 and 11.
+pub(crate) fn class_name(class: u8) -> String {
+    match class {
+        10 => "T".to_string(),
+        11 => "E".to_string(),
+        class => class.to_string(),
+    }
+}
+
+/// The intervals of an ordering above its first pitch class.
+fn relative(ordering: &[u8]) -> Vec<u8> {
+    ordering
+        .iter()
+        .map(|&class| (class + 12 - ordering[0]) % 12)
+        .collect()
+}
+
+/// The order in which two orderings' intervals above their first pitch class are compared.
+fn packing_key(intervals: &[u8], packing: Packing) -> Vec<u8> {
+    let last = intervals.len().saturating_sub(1);
+    let mut key = vec![intervals.get(last).copied().unwrap_or(0)];
+    match packing {
+        Packing::Forte => key.extend(intervals.iter().take(last).skip(1)),
+        Packing::Rahn => key.extend(intervals.iter().take(last).skip(1).rev()),
+    }
+    key
+}
+
+fn normal_form(classes: &[u8], packing: Packing) -> Vec<u8> {
+    (0..classes.len())
+        .map(|start| {
+            let mut rotation = classes[start..].to_vec();
+            rotation.extend_from_slice(&classes[..start]);
+            rotation
+        })
+        .min_by_key(|rotation| (packing_key(&relative(rotation), packing), rotation[0]))
+        .unwrap_or_default()
+}
+
+fn prime_form(classes: &[u8], packing: Packing) -> Vec<u8> {
+    let mut inverted: Vec<u8> = classes.iter().map(|&class| (12 - class) % 12).collect();
+    inverted.sort_unstable();
+    let original = relative(&normal_form(classes, packing));
+    let inversion = relative(&normal_form(&inverted, packing));
+    if packing_key(&inversion, packing) < packing_key(&original, packing) {
+        inversion
+    } else {
+        original
+    }
+}
@@ -0,0 +1,367 @@
+use crate::note::{Note, Notes, Pitch, Spelling};
+use crate::pitch_class_set::pitch_class_set::class_name;
+use crate::pitch_class_set::{PitchClassSet, PitchClassSetError};
+use std::fmt;
+use std::str::FromStr;
+
+/// The four ways a twelve-tone row can be read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RowForm {
+    /// The row as written.
+    Prime,
+    /// The row backwards.
+    Retrograde,
+    /// The row with every interval turned upside down.
+    Inversion,
+    /// The inversion backwards.
+    RetrogradeInversion,
+}
+
+/// A form of a row at a transposition, such as P0, I5, or RI7.
+///
+/// Labels are numbered by pitch class: Pn and In start on pitch class n, and Rn and RIn are Pn and
+/// In read backwards, so they end on n.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RowLabel {
+    /// How the row is read.
+    pub form: RowForm,
+    /// The pitch class the prime or inversion starts on.
+    pub transposition: u8,
+}
+
+/// An ordering of all twelve pitch classes, each used once.
+///
+/// Rows are spelled with sharps in the fourth octave unless given another [`Spelling`] and
+/// octave, so they can be added to a `MidiBuilder` like any other [`Notes`].
+///
+/// ```
+/// use rust_music_theory::pitch_class_set::{RowForm, RowLabel, ToneRow};
+///
+/// let row = ToneRow::new(&[0, 11, 7, 8, 3, 1, 2, 10, 6, 5, 4, 9]).unwrap();
+/// let label = RowLabel { form: RowForm::Inversion, transposition: 0 };
+/// assert_eq!(row.form(label).pitch_classes()[..4], [0, 1, 5, 4]);
+/// assert_eq!(row.matrix()[1][0], 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToneRow {
+    classes: [u8; 12],
+    spelling: Spelling,
+    octave: i16,
+}
+
+impl ToneRow {
+    /// A row from twelve pitch classes.
+    ///
+    /// # Errors
+    ///
+    /// Fails unless the numbers are each pitch class from 0 to 11 exactly once.
+    pub fn new(classes: &[u8]) -> Result<Self, PitchClassSetError> {
+        if classes.len() != 12 {
+            return Err(PitchClassSetError::RowLength(classes.len()));
+        }
+        let mut row = [0; 12];
+        for (index, &class) in classes.iter().enumerate() {
+            if class > 11 {
+                return Err(PitchClassSetError::InvalidPitchClass(class));
+            }
+            if row[..index].contains(&class) {
+                return Err(PitchClassSetError::RepeatedPitchClass(class));
+            }
+            row[index] = class;
+        }
+        Ok(ToneRow {
+            classes: row,
+            spelling: Spelling::Sharps,
+            octave: 4,
+        })
+    }
+
+    /// A row from twelve pitches, as given by [`Pitch::into_u8`].
+    pub fn from_pitches(pitches: &[Pitch]) -> Result<Self, PitchClassSetError> {
+        let classes: Vec<u8> = pitches.iter().map(|pitch| pitch.into_u8()).collect();
+        Self::new(&classes)
+    }
+
+    /// A row from the pitch classes of twelve notes, such as a melody.
+    pub fn from_notes<N: Notes + ?Sized>(notes: &N) -> Result<Self, PitchClassSetError> {
+        let pitches: Vec<Pitch> = notes.notes().iter().map(|note| note.pitch).collect();
+        Self::from_pitches(&pitches)
+    }
+
+    /// Spell the row's notes with a policy.
+    pub fn spelling(mut self, spelling: Spelling) -> Self {
+        self.spelling = spelling;
+        self
+    }
+
+    /// Place the row's notes in an octave, from its C upwards.
+    pub fn octave(mut self, octave: i16) -> Self {
+        self.octave = octave;
+        self
+    }
+
+    /// The pitch classes in order.
+    pub fn pitch_classes(&self) -> [u8; 12] {
+        self.classes
+    }
+
+    /// The row's pitches, spelled with its policy.
+    pub fn pitches(&self) -> Vec<Pitch> {
+        self.classes
+            .iter()
+            .map(|&class| self.spelling.spell(class))
+            .collect()
+    }
+
+    /// The row read in a form at a transposition, keeping its spelling and octave.
+    pub fn form(&self, label: RowLabel) -> ToneRow {
+        let transposition = label.transposition % 12;
+        let first = self.classes[0];
+        let mut classes = self.classes.map(|class| match label.form {
+            RowForm::Prime | RowForm::Retrograde => (class + 12 - first + transposition) % 12,
+            RowForm::Inversion | RowForm::RetrogradeInversion => {
+                (first + 12 - class + transposition) % 12
+            }
+        });
+        if matches!(
+            label.form,
+            RowForm::Retrograde | RowForm::RetrogradeInversion
+        ) {
+            classes.reverse();
+        }
+        ToneRow {
+            classes,
+            ..self.clone()
+        }
+    }
+
+    /// The row starting on a pitch class.
+    pub fn prime(&self, transposition: u8) -> ToneRow {
+        self.form(RowLabel::new(RowForm::Prime, transposition))
+    }
+
+    /// The prime starting on a pitch class, read backwards.
+    pub fn retrograde(&self, transposition: u8) -> ToneRow {
+        self.form(RowLabel::new(RowForm::Retrograde, transposition))
+    }
+
+    /// The inversion starting on a pitch class.
+    pub fn inversion(&self, transposition: u8) -> ToneRow {
+        self.form(RowLabel::new(RowForm::Inversion, transposition))
+    }
+
+    /// The inversion starting on a pitch class, read backwards.
+    pub fn retrograde_inversion(&self, transposition: u8) -> ToneRow {
+        self.form(RowLabel::new(RowForm::RetrogradeInversion, transposition))
+    }
+
+    /// All 48 forms of the row with their labels, by form and then transposition.
+    pub fn forms(&self) -> Vec<(RowLabel, ToneRow)> {
+        RowLabel::all()
+            .map(|label| (label, self.form(label)))
+            .collect()
+    }
+
+    /// The twelve-tone matrix.
+    ///
+    /// The first row is the row as written and the first column its inversion from the same
+    /// pitch class, so the rows read left to right are primes, right to left retrogrades, top to
+    /// bottom inversions, and bottom to top retrograde inversions.
+    pub fn matrix(&self) -> [[u8; 12]; 12] {
+        let inversion = self.inversion(self.classes[0]);
+        let mut matrix = [[0; 12]; 12];
+        for (row, &start) in matrix.iter_mut().zip(inversion.classes.iter()) {
+            *row = self.prime(start).classes;
+        }
+        matrix
+    }
+
+    /// Every label under which this row reads another row's pitch classes.
+    ///
+    /// Symmetrical rows can match more than one form, and rows that are not forms of this one
+    /// match none.
+    pub fn labels_of(&self, other: &ToneRow) -> Vec<RowLabel> {
+        self.forms()
+            .into_iter()
+            .filter(|(_, form)| form.classes == other.classes)
+            .map(|(label, _)| label)
+            .collect()
+    }
+
+    /// Every form whose first hexachord holds the pitch classes this row's first hexachord leaves
+    /// out, so the two forms together complete the aggregate.
+    ///
+    /// The retrograde ending on the row's first pitch class always does so, so every row is
+    /// retrograde-combinatorial.
+    pub fn combinatorial_forms(&self) -> Vec<RowLabel> {
+        let complement = hexachord(&self.classes).complement();
+        self.forms()
+            .into_iter()
+            .filter(|(_, form)| hexachord(&form.classes) == complement)
+            .map(|(label, _)| label)
+            .collect()
+    }
+
+    /// Whether some form of the kind completes the aggregate with the row's first hexachord.
+    pub fn is_combinatorial(&self, form: RowForm) -> bool {
+        self.combinatorial_forms()
+            .iter()
+            .any(|label| label.form == form)
+    }
+
+    /// Whether the row is prime-, retrograde-, inversion-, and retrograde-inversion-combinatorial.
+    pub fn is_all_combinatorial(&self) -> bool {
+        [
+            RowForm::Prime,
+            RowForm::Retrograde,
+            RowForm::Inversion,
+            RowForm::RetrogradeInversion,
+        ]
+        .iter()
+        .all(|&form| self.is_combinatorial(form))
+    }
+
+    /// Whether the row is derived from its first segment of a length: every later segment is a
+    /// transposition, inversion, retrograde, or retrograde inversion of the first, in order.
+    ///
+    /// Only lengths 2, 3, 4, and 6 divide the row into equal segments.
+    pub fn is_derived(&self, length: usize) -> bool {
+        if !matches!(length, 2 | 3 | 4 | 6) {
+            return false;
+        }
+        let mut segments = self.classes.chunks(length);
+        let first: Vec<u8> = segments.next().unwrap_or_default().to_vec();
+        let shape = intervals(&first);
+        segments.all(|segment| {
+            let mut backwards = segment.to_vec();
+            backwards.reverse();
+            [segment.to_vec(), backwards].iter().any(|candidate| {
+                let candidate = intervals(candidate);
+                candidate == shape || candidate.iter().zip(&shape).all(|(a, b)| (a + b) % 12 == 0)
+            })
+        })
+    }
+
+    /// The lengths of first segment from which the row is derived, shortest first.
+    pub fn derivations(&self) -> Vec<usize> {
+        [2, 3, 4, 6]
+            .iter()
+            .copied()
+            .filter(|&length| self.is_derived(length))
+            .collect()
+    }
+}
+
+impl Notes for ToneRow {
+    /// The row's notes in its octave, spelled with its policy.
+    fn notes(&self) -> Vec<Note> {
+        self.classes
+            .iter()
+            .map(|&class| {
+                let midi = (self.octave + 1) * 12 + class as i16;
+                Note::from_midi_with_pitch(self.spelling.spell(class), midi)
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for ToneRow {
+    /// Write the pitch classes with T and E for 10 and 11, as in `0 E 7 8 3 1 2 T 6 5 4 9`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let classes: Vec<String> = self
+            .classes
+            .iter()
+            .map(|&class| class_name(class))
+            .collect();
+        write!(f, "{}", classes.join(" "))
+    }
+}
+
+impl RowLabel {
+    /// A label from a form and the pitch class its prime or inversion starts on.
+    pub fn new(form: RowForm, transposition: u8) -> Self {
+        RowLabel {
+            form,
+            transposition: transposition % 12,
+        }
+    }
+
+    /// Parse a label such as `P0`, `I5`, `R11`, or `RI7`.
+    ///
+    /// # Errors
+    ///
+    /// Fails unless the text is a form letter followed by a transposition from 0 to 11.
+    pub fn parse(text: &str) -> Result<Self, PitchClassSetError> {
+        let invalid = || PitchClassSetError::InvalidRowLabel(text.to_string());
+        let text = text.trim();
+        let split = text
+            .find(|c: char| c.is_ascii_digit())
+            .ok_or_else(invalid)?;
+        let form = match text[..split].to_ascii_uppercase().as_str() {
+            "P" => RowForm::Prime,
+            "R" => RowForm::Retrograde,
+            "I" => RowForm::Inversion,
+            "RI" => RowForm::RetrogradeInversion,
+            _ => return Err(invalid()),
+        };
+        let transposition = text[split..].parse::<u8>().map_err(|_| invalid())?;
+        if transposition > 11 {
+            return Err(invalid());
+        }
+        Ok(RowLabel::new(form, transposition))
+    }
+
+    fn all() -> impl Iterator<Item = RowLabel> {
+        [
+            RowForm::Prime,
+            RowForm::Retrograde,
+            RowForm::Inversion,
+            RowForm::RetrogradeInversion,
+        ]
+        .iter()
+        .flat_map(|&form| (0..12).map(move |transposition| RowLabel::new(form, transposition)))
+    }
+}
+
+impl fmt::Display for RowForm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let letters = match self {
+            RowForm::Prime => "P",
+            RowForm::Retrograde => "R",
+            RowForm::Inversion => "I",
+            RowForm::RetrogradeInversion => "RI",
+        };
+        write!(f, "{}", letters)
+    }
+}
+
+impl fmt::Display for RowLabel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.form, self.transposition)
+    }
+}
+
+impl FromStr for RowLabel {
+    type Err = PitchClassSetError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse(text)
+    }
+}
+
+/// The set of a row's first six pitch classes.
+fn hexachord(classes: &[u8; 12]) -> PitchClassSet {
+    PitchClassSet::from_mask(
+        classes[..6]
+            .iter()
+            .fold(0, |mask, &class| mask | 1 << class),
+    )
+}
+
+/// The ordered intervals between successive pitch classes, in semitones upwards.
+fn intervals(classes: &[u8]) -> Vec<u8> {
+    classes
+        .windows(2)
+        .map(|pair| (pair[1] + 12 - pair[0]) % 12)
+        .collect()
+}
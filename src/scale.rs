@@ -22,12 +22,15 @@
 //! assert_eq!(pitches, ["C", "Db", "Eb", "Fb", "Gb", "Ab", "Bb", "C"]);
 //! ```
 
+mod chords;
 mod errors;
+mod identify;
 mod mode;
 mod scale;
 mod scale_type;
 
 pub use errors::ScaleError;
+pub use identify::{ScaleCandidate, ScaleMatch};
 pub use mode::Mode;
 pub use scale::{Direction, Scale};
 pub use scale_type::ScaleType;
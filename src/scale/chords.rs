@@ -0,0 +1,136 @@
+use crate::chord::{Chord, ChordError, ChordExtension, ChordTone, SeventhQuality, TriadQuality};
+use crate::note::{Note, Notes};
+use crate::scale::{Direction, Scale, ScaleError, ScaleType};
+
+impl Scale {
+    /// The chords stacked in thirds on every degree of a seven-note scale, from the tonic up.
+    ///
+    /// `extension` sets how far each chord is stacked, from a triad to a thirteenth. Tones that
+    /// differ from a chord's usual form become alterations, so the dominant thirteenth of A
+    /// harmonic minor has a flat ninth and a flat thirteenth. Diminished seventh chords have no
+    /// extended forms, so their upper tones are added, as in `G#dim7addb9`.
+    pub fn diatonic_chords(&self, extension: ChordExtension) -> Result<Vec<Chord>, ScaleError> {
+        (1..=7)
+            .map(|degree| self.chord_on_degree(degree, extension))
+            .collect()
+    }
+
+    /// The chord stacked in thirds on a degree of a seven-note scale, numbered from 1.
+    ///
+    /// # Errors
+    ///
+    /// Fails for scales without seven degrees, degrees outside 1 to 7 and sixth chords. Stacks
+    /// no chord symbol can describe fail with [`ScaleError::InvalidChord`].
+    pub fn chord_on_degree(
+        &self,
+        degree: u8,
+        extension: ChordExtension,
+    ) -> Result<Chord, ScaleError> {
+        if !matches!(
+            self.scale_type,
+            ScaleType::Diatonic | ScaleType::HarmonicMinor | ScaleType::MelodicMinor
+        ) {
+            return Err(ScaleError::NotHeptatonic(self.scale_type));
+        }
+        if !(1..=7).contains(&degree) {
+            return Err(ScaleError::InvalidDegree(degree));
+        }
+        let size = match extension {
+            ChordExtension::Triad => 3,
+            ChordExtension::Seventh => 4,
+            ChordExtension::Ninth => 5,
+            ChordExtension::Eleventh => 6,
+            ChordExtension::Thirteenth => 7,
+            ChordExtension::Sixth | ChordExtension::SixNine => {
+                return Err(ScaleError::UnsupportedExtension(extension))
+            }
+        };
+
+        // Melodic minor descends differently, so always stack the ascending form.
+        let ascending = Scale {
+            direction: Direction::Ascending,
+            ..self.clone()
+        };
+        let notes: Vec<Note> = ascending.notes().into_iter().take(7).collect();
+        let root = &notes[degree as usize - 1];
+        let mut tones = (0..size)
+            .map(|step| {
+                let pitch = notes[(degree as usize - 1 + 2 * step) % 7].pitch;
+                let natural = ChordTone::new(2 * step as u8 + 1, 0)?.semitones();
+                let semitones = pitch.into_u8() as i16 - root.pitch.into_u8() as i16;
+                let alteration = (semitones - natural + 6).rem_euclid(12) - 6;
+                ChordTone::new(2 * step as u8 + 1, alteration as i8)
+            })
+            .collect::<Result<Vec<ChordTone>, ChordError>>()?;
+
+        let unsupported = || {
+            let pitches: Vec<String> = (0..size)
+                .map(|step| {
+                    notes[(degree as usize - 1 + 2 * step) % 7]
+                        .pitch
+                        .to_string()
+                })
+                .collect();
+            ScaleError::InvalidChord(ChordError::UnsupportedChord(pitches.join(" ")))
+        };
+        // Extended chords are specified on major and minor triads and on the diminished seventh,
+        // so other diminished and augmented fifths become alterations.
+        let extended = size > 4;
+        let diminished_seventh = tones.get(3).map(|tone| tone.alteration()) == Some(-2);
+        let triad = match (tones[1].alteration(), tones[2].alteration()) {
+            (0, 1) if !extended => TriadQuality::Augmented,
+            (0, _) => TriadQuality::Major,
+            (-1, -1) if !extended || diminished_seventh => TriadQuality::Diminished,
+            (-1, _) => TriadQuality::Minor,
+            _ => return Err(unsupported()),
+        };
+        // Diminished seventh chords have no extended forms, so their upper tones are added.
+        let added = if triad == TriadQuality::Diminished && extended {
+            tones.split_off(4)
+        } else {
+            Vec::new()
+        };
+        let mut builder = Chord::builder(root.pitch)
+            .octave(root.octave)
+            .triad_quality(triad)
+            .extension(if added.is_empty() {
+                extension
+            } else {
+                ChordExtension::Seventh
+            });
+        if let Some(seventh) = tones.get(3) {
+            builder = builder.seventh_quality(match seventh.alteration() {
+                0 => SeventhQuality::Major,
+                -1 => SeventhQuality::Minor,
+                -2 => SeventhQuality::Diminished,
+                _ => return Err(unsupported()),
+            });
+        }
+
+        // Alter the fifth and extensions wherever the scale departs from the chord's usual form.
+        let usual = builder.clone().build()?;
+        for tone in &tones {
+            let differs = usual
+                .formula()
+                .tones()
+                .iter()
+                .any(|usual| usual.degree() == tone.degree() && usual != tone);
+            if differs {
+                builder = builder.alter(tone.degree(), tone.alteration())?;
+            }
+        }
+        for tone in &added {
+            builder = builder.add(tone.degree(), tone.alteration())?;
+        }
+        let mut expected = tones;
+        expected.extend(added);
+        let chord = builder.build()?;
+        let mut built = chord.formula().tones().to_vec();
+        built.sort_by_key(|tone| tone.degree());
+        expected.sort_by_key(|tone| tone.degree());
+        if built != expected {
+            return Err(unsupported());
+        }
+        Ok(chord)
+    }
+}
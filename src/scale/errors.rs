@@ -1,3 +1,4 @@
+use crate::chord::{ChordError, ChordExtension};
 use crate::interval::IntervalError;
 use crate::note::NoteError;
 use crate::scale::{Mode, ScaleType};
@@ -10,6 +11,10 @@ pub enum ScaleError {
     ModeFromRegex,
     InvalidRegex,
     IncompatibleMode { scale_type: ScaleType, mode: Mode },
+    NotHeptatonic(ScaleType),
+    InvalidDegree(u8),
+    UnsupportedExtension(ChordExtension),
+    InvalidChord(ChordError),
 }
 
 impl fmt::Display for ScaleError {
@@ -27,6 +32,14 @@ impl fmt::Display for ScaleError {
                 mode.scale_type(),
                 scale_type
             ),
+            ScaleError::NotHeptatonic(scale_type) => {
+                write!(f, "Scale type {} does not have seven degrees", scale_type)
+            }
+            ScaleError::InvalidDegree(degree) => write!(f, "Invalid scale degree: {}", degree),
+            ScaleError::UnsupportedExtension(extension) => {
+                write!(f, "Cannot stack a {:?} chord in thirds", extension)
+            }
+            ScaleError::InvalidChord(ref error) => write!(f, "Invalid chord: {}", error),
         }
     }
 }
@@ -60,3 +73,9 @@ impl From<IntervalError> for ScaleError {
         }
     }
 }
+
+impl From<ChordError> for ScaleError {
+    fn from(e: ChordError) -> Self {
+        ScaleError::InvalidChord(e)
+    }
+}
@@ -0,0 +1,106 @@
+use crate::note::{Notes, Pitch};
+use crate::scale::{Direction, Mode, Scale};
+use std::collections::HashSet;
+
+/// How a scale must relate to the pitches being identified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScaleMatch {
+    /// The scale contains every pitch class and may contain others.
+    Subset,
+    /// The scale contains exactly the given pitch classes.
+    Exact,
+}
+
+/// A scale that contains a set of pitches.
+#[derive(Debug, Clone)]
+pub struct ScaleCandidate {
+    /// The matching scale, spelled to agree with the given pitches where possible.
+    pub scale: Scale,
+    /// The number of pitch classes in the scale that were not given.
+    pub extra_notes: usize,
+}
+
+impl Scale {
+    /// Find every tonic and mode whose scale contains the pitch classes of `pitches`.
+    ///
+    /// Candidates are ranked by how few extra notes they add, then by whether their tonic is one
+    /// of the given pitches, then by mode order. Symmetric scales such as the chromatic and
+    /// whole-tone scales are listed once per distinct set of notes, from their best-ranked
+    /// tonic. An empty set of pitches matches nothing.
+    pub fn containing(pitches: &[Pitch], matching: ScaleMatch) -> Vec<ScaleCandidate> {
+        let wanted = pitch_class_set(pitches.iter().copied());
+        if wanted == 0 {
+            return Vec::new();
+        }
+
+        let mut candidates = Vec::new();
+        for &mode in Mode::all() {
+            for tonic in 0..12 {
+                let scale = match best_spelled_scale(tonic, mode, pitches) {
+                    Some(scale) => scale,
+                    None => continue,
+                };
+                let available = pitch_class_set(scale.notes().iter().map(|note| note.pitch));
+                if wanted & !available != 0 {
+                    continue;
+                }
+                let extra_notes = (available & !wanted).count_ones() as usize;
+                if matching == ScaleMatch::Exact && extra_notes > 0 {
+                    continue;
+                }
+                candidates.push((available, ScaleCandidate { scale, extra_notes }));
+            }
+        }
+
+        candidates.sort_by_key(|(_, candidate)| {
+            let tonic_given = wanted & (1 << candidate.scale.tonic.into_u8()) != 0;
+            (candidate.extra_notes, !tonic_given)
+        });
+        let mut seen = HashSet::new();
+        candidates
+            .into_iter()
+            .filter(|(available, candidate)| seen.insert((candidate.scale.mode, *available)))
+            .map(|(_, candidate)| candidate)
+            .collect()
+    }
+}
+
+/// Spell the tonic with sharps or flats, preferring the scale that reuses the given spellings and
+/// then the one with fewer accidentals.
+fn best_spelled_scale(tonic: u8, mode: Mode, pitches: &[Pitch]) -> Option<Scale> {
+    let sharp = Pitch::from_u8(tonic);
+    let flat = Pitch::from_u8_with_direction(tonic, Direction::Descending);
+    let spellings = if sharp == flat {
+        vec![sharp]
+    } else {
+        vec![sharp, flat]
+    };
+    spellings
+        .into_iter()
+        .filter_map(|tonic| {
+            Scale::new(
+                mode.scale_type(),
+                tonic,
+                4,
+                Some(mode),
+                Direction::Ascending,
+            )
+            .ok()
+        })
+        .min_by_key(|scale| {
+            let notes = scale.notes();
+            let reused = pitches
+                .iter()
+                .filter(|pitch| notes.iter().any(|note| note.pitch == **pitch))
+                .count();
+            let accidentals: i32 = notes
+                .iter()
+                .map(|note| (note.pitch.accidental as i32).abs())
+                .sum();
+            (std::cmp::Reverse(reused), accidentals)
+        })
+}
+
+fn pitch_class_set(pitches: impl Iterator<Item = Pitch>) -> u16 {
+    pitches.fold(0, |set, pitch| set | (1 << pitch.into_u8()))
+}
@@ -154,6 +154,11 @@ fn normalize_mode_name(input: &str) -> String {
 }
 
 impl Mode {
+    /// Every supported mode, grouped by parent scale family.
+    pub fn all() -> &'static [Self] {
+        ALL_MODES
+    }
+
     /// Every supported seven-note mode, grouped by parent scale family.
     pub fn heptatonic_modes() -> &'static [Self] {
         HEPTATONIC_MODES
@@ -1,5 +1,5 @@
-use crate::interval::Interval;
-use crate::note::{Note, NoteLetter, Notes, Pitch};
+use crate::interval::{Interval, Number, Quality};
+use crate::note::{Note, NoteLetter, Notes, Pitch, Spelling};
 use crate::scale::errors::ScaleError;
 use crate::scale::{Mode, ScaleType};
 use strum_macros::Display;
@@ -87,6 +87,20 @@ impl Scale {
             ScaleType::WholeTone => Interval::from_semitones(&[2, 2, 2, 2, 2, 2]),
         }?;
 
+        if matches!(
+            scale_type,
+            ScaleType::Diatonic | ScaleType::HarmonicMinor | ScaleType::MelodicMinor
+        ) {
+            // Every step of a seven-note scale is a second, so the three-semitone step of the
+            // harmonic minor is an augmented second rather than a minor third.
+            for interval in intervals.iter_mut() {
+                if interval.number == Number::Third {
+                    *interval =
+                        Interval::from_number_and_quality(Number::Second, Quality::Augmented)?;
+                }
+            }
+        }
+
         if let Some(mode) = mode {
             if mode.scale_type() == scale_type {
                 intervals.rotate_left(mode.rotation());
@@ -118,6 +132,31 @@ impl Scale {
         Self::from_regex_in_direction(string, Direction::Ascending)
     }
 
+    /// Move the scale by a spelled interval, keeping its mode and direction.
+    pub fn transpose(&self, interval: Interval, direction: Direction) -> Self {
+        let tonic = Note::new(self.tonic, self.octave).transpose(interval, direction);
+        self.with_tonic(tonic)
+    }
+
+    /// Move the scale by a signed number of semitones, spelling the new tonic with `spelling`.
+    pub fn transpose_semitones(&self, semitones: i16, spelling: &Spelling) -> Self {
+        let tonic = Note::new(self.tonic, self.octave).transpose_semitones(semitones, spelling);
+        self.with_tonic(tonic)
+    }
+
+    /// Move the scale to a new written tonic in the same octave.
+    pub fn transpose_to(&self, tonic: Pitch) -> Self {
+        self.with_tonic(Note::new(tonic, self.octave))
+    }
+
+    fn with_tonic(&self, tonic: Note) -> Self {
+        Scale {
+            tonic: tonic.pitch,
+            octave: tonic.octave,
+            ..self.clone()
+        }
+    }
+
     pub fn absolute_intervals(&self) -> Vec<Interval> {
         let mut qualities = Vec::new();
         let mut sum = 0;
@@ -0,0 +1,22 @@
+//! Tuning systems that give notes their frequencies.
+//!
+//! ```
+//! use rust_music_theory::note::Note;
+//! use rust_music_theory::tuning::TuningSystem;
+//!
+//! let a4: Note = "A4".parse().unwrap();
+//! let e5: Note = "E5".parse().unwrap();
+//! assert_eq!(a4.frequency(&TuningSystem::equal_temperament()), 440.0);
+//! let baroque = TuningSystem::equal_temperament().reference_frequency(415.0);
+//! assert_eq!(a4.frequency(&baroque), 415.0);
+//! let pure_fifth = e5.frequency(&TuningSystem::pythagorean());
+//! assert!((pure_fifth - 660.0).abs() < 1e-9);
+//! ```
+
+mod errors;
+mod scala;
+mod tuning_system;
+
+pub use errors::TuningError;
+pub use scala::{KeyboardMapping, ScalaScale};
+pub use tuning_system::{Temperament, TuningSystem};
@@ -0,0 +1,34 @@
+use std::error;
+use std::fmt;
+
+/// An error while building a tuning system or reading a Scala file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TuningError {
+    InvalidDivisions(u16),
+    InvalidScala { line: usize, message: String },
+    InvalidKeyboardMapping { line: usize, message: String },
+    Io(String),
+}
+
+impl fmt::Display for TuningError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TuningError::InvalidDivisions(divisions) => {
+                write!(f, "Cannot divide the octave into {} steps", divisions)
+            }
+            TuningError::InvalidScala { line, message } => {
+                write!(f, "Invalid Scala scale at line {}: {}", line, message)
+            }
+            TuningError::InvalidKeyboardMapping { line, message } => {
+                write!(
+                    f,
+                    "Invalid Scala keyboard mapping at line {}: {}",
+                    line, message
+                )
+            }
+            TuningError::Io(message) => write!(f, "Cannot read tuning file: {}", message),
+        }
+    }
+}
+
+impl error::Error for TuningError {}
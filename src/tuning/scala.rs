@@ -0,0 +1,298 @@
+use crate::tuning::TuningError;
+use std::path::Path;
+
+/// A scale read from a Scala `.scl` file: the sizes of its degrees above the first, in cents.
+///
+/// The last degree is the interval at which the scale repeats, usually an octave.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScalaScale {
+    description: String,
+    cents: Vec<f64>,
+}
+
+/// How a Scala `.kbm` file maps MIDI keys to scale degrees.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyboardMapping {
+    first_key: u8,
+    last_key: u8,
+    middle_key: u8,
+    reference_key: u8,
+    reference_frequency: f64,
+    octave_degree: usize,
+    map: Vec<Option<usize>>,
+}
+
+impl ScalaScale {
+    /// A scale from degree sizes in cents, the last being the period.
+    ///
+    /// # Errors
+    ///
+    /// Fails without degrees.
+    pub fn new(description: &str, cents: Vec<f64>) -> Result<Self, TuningError> {
+        if cents.is_empty() {
+            return Err(TuningError::InvalidScala {
+                line: 0,
+                message: "the scale has no degrees".to_string(),
+            });
+        }
+        Ok(ScalaScale {
+            description: description.to_string(),
+            cents,
+        })
+    }
+
+    /// Parse the contents of a `.scl` file.
+    ///
+    /// Lines starting with `!` are comments. The first other line describes the scale and the
+    /// second gives the number of degrees, which follow one per line as cents when they contain
+    /// a period, such as `701.955`, or as ratios otherwise, such as `3/2` or `2`.
+    ///
+    /// # Errors
+    ///
+    /// Fails on a missing or malformed count or degree, with the line number counted from 1.
+    pub fn parse(text: &str) -> Result<Self, TuningError> {
+        let invalid = |line: usize, message: &str| TuningError::InvalidScala {
+            line,
+            message: message.to_string(),
+        };
+        let mut lines = data_lines(text);
+        let (_, description) = lines
+            .next()
+            .ok_or_else(|| invalid(0, "missing description"))?;
+        let (line, count) = lines
+            .next()
+            .ok_or_else(|| invalid(0, "missing note count"))?;
+        let count: usize = first_token(count)
+            .parse()
+            .map_err(|_| invalid(line, "expected the number of notes"))?;
+        let mut cents = Vec::new();
+        for _ in 0..count {
+            let (line, pitch) = lines
+                .next()
+                .ok_or_else(|| invalid(0, "fewer notes than the count"))?;
+            cents.push(
+                parse_pitch(first_token(pitch))
+                    .ok_or_else(|| invalid(line, "expected cents or a ratio"))?,
+            );
+        }
+        Self::new(description.trim(), cents)
+    }
+
+    /// Read a `.scl` file.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the file cannot be read or parsed.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, TuningError> {
+        let text =
+            std::fs::read_to_string(path).map_err(|error| TuningError::Io(error.to_string()))?;
+        Self::parse(&text)
+    }
+
+    /// The description from the file.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// The sizes of the degrees above the first in cents, ending with the period.
+    pub fn cents(&self) -> &[f64] {
+        &self.cents
+    }
+
+    /// The number of degrees in each period.
+    pub fn len(&self) -> usize {
+        self.cents.len()
+    }
+
+    /// Whether the scale has no degrees, which a parsed scale never is.
+    pub fn is_empty(&self) -> bool {
+        self.cents.is_empty()
+    }
+
+    /// The interval at which the scale repeats, in cents.
+    pub fn period(&self) -> f64 {
+        self.cents.last().copied().unwrap_or(1200.0)
+    }
+
+    /// The size of a degree above the first, in any period.
+    pub(crate) fn degree_cents(&self, degree: i32) -> f64 {
+        let size = self.cents.len() as i32;
+        let step = degree.rem_euclid(size);
+        let period = degree.div_euclid(size) as f64 * self.period();
+        if step == 0 {
+            period
+        } else {
+            period + self.cents[step as usize - 1]
+        }
+    }
+}
+
+impl KeyboardMapping {
+    /// Map every key to the next scale degree, with degree 0 on a middle key and a reference key
+    /// sounding at a frequency.
+    pub fn linear(middle_key: u8, reference_key: u8, reference_frequency: f64) -> Self {
+        KeyboardMapping {
+            first_key: 0,
+            last_key: 127,
+            middle_key,
+            reference_key,
+            reference_frequency,
+            octave_degree: 0,
+            map: Vec::new(),
+        }
+    }
+
+    /// Parse the contents of a `.kbm` file.
+    ///
+    /// After `!` comments, the file gives the size of the mapping pattern, the first and last
+    /// keys to retune, the middle key where degree 0 sits, the reference key and its frequency,
+    /// the degree that acts as the formal octave, and then one degree per key of the pattern,
+    /// with `x` for keys left silent.
+    ///
+    /// # Errors
+    ///
+    /// Fails on a missing or malformed value, with the line number counted from 1.
+    pub fn parse(text: &str) -> Result<Self, TuningError> {
+        let invalid = |line: usize, message: &str| TuningError::InvalidKeyboardMapping {
+            line,
+            message: message.to_string(),
+        };
+        let mut lines = data_lines(text);
+        let mut number = |name: &str| -> Result<(usize, f64), TuningError> {
+            let (line, value) = lines
+                .next()
+                .ok_or_else(|| invalid(0, &format!("missing {}", name)))?;
+            first_token(value)
+                .parse::<f64>()
+                .map(|value| (line, value))
+                .map_err(|_| invalid(line, &format!("expected the {}", name)))
+        };
+        let key = |(line, value): (usize, f64), name: &str| {
+            if value.fract() == 0.0 && (0.0..=127.0).contains(&value) {
+                Ok(value as u8)
+            } else {
+                Err(invalid(
+                    line,
+                    &format!("expected the {} from 0 to 127", name),
+                ))
+            }
+        };
+        let (line, size) = number("map size")?;
+        if !(0.0..=128.0).contains(&size) || size.fract() != 0.0 {
+            return Err(invalid(line, "expected the map size from 0 to 128"));
+        }
+        let first_key = key(number("first key")?, "first key")?;
+        let last_key = key(number("last key")?, "last key")?;
+        let middle_key = key(number("middle key")?, "middle key")?;
+        let reference_key = key(number("reference key")?, "reference key")?;
+        let (line, reference_frequency) = number("reference frequency")?;
+        if !(reference_frequency.is_finite() && reference_frequency > 0.0) {
+            return Err(invalid(line, "expected a positive reference frequency"));
+        }
+        let (line, octave_degree) = number("octave degree")?;
+        if !(0.0..=u16::MAX as f64).contains(&octave_degree) || octave_degree.fract() != 0.0 {
+            return Err(invalid(line, "expected the octave degree"));
+        }
+
+        let mut map = Vec::with_capacity(size as usize);
+        for _ in 0..size as usize {
+            match lines.next() {
+                None => map.push(None),
+                Some((_, entry)) if first_token(entry).eq_ignore_ascii_case("x") => map.push(None),
+                Some((line, entry)) => map.push(Some(
+                    first_token(entry)
+                        .parse::<u16>()
+                        .map_err(|_| invalid(line, "expected a degree or x"))?
+                        as usize,
+                )),
+            }
+        }
+        Ok(KeyboardMapping {
+            first_key,
+            last_key,
+            middle_key,
+            reference_key,
+            reference_frequency,
+            octave_degree: octave_degree as usize,
+            map,
+        })
+    }
+
+    /// Read a `.kbm` file.
+    ///
+    /// # Errors
+    ///
+    /// Fails when the file cannot be read or parsed.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, TuningError> {
+        let text =
+            std::fs::read_to_string(path).map_err(|error| TuningError::Io(error.to_string()))?;
+        Self::parse(&text)
+    }
+
+    /// The key where scale degree 0 sits.
+    pub fn middle_key(&self) -> u8 {
+        self.middle_key
+    }
+
+    /// The key tuned to the reference frequency.
+    pub fn reference_key(&self) -> u8 {
+        self.reference_key
+    }
+
+    /// The frequency of the reference key in Hz.
+    pub fn reference_frequency(&self) -> f64 {
+        self.reference_frequency
+    }
+
+    /// The scale degree a key plays, or `None` for a silent key.
+    ///
+    /// An empty pattern maps every key to the next degree, with a scale's period as the octave.
+    pub(crate) fn degree(&self, key: i16, scale_size: usize) -> Option<i32> {
+        if key < self.first_key as i16 || key > self.last_key as i16 {
+            return None;
+        }
+        let offset = (key - self.middle_key as i16) as i32;
+        if self.map.is_empty() {
+            return Some(offset);
+        }
+        let size = self.map.len() as i32;
+        let octave_degree = if self.octave_degree == 0 {
+            scale_size
+        } else {
+            self.octave_degree
+        };
+        let degree = self.map[offset.rem_euclid(size) as usize]?;
+        Some(degree as i32 + offset.div_euclid(size) * octave_degree as i32)
+    }
+}
+
+/// The lines that are not comments, numbered from 1.
+fn data_lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim_end_matches('\r')))
+        .filter(|(_, line)| !line.starts_with('!'))
+}
+
+fn first_token(line: &str) -> &str {
+    line.split_whitespace().next().unwrap_or("")
+}
+
+/// A pitch in cents from cents with a period, or from a ratio or whole number.
+fn parse_pitch(token: &str) -> Option<f64> {
+    if token.contains('.') {
+        return token.parse().ok().filter(|cents: &f64| cents.is_finite());
+    }
+    let (numerator, denominator) = match token.split_once('/') {
+        Some((numerator, denominator)) => (
+            numerator.parse::<f64>().ok()?,
+            denominator.parse::<f64>().ok()?,
+        ),
+        None => (token.parse::<f64>().ok()?, 1.0),
+    };
+    let cents = 1200.0 * (numerator / denominator).log2();
+    if numerator <= 0.0 || denominator <= 0.0 || !cents.is_finite() {
+        return None;
+    }
+    Some(cents)
+}
@@ -0,0 +1,255 @@
+use crate::note::{Note, NoteLetter, Pitch};
+use crate::tuning::{KeyboardMapping, ScalaScale, TuningError};
+
+/// Just ratios above the tonic for each semitone of the chromatic scale.
+const JUST_RATIOS: [f64; 12] = [
+    1.0,
+    16.0 / 15.0,
+    9.0 / 8.0,
+    6.0 / 5.0,
+    5.0 / 4.0,
+    4.0 / 3.0,
+    45.0 / 32.0,
+    3.0 / 2.0,
+    8.0 / 5.0,
+    5.0 / 3.0,
+    9.0 / 5.0,
+    15.0 / 8.0,
+];
+
+/// How a tuning system divides the octave.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Temperament {
+    /// Twelve equal semitones.
+    EqualTemperament,
+    /// Pure 3:2 fifths, spelled along the circle of fifths, so that G# is higher than Ab.
+    Pythagorean,
+    /// Fifths narrowed by a quarter of the syntonic comma so that major thirds are pure, spelled
+    /// along the circle of fifths, so that G# is lower than Ab.
+    QuarterCommaMeantone,
+    /// Five-limit just ratios above a tonic, such as 5:4 for the major third and 3:2 for the
+    /// fifth, chosen by the number of semitones above the tonic.
+    JustIntonation(Pitch),
+    /// The octave divided into equal steps, with notes spelled along a chain of the steps' best
+    /// fifth, so that in 19 or 31 steps C# and Db differ.
+    EqualDivisions(u16),
+    /// A scale from a Scala file with its keyboard mapping.
+    Scala(ScalaScale, KeyboardMapping),
+}
+
+/// A temperament with a reference pitch, which together give every note a frequency.
+///
+/// The reference note always sounds at the reference frequency, A4 = 440 Hz unless changed, and
+/// the temperament tunes every other note relative to it.
+///
+/// ```
+/// use rust_music_theory::note::{Note, NoteLetter, Pitch};
+/// use rust_music_theory::tuning::TuningSystem;
+///
+/// let c_major = TuningSystem::just_intonation(Pitch::new(NoteLetter::C, 0));
+/// let c4: Note = "C4".parse().unwrap();
+/// let e4: Note = "E4".parse().unwrap();
+/// assert!((e4.frequency(&c_major) / c4.frequency(&c_major) - 1.25).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TuningSystem {
+    temperament: Temperament,
+    reference: Note,
+    reference_frequency: f64,
+}
+
+impl TuningSystem {
+    /// A tuning system from a temperament, with A4 at 440 Hz.
+    pub fn new(temperament: Temperament) -> Self {
+        TuningSystem {
+            temperament,
+            reference: Note::new(Pitch::new(NoteLetter::A, 0), 4),
+            reference_frequency: 440.0,
+        }
+    }
+
+    /// Twelve-tone equal temperament.
+    pub fn equal_temperament() -> Self {
+        Self::new(Temperament::EqualTemperament)
+    }
+
+    /// Pythagorean tuning in pure fifths.
+    pub fn pythagorean() -> Self {
+        Self::new(Temperament::Pythagorean)
+    }
+
+    /// Quarter-comma meantone.
+    pub fn quarter_comma_meantone() -> Self {
+        Self::new(Temperament::QuarterCommaMeantone)
+    }
+
+    /// Five-limit just intonation above a tonic.
+    pub fn just_intonation(tonic: Pitch) -> Self {
+        Self::new(Temperament::JustIntonation(tonic))
+    }
+
+    /// The octave divided into a number of equal steps, such as 19, 24, 31, or 53.
+    ///
+    /// # Errors
+    ///
+    /// Fails for zero steps.
+    pub fn equal_divisions(steps: u16) -> Result<Self, TuningError> {
+        if steps == 0 {
+            return Err(TuningError::InvalidDivisions(steps));
+        }
+        Ok(Self::new(Temperament::EqualDivisions(steps)))
+    }
+
+    /// A Scala scale with degree 0 on middle C and A4 at 440 Hz.
+    pub fn scala(scale: ScalaScale) -> Self {
+        Self::scala_with_mapping(scale, KeyboardMapping::linear(60, 69, 440.0))
+    }
+
+    /// A Scala scale laid out on the keys by a keyboard mapping, whose reference key and
+    /// frequency become the system's reference.
+    pub fn scala_with_mapping(scale: ScalaScale, mapping: KeyboardMapping) -> Self {
+        let reference = Note::from_midi_with_pitch(
+            Pitch::from_u8(mapping.reference_key() % 12),
+            mapping.reference_key() as i16,
+        );
+        let reference_frequency = mapping.reference_frequency();
+        TuningSystem {
+            temperament: Temperament::Scala(scale, mapping),
+            reference,
+            reference_frequency,
+        }
+    }
+
+    /// Tune a reference note to a frequency in Hz.
+    pub fn reference(mut self, note: Note, frequency: f64) -> Self {
+        self.reference = note;
+        self.reference_frequency = frequency;
+        self
+    }
+
+    /// Tune the reference note, A4 unless changed, to a frequency in Hz, such as 432 or 415.
+    pub fn reference_frequency(mut self, frequency: f64) -> Self {
+        self.reference_frequency = frequency;
+        self
+    }
+
+    /// The temperament.
+    pub fn temperament(&self) -> &Temperament {
+        &self.temperament
+    }
+
+    /// The note tuned to the reference frequency.
+    pub fn reference_note(&self) -> &Note {
+        &self.reference
+    }
+
+    /// The frequency of a note in Hz.
+    ///
+    /// Keys a Scala keyboard mapping leaves silent are tuned in equal temperament instead.
+    pub fn frequency(&self, note: &Note) -> f64 {
+        self.reference_frequency
+            * 2f64.powf((self.cents(note) - self.cents(&self.reference)) / 1200.0)
+    }
+
+    /// Whether a note is played by the tuning, which only a Scala keyboard mapping can prevent.
+    pub fn is_mapped(&self, note: &Note) -> bool {
+        match &self.temperament {
+            Temperament::Scala(scale, mapping) => mapping.degree(key(note), scale.len()).is_some(),
+            _ => true,
+        }
+    }
+
+    /// The frequency of a step of an equal division or a Scala scale, counted from middle C.
+    ///
+    /// Steps reach pitches that sharps and flats cannot spell, such as the quarter tones of 24
+    /// equal divisions. Other temperaments have no steps.
+    pub fn step_frequency(&self, step: i32) -> Option<f64> {
+        let c4 = Note::new(Pitch::new(NoteLetter::C, 0), 4);
+        let cents = match &self.temperament {
+            Temperament::EqualTemperament => self.cents(&c4) + step as f64 * 100.0,
+            Temperament::EqualDivisions(steps) => {
+                self.cents(&c4) + step as f64 * 1200.0 / *steps as f64
+            }
+            Temperament::Scala(scale, mapping) => {
+                let middle = mapping.degree(60, scale.len()).unwrap_or(0);
+                self.scala_cents(scale, mapping, middle + step)
+            }
+            _ => return None,
+        };
+        Some(self.reference_frequency * 2f64.powf((cents - self.cents(&self.reference)) / 1200.0))
+    }
+
+    /// A note's position in cents on the tuning's own scale, up to a constant.
+    fn cents(&self, note: &Note) -> f64 {
+        let equal = key(note) as f64 * 100.0;
+        match &self.temperament {
+            Temperament::EqualTemperament => equal,
+            Temperament::Pythagorean => fifths_cents(note, 1200.0 * 1.5f64.log2()),
+            Temperament::QuarterCommaMeantone => fifths_cents(note, 300.0 * 5f64.log2()),
+            Temperament::EqualDivisions(steps) => {
+                let steps = *steps as f64;
+                let fifth = (steps * 1.5f64.log2()).round() * 1200.0 / steps;
+                fifths_cents(note, fifth)
+            }
+            Temperament::JustIntonation(tonic) => {
+                let above = key(note) - tonic.into_u8() as i16;
+                let ratio = JUST_RATIOS[above.rem_euclid(12) as usize];
+                (above.div_euclid(12) as f64 + ratio.log2()) * 1200.0
+            }
+            Temperament::Scala(scale, mapping) => match mapping.degree(key(note), scale.len()) {
+                Some(degree) => self.scala_cents(scale, mapping, degree),
+                None => equal,
+            },
+        }
+    }
+
+    /// A Scala degree's position in cents, measured from the reference key so that silent keys
+    /// can keep equal temperament.
+    fn scala_cents(&self, scale: &ScalaScale, mapping: &KeyboardMapping, degree: i32) -> f64 {
+        let reference = mapping
+            .degree(key(&self.reference), scale.len())
+            .map_or(0.0, |degree| scale.degree_cents(degree));
+        scale.degree_cents(degree) - reference + key(&self.reference) as f64 * 100.0
+    }
+}
+
+impl Default for TuningSystem {
+    fn default() -> Self {
+        Self::equal_temperament()
+    }
+}
+
+impl Note {
+    /// The note's frequency in Hz in a tuning system.
+    pub fn frequency(&self, tuning: &TuningSystem) -> f64 {
+        tuning.frequency(self)
+    }
+}
+
+/// The note's MIDI key number, which may lie outside 0 to 127.
+fn key(note: &Note) -> i16 {
+    (note.octave + 1) * 12 + note.pitch.semitones_from_c()
+}
+
+/// A note's cents above C0 in a tuning of stacked fifths of a size.
+///
+/// The note is reached by its number of fifths from C along the circle of fifths, then brought
+/// back by octaves to the letter's place.
+fn fifths_cents(note: &Note, fifth: f64) -> f64 {
+    let letter = note.pitch.letter;
+    let fifths = letter_fifths(letter) + 7 * note.pitch.accidental as i32;
+    let octaves = (4 * fifths - letter.index() as i32).div_euclid(7);
+    (note.octave as i32 - octaves) as f64 * 1200.0 + fifths as f64 * fifth
+}
+
+fn letter_fifths(letter: NoteLetter) -> i32 {
+    match letter {
+        NoteLetter::F => -1,
+        NoteLetter::C => 0,
+        NoteLetter::G => 1,
+        NoteLetter::D => 2,
+        NoteLetter::A => 3,
+        NoteLetter::E => 4,
+        NoteLetter::B => 5,
+    }
+}
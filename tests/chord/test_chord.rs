@@ -1,6 +1,8 @@
 extern crate rust_music_theory as theory;
 use theory::chord::{Number::*, Quality::*, *};
+use theory::interval::Interval;
 use theory::note::{PitchSymbol::*, *};
+use theory::scale::Direction;
 
 fn assert_notes(symbols: &[PitchSymbol], notes: Vec<Note>) {
     for (i, symbol) in symbols.iter().enumerate() {
@@ -297,4 +299,57 @@ mod chord_tests {
         assert_eq!(dim_triad_intervals[0].semitone_count, 3);
         assert_eq!(dim_triad_intervals[1].semitone_count, 3);
     }
+
+    #[test]
+    fn test_chord_transpose_by_interval() {
+        let minor_third: Interval = "m3".parse().unwrap();
+        let chord = Chord::parse("C/E").unwrap();
+        let up = chord.transpose(minor_third, Direction::Ascending);
+        assert_eq!(up.to_string(), "Eb/G");
+        assert_eq!(up.spec(), chord.spec());
+        assert_eq!(up.inversion(), 1);
+        assert_notes(&[G, Bb, Eb], up.notes());
+
+        let slash = Chord::parse("Cmaj7/D").unwrap();
+        assert_eq!(slash.transpose(minor_third, Direction::Ascending).to_string(), "Ebmaj7/F");
+        assert_eq!(slash.transpose(minor_third, Direction::Descending).to_string(), "Amaj7/B");
+
+        let tritone: Interval = "A4".parse().unwrap();
+        let dominant = Chord::parse("F#7/A#").unwrap();
+        assert_eq!(dominant.transpose(tritone, Direction::Ascending).to_string(), "B#7/D##");
+    }
+
+    #[test]
+    fn test_chord_transpose_octave() {
+        let chord = Chord::parse("A").unwrap().with_octave(3);
+        let up = chord.transpose("M3".parse().unwrap(), Direction::Ascending);
+        assert_eq!(up.root(), Pitch::new(NoteLetter::C, 1));
+        assert_eq!(up.octave(), 4);
+        let down = chord.transpose("P8".parse().unwrap(), Direction::Descending);
+        assert_eq!(down.octave(), 2);
+    }
+
+    #[test]
+    fn test_chord_transpose_semitones_with_spelling() {
+        let chord = Chord::parse("Dm7/C").unwrap();
+        let b_major = Spelling::Key(KeySignature::new(Pitch::new(NoteLetter::B, 0)));
+        let db_major = Spelling::Key(KeySignature::new(Pitch::new(NoteLetter::D, -1)));
+        assert_eq!(chord.transpose_semitones(4, &b_major).to_string(), "F#m7/E");
+        assert_eq!(chord.transpose_semitones(4, &db_major).to_string(), "Gbm7/Fb");
+        assert_eq!(chord.transpose_semitones(-2, &Spelling::Flats).to_string(), "Cm7/Bb");
+        assert_eq!(chord.transpose_semitones(-2, &Spelling::Flats).octave(), 4);
+        assert_eq!(chord.transpose_semitones(-3, &Spelling::Flats).octave(), 3);
+    }
+
+    #[test]
+    fn test_chord_transpose_to_root() {
+        let chord = Chord::parse("G7/B").unwrap();
+        let moved = chord.transpose_to(Pitch::new(NoteLetter::D, -1));
+        assert_eq!(moved.to_string(), "Db7/F");
+        assert_eq!(moved.octave(), chord.octave());
+        assert_eq!(
+            Chord::parse("C/Bb").unwrap().transpose_to(Pitch::new(NoteLetter::E, 0)).to_string(),
+            "E/D"
+        );
+    }
 }
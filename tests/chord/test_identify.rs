@@ -0,0 +1,144 @@
+extern crate rust_music_theory as theory;
+use theory::chord::{Chord, ChordCandidate};
+use theory::note::Note;
+
+fn identify(names: &[&str]) -> Vec<ChordCandidate> {
+    let notes: Vec<Note> = names.iter().map(|name| name.parse().unwrap()).collect();
+    Chord::from_notes(&notes)
+}
+
+fn symbols(candidates: &[ChordCandidate], count: usize) -> Vec<String> {
+    candidates
+        .iter()
+        .take(count)
+        .map(|candidate| candidate.chord.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod identify_tests {
+    use super::*;
+
+    #[test]
+    fn test_root_position_triads_and_sevenths() {
+        let candidates = identify(&["C4", "E4", "G4"]);
+        assert_eq!(candidates[0].chord.to_string(), "C");
+        assert!(candidates[0].is_exact());
+
+        assert_eq!(
+            identify(&["G3", "B3", "D4", "F4"])[0].chord.to_string(),
+            "G7"
+        );
+        assert_eq!(
+            identify(&["C4", "Eb4", "Gb4", "A4"])[0].chord.to_string(),
+            "Cdim7"
+        );
+        assert_eq!(
+            identify(&["D3", "F#3", "A3", "C4", "E4"])[0]
+                .chord
+                .to_string(),
+            "D9"
+        );
+    }
+
+    #[test]
+    fn test_notes_are_an_unordered_set() {
+        let candidates = identify(&["G4", "C4", "E4", "C5", "G3"]);
+        assert_eq!(candidates[0].chord.to_string(), "C/G");
+        assert_eq!(candidates[0].chord.inversion(), 2);
+        assert!(candidates[0].is_exact());
+    }
+
+    #[test]
+    fn test_inversions_and_slash_basses() {
+        let inversion = identify(&["E3", "G3", "C4"]);
+        assert_eq!(inversion[0].chord.to_string(), "C/E");
+        assert_eq!(inversion[0].chord.inversion(), 1);
+
+        let slash = identify(&["D3", "C4", "E4", "G4"]);
+        assert!(symbols(&slash, 3).contains(&"C/D".to_string()));
+        let c_over_d = slash
+            .iter()
+            .find(|candidate| candidate.chord.to_string() == "C/D")
+            .unwrap();
+        assert!(c_over_d.is_exact());
+        assert_eq!(c_over_d.chord.inversion(), 0);
+    }
+
+    #[test]
+    fn test_omitted_fifths_and_elevenths() {
+        let no_fifth = identify(&["C4", "E4", "Bb4"]);
+        assert_eq!(symbols(&no_fifth, 2), ["C7no5", "C7"]);
+        assert!(no_fifth[0].is_exact());
+        let missing: Vec<String> = no_fifth[1]
+            .missing
+            .iter()
+            .map(|pitch| pitch.to_string())
+            .collect();
+        assert_eq!(missing, ["G"]);
+
+        let thirteenth = identify(&["C4", "E4", "G4", "B4", "D5", "A5"]);
+        assert_eq!(thirteenth[0].chord.to_string(), "Cmaj13no11");
+    }
+
+    #[test]
+    fn test_altered_chords_keep_the_given_spelling() {
+        let candidates = identify(&["E3", "G#3", "D4", "F4", "C5"]);
+        assert_eq!(candidates[0].chord.to_string(), "E7b9b13no5");
+
+        let sharp_nine = identify(&["C3", "E3", "Bb3", "D#4"]);
+        assert_eq!(sharp_nine[0].chord.to_string(), "C7#9no5");
+    }
+
+    #[test]
+    fn test_unexplained_notes_are_reported() {
+        let candidates = identify(&["C4", "E4", "G4", "F#4"]);
+        let major = candidates
+            .iter()
+            .find(|candidate| candidate.chord.to_string() == "C")
+            .unwrap();
+        let unexplained: Vec<String> = major
+            .unexplained
+            .iter()
+            .map(|note| note.to_string())
+            .collect();
+        assert_eq!(unexplained, ["F#"]);
+        assert!(major.missing.is_empty());
+    }
+
+    #[test]
+    fn test_from_midi_pitches_spells_roots_conventionally() {
+        assert_eq!(
+            Chord::from_midi_pitches(&[63, 67, 70])[0].chord.to_string(),
+            "Eb"
+        );
+        assert_eq!(
+            Chord::from_midi_pitches(&[61, 65, 68])[0].chord.to_string(),
+            "Db"
+        );
+        assert_eq!(
+            Chord::from_midi_pitches(&[66, 70, 73])[0].chord.to_string(),
+            "F#"
+        );
+        assert_eq!(
+            Chord::from_midi_pitches(&[56, 60, 63, 66])[0]
+                .chord
+                .to_string(),
+            "Ab7"
+        );
+
+        let slash = Chord::from_midi_pitches(&[56, 60, 63, 66]);
+        assert!(symbols(&slash, 3).contains(&"Cdim/Ab".to_string()));
+
+        assert_eq!(Chord::from_midi_pitches(&[60, 64, 67])[0].chord.octave(), 4);
+        let low = Chord::from_midi_pitches(&[40, 55, 60]);
+        assert_eq!(low[0].chord.to_string(), "C/E");
+        assert_eq!(low[0].chord.octave(), 2);
+    }
+
+    #[test]
+    fn test_empty_and_single_note_input() {
+        assert!(Chord::from_notes(&[]).is_empty());
+        assert!(Chord::from_midi_pitches(&[60]).is_empty());
+    }
+}
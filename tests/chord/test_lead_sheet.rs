@@ -273,3 +273,22 @@ fn parse_error_categories_carry_source_positions() {
         Err(ChordError::InvalidSlashBass { position: 2 })
     ));
 }
+
+#[test]
+fn chord_tones_expose_spelled_intervals() {
+    let tone_intervals = Chord::parse("C7(b9,#11)")
+        .unwrap()
+        .formula()
+        .tones()
+        .iter()
+        .map(|tone| tone.interval().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(tone_intervals, ["P1", "M3", "P5", "m7", "m9", "A11"]);
+
+    let diminished = Chord::parse("Cdim7").unwrap();
+    let seventh = diminished.formula().tones().last().unwrap();
+    assert_eq!(seventh.interval().unwrap().to_string(), "d7");
+
+    let lowered_root = theory::chord::ChordTone::new(1, -1).unwrap();
+    assert!(lowered_root.interval().is_err());
+}
@@ -0,0 +1,161 @@
+extern crate rust_music_theory as theory;
+use theory::chord::{Chord, ChordError, Voicing};
+use theory::note::Note;
+
+fn voiced(symbol: &str, voicing: Voicing) -> Vec<String> {
+    names(&Chord::parse(symbol).unwrap().voicing(voicing).unwrap())
+}
+
+fn names(notes: &[Note]) -> Vec<String> {
+    notes
+        .iter()
+        .map(|note| format!("{}{}", note.pitch, note.octave))
+        .collect()
+}
+
+fn note(name: &str) -> Note {
+    name.parse().unwrap()
+}
+
+#[cfg(test)]
+mod voicing_tests {
+    use super::*;
+
+    #[test]
+    fn test_close_and_open_voicings() {
+        assert_eq!(
+            voiced("Cmaj7", Voicing::Close),
+            vec!["C4", "E4", "G4", "B4"]
+        );
+        assert_eq!(voiced("Cmaj7", Voicing::Open), vec!["C4", "G4", "E5", "B5"]);
+        assert_eq!(voiced("C", Voicing::Open), vec!["C4", "G4", "E5"]);
+        assert_eq!(
+            voiced("Cmaj7/E", Voicing::Close),
+            vec!["E4", "G4", "B4", "C5"]
+        );
+        assert_eq!(
+            voiced("C/F#", Voicing::Close),
+            vec!["F#4", "C5", "E5", "G5"]
+        );
+    }
+
+    #[test]
+    fn test_drop_voicings() {
+        assert_eq!(
+            voiced("Cmaj7", Voicing::Drop2),
+            vec!["G4", "C5", "E5", "B5"]
+        );
+        assert_eq!(
+            voiced("Cmaj7", Voicing::Drop3),
+            vec!["E4", "C5", "G5", "B5"]
+        );
+        assert_eq!(
+            voiced("Cmaj7", Voicing::Drop24),
+            vec!["C4", "G4", "E5", "B5"]
+        );
+        // Extended chords keep their four most important tones.
+        assert_eq!(voiced("C9", Voicing::Drop2), vec!["E4", "C5", "D5", "Bb5"]);
+        // Inversions keep their bass.
+        assert_eq!(
+            voiced("Cmaj7/E", Voicing::Drop2),
+            vec!["E4", "B4", "C5", "G5"]
+        );
+        assert_eq!(
+            voiced("C7/Bb", Voicing::Drop3),
+            vec!["Bb4", "G5", "C6", "E6"]
+        );
+    }
+
+    #[test]
+    fn test_shell_and_rootless_voicings() {
+        assert_eq!(voiced("Dm7", Voicing::Shell), vec!["D4", "F4", "C5"]);
+        assert_eq!(voiced("C6", Voicing::Shell), vec!["C4", "E4", "A4"]);
+        assert_eq!(
+            voiced("Dm7", Voicing::RootlessA),
+            vec!["F4", "A4", "C5", "E5"]
+        );
+        assert_eq!(
+            voiced("Dm7", Voicing::RootlessB),
+            vec!["C5", "E5", "F5", "A5"]
+        );
+        assert_eq!(
+            voiced("G7", Voicing::RootlessA),
+            vec!["B4", "E5", "F5", "A5"]
+        );
+        assert_eq!(
+            voiced("C7b9", Voicing::RootlessB),
+            vec!["Bb4", "Db5", "E5", "A5"]
+        );
+        assert_eq!(
+            voiced("Cm7b5", Voicing::RootlessA),
+            vec!["Eb4", "Gb4", "Bb4", "D5"]
+        );
+    }
+
+    #[test]
+    fn test_spread_and_quartal_voicings() {
+        assert_eq!(voiced("Dm7", Voicing::SpreadTriad), vec!["D4", "A4", "F5"]);
+        assert_eq!(
+            voiced("Dm7", Voicing::Quartal),
+            vec!["D4", "G4", "C5", "F5"]
+        );
+        assert_eq!(
+            voiced("Dm7", Voicing::SoWhat),
+            vec!["D4", "G4", "C5", "F5", "A5"]
+        );
+        assert_eq!(voiced("C7", Voicing::Quartal), vec!["E4", "A4", "D5", "G5"]);
+        assert_eq!(
+            voiced("C/E", Voicing::Quartal),
+            vec!["E4", "E5", "A5", "D6", "G6"]
+        );
+    }
+
+    #[test]
+    fn test_target_top_note() {
+        let chord = Chord::parse("Cmaj7").unwrap();
+        let top = |voicing| names(&chord.voicing_with_top(voicing, &note("E5")).unwrap());
+        assert_eq!(top(Voicing::Close), vec!["G4", "B4", "C5", "E5"]);
+        assert_eq!(top(Voicing::Drop2), vec!["C4", "G4", "B4", "E5"]);
+        assert_eq!(top(Voicing::Shell), vec!["C4", "B4", "E5"]);
+        // Without the target note in the voicing, the nearest top is chosen.
+        let fifth = Chord::parse("Dm7")
+            .unwrap()
+            .voicing_with_top(Voicing::Close, &note("E5"))
+            .unwrap();
+        assert_eq!(names(&fifth), vec!["A4", "C5", "D5", "F5"]);
+    }
+
+    #[test]
+    fn test_range() {
+        let chord = Chord::parse("G7").unwrap();
+        let voicing = chord
+            .voicing_in_range(Voicing::Drop2, &note("C3"), &note("C5"))
+            .unwrap();
+        assert_eq!(names(&voicing), vec!["F3", "B3", "D4", "G4"]);
+        assert_eq!(
+            chord.voicing_in_range(Voicing::Quartal, &note("C3"), &note("C4")),
+            Err(ChordError::VoicingOutOfRange(Voicing::Quartal))
+        );
+    }
+
+    #[test]
+    fn test_unsupported_voicings() {
+        let triad = Chord::parse("C").unwrap();
+        assert!(matches!(
+            triad.voicing(Voicing::Drop3),
+            Err(ChordError::UnsupportedVoicing {
+                voicing: Voicing::Drop3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            triad.voicing(Voicing::Shell),
+            Err(ChordError::UnsupportedVoicing { .. })
+        ));
+        assert!(matches!(
+            Chord::parse("C7b9").unwrap().voicing(Voicing::Quartal),
+            Err(ChordError::UnsupportedVoicing { .. })
+        ));
+        assert_eq!(Voicing::Drop24.to_string(), "drop-2&4");
+    }
+}
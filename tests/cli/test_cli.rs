@@ -118,3 +118,72 @@ fn second_audit_regressions_match_the_rust_parser() {
         assert!(String::from_utf8(result.stderr).unwrap().contains("error:"));
     }
 }
+
+#[test]
+fn prints_notes_of_interval_expressions() {
+    let up = rustmt(&["interval", "Eb4", "+", "m7"]);
+    assert!(up.status.success());
+    assert_eq!(stdout(&up), "Notes:\n  1: Eb4\n  2: Db5\n");
+
+    let down = rustmt(&["interval", "C5 - perfect fifth"]);
+    assert!(down.status.success());
+    assert_eq!(stdout(&down), "Notes:\n  1: C5\n  2: F4\n");
+
+    let compound = rustmt(&["interval", "D3", "+", "M9"]);
+    assert_eq!(stdout(&compound), "Notes:\n  1: D3\n  2: E4\n");
+
+    let invalid = rustmt(&["interval", "C4", "+", "P3"]);
+    assert!(!invalid.status.success());
+    assert!(String::from_utf8(invalid.stderr)
+        .unwrap()
+        .contains("quality P cannot be applied to number 3"));
+
+    assert!(!rustmt(&["interval", "C4", "m3"]).status.success());
+}
+
+#[test]
+fn prints_chord_diagrams_and_scale_tablature() {
+    let diagram = rustmt(&["chord", "C", "--diagram"]);
+    assert!(diagram.status.success());
+    assert_eq!(
+        stdout(&diagram),
+        "C: x32010\nx     o   o\n===========\n| | | | o |\n| | o | | |\n| o | | | |\n| | | | | |\n"
+    );
+
+    let tab = rustmt(&["scale", "C", "major", "--tab"]);
+    assert!(tab.status.success());
+    assert_eq!(
+        stdout(&tab),
+        "E|-----------5-7-8-|\n\
+         B|-----5-6-8-------|\n\
+         G|-5-7-------------|\n\
+         D|-----------------|\n\
+         A|-----------------|\n\
+         E|-----------------|\n"
+    );
+}
+
+#[test]
+fn prints_diagrams_and_tablature_in_other_tunings() {
+    let diagram = rustmt(&["chord", "C", "--diagram", "--tuning", "ukulele"]);
+    assert!(diagram.status.success());
+    assert!(stdout(&diagram).starts_with("C: 0003\n"));
+
+    let drop_d = rustmt(&["chord", "D", "--diagram", "--tuning", "drop-d"]);
+    assert!(drop_d.status.success());
+    assert!(stdout(&drop_d).starts_with("D: 000232\n"));
+
+    let tab = rustmt(&["scale", "C", "major", "--tab", "--tuning", "ukulele"]);
+    assert!(tab.status.success());
+    assert_eq!(
+        stdout(&tab),
+        "A|-----------0-2-3-|\n\
+         E|-------1-3-------|\n\
+         C|-0-2-4-----------|\n\
+         G|-----------------|\n"
+    );
+
+    assert!(!rustmt(&["chord", "C", "--diagram", "--tuning", "banjo"])
+        .status
+        .success());
+}
@@ -1,5 +1,5 @@
 use rust_music_theory::chord::ChordError;
-use rust_music_theory::interval::IntervalError;
+use rust_music_theory::interval::{IntervalError, Number, Quality};
 use rust_music_theory::note::NoteError;
 use rust_music_theory::scale::ScaleError;
 use std::error::Error;
@@ -17,7 +17,24 @@ fn error_messages_are_specific_and_stable() {
     );
     assert_eq!(ChordError::InvalidInversion(4).to_string(), "Invalid chord inversion: 4");
     assert_eq!(IntervalError::InvalidInterval.to_string(), "Invalid interval!");
+    assert_eq!(
+        IntervalError::InvalidQuality {
+            quality: Quality::Major,
+            number: Number::Fifth
+        }
+        .to_string(),
+        "Invalid interval: quality M cannot be applied to number 5"
+    );
+    assert_eq!(
+        IntervalError::UnknownQuality("huge".to_string()).to_string(),
+        "Unknown interval quality: huge"
+    );
+    assert_eq!(
+        IntervalError::UnknownNumber("zeroth".to_string()).to_string(),
+        "Unknown interval number: zeroth"
+    );
     assert_eq!(NoteError::InvalidPitch.to_string(), "Invalid Pitch Class!");
+    assert_eq!(NoteError::InvalidOctave.to_string(), "Invalid Octave!");
     assert_eq!(ScaleError::ModeFromRegex.to_string(), "Can't determine the mode!");
     assert_eq!(ScaleError::InvalidRegex.to_string(), "Invalid scale regex!");
     assert_eq!(
@@ -0,0 +1,237 @@
+extern crate rust_music_theory as theory;
+use theory::chord::Chord;
+use theory::fretboard::{
+    CagedShape, FretPosition, Fretboard, FretboardError, ScalePattern, Tuning,
+};
+use theory::note::{Note, NoteLetter, Notes, Pitch};
+use theory::scale::Scale;
+
+fn guitar() -> Fretboard {
+    Fretboard::new(Tuning::standard(), 22)
+}
+
+fn shape(symbol: &str) -> String {
+    guitar().chord_fingerings(&Chord::parse(symbol).unwrap(), 4)[0]
+        .frets
+        .iter()
+        .map(|fret| fret.map_or("x".to_string(), |fret| fret.to_string()))
+        .collect()
+}
+
+fn names(notes: &[Note]) -> Vec<String> {
+    notes
+        .iter()
+        .map(|note| format!("{}{}", note.pitch, note.octave))
+        .collect()
+}
+
+fn position(string: usize, fret: u8) -> FretPosition {
+    FretPosition { string, fret }
+}
+
+#[cfg(test)]
+mod fretboard_tests {
+    use super::*;
+
+    #[test]
+    fn test_tunings() {
+        assert_eq!(Tuning::standard().to_string(), "E2 A2 D3 G3 B3 E4");
+        assert_eq!(Tuning::drop_d().to_string(), "D2 A2 D3 G3 B3 E4");
+        assert_eq!(Tuning::dadgad().to_string(), "D2 A2 D3 G3 A3 D4");
+        assert_eq!(Tuning::bass().to_string(), "E1 A1 D2 G2");
+        assert_eq!(Tuning::ukulele().to_string(), "G4 C4 E4 A4");
+        assert_eq!(Tuning::seven_string().len(), 7);
+        assert_eq!(Tuning::new(Vec::new()), Err(FretboardError::EmptyTuning));
+    }
+
+    #[test]
+    fn test_positions_of_a_note() {
+        let e4 = Note::new(Pitch::new(NoteLetter::E, 0), 4);
+        assert_eq!(
+            guitar().positions(&e4),
+            [
+                position(1, 19),
+                position(2, 14),
+                position(3, 9),
+                position(4, 5),
+                position(5, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pitch_positions_and_note_at() {
+        let board = Fretboard::new(Tuning::standard(), 12);
+        let positions = board.pitch_positions(Pitch::new(NoteLetter::A, 0));
+        assert_eq!(
+            positions[..3],
+            [position(0, 5), position(1, 0), position(1, 12)]
+        );
+        assert_eq!(positions.len(), 7);
+        let note = board.note_at(position(1, 1)).unwrap();
+        assert_eq!(names(&[note]), ["A#2"]);
+        assert_eq!(board.note_at(position(6, 0)), None);
+        assert_eq!(board.note_at(position(0, 13)), None);
+    }
+
+    #[test]
+    fn test_open_chord_fingerings() {
+        assert_eq!(shape("C"), "x32010");
+        assert_eq!(shape("E"), "022100");
+        assert_eq!(shape("G"), "320003");
+        assert_eq!(shape("Am"), "x02210");
+        assert_eq!(shape("D"), "xx0232");
+        assert_eq!(shape("Cmaj7"), "x32000");
+        assert_eq!(shape("C/G"), "332010");
+    }
+
+    #[test]
+    fn test_fingering_notes_are_spelled_from_the_chord() {
+        let fingerings = guitar().chord_fingerings(&Chord::parse("Ab").unwrap(), 4);
+        let notes = fingerings[0].notes();
+        assert_eq!(notes[0].pitch, Pitch::new(NoteLetter::A, -1));
+        assert!(notes
+            .iter()
+            .all(|note| ["Ab", "C", "Eb"].contains(&note.pitch.to_string().as_str())));
+        assert_eq!(fingerings[0].positions().len(), notes.len());
+    }
+
+    #[test]
+    fn test_barre_chords() {
+        let fingerings = guitar().chord_fingerings(&Chord::parse("F").unwrap(), 4);
+        let barred = &fingerings[0];
+        assert_eq!(
+            barred.frets,
+            [Some(1), Some(3), Some(3), Some(2), Some(1), Some(1)]
+        );
+        let barre = barred.barre.unwrap();
+        assert_eq!((barre.fret, barre.first, barre.last), (1, 0, 5));
+        assert!(fingerings
+            .iter()
+            .all(|fingering| fingering.positions().len() >= 3));
+    }
+
+    #[test]
+    fn test_fingerings_are_ranked_by_difficulty() {
+        let fingerings = guitar().chord_fingerings(&Chord::parse("Dm7").unwrap(), 4);
+        assert!(fingerings
+            .windows(2)
+            .all(|pair| pair[0].difficulty <= pair[1].difficulty));
+        for fingering in &fingerings {
+            let fretted: Vec<u8> = fingering
+                .frets
+                .iter()
+                .flatten()
+                .copied()
+                .filter(|&f| f > 0)
+                .collect();
+            if let (Some(low), Some(high)) = (fretted.iter().min(), fretted.iter().max()) {
+                assert!(high - low < 4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_spans_wider_than_the_neck() {
+        let c = Chord::parse("C").unwrap();
+        let short = Fretboard::new(Tuning::standard(), 5);
+        assert_eq!(
+            short.chord_fingerings(&c, 255),
+            short.chord_fingerings(&c, 5)
+        );
+        let ukulele = Fretboard::new(Tuning::ukulele(), 12);
+        let wide = ukulele.chord_fingerings(&c, u8::MAX);
+        assert_eq!(wide, ukulele.chord_fingerings(&c, 12));
+        assert!(wide.iter().all(|fingering| fingering
+            .frets
+            .iter()
+            .flatten()
+            .all(|&fret| fret <= 12)));
+    }
+
+    #[test]
+    fn test_other_tunings() {
+        let ukulele = Fretboard::new(Tuning::ukulele(), 12);
+        let fingerings = ukulele.chord_fingerings(&Chord::parse("C").unwrap(), 4);
+        assert_eq!(fingerings[0].frets, [Some(0), Some(0), Some(0), Some(3)]);
+
+        let drop_d = Fretboard::new(Tuning::drop_d(), 22);
+        let fingerings = drop_d.chord_fingerings(&Chord::parse("D").unwrap(), 4);
+        assert_eq!(fingerings[0].frets[..3], [Some(0), Some(0), Some(0)]);
+    }
+
+    #[test]
+    fn test_caged_boxes() {
+        let scale = Scale::from_regex("C major").unwrap();
+        let boxes = guitar().caged_boxes(&scale).unwrap();
+        let shapes: Vec<ScalePattern> = boxes.iter().map(|b| b.pattern).collect();
+        assert_eq!(
+            shapes,
+            [
+                CagedShape::C,
+                CagedShape::A,
+                CagedShape::G,
+                CagedShape::E,
+                CagedShape::D
+            ]
+            .iter()
+            .map(|&shape| ScalePattern::Caged(shape))
+            .collect::<Vec<_>>()
+        );
+        let first = &boxes[0];
+        assert_eq!(
+            first.positions()[..3],
+            [position(0, 0), position(0, 1), position(0, 3)]
+        );
+        assert_eq!(names(&first.notes()[..3]), ["E2", "F2", "G2"]);
+        for scale_box in &boxes {
+            let notes = scale_box.notes();
+            assert!(notes
+                .windows(2)
+                .all(|pair| pair[0].midi_pitch() < pair[1].midi_pitch()));
+        }
+    }
+
+    #[test]
+    fn test_caged_boxes_need_three_strings() {
+        let scale = Scale::from_regex("C major").unwrap();
+        let tuning = Tuning::new(vec![
+            Note::new(Pitch::new(NoteLetter::E, 0), 2),
+            Note::new(Pitch::new(NoteLetter::A, 0), 2),
+        ])
+        .unwrap();
+        assert_eq!(
+            Fretboard::new(tuning, 22).caged_boxes(&scale),
+            Err(FretboardError::TooFewStrings {
+                strings: 2,
+                needed: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_three_notes_per_string() {
+        let scale = Scale::from_regex("G major").unwrap();
+        let patterns = guitar().three_notes_per_string(&scale).unwrap();
+        assert_eq!(patterns.len(), 7);
+        assert_eq!(patterns[0].pattern, ScalePattern::ThreeNotesPerString(1));
+        assert_eq!(
+            patterns[0].positions()[..3],
+            [position(0, 3), position(0, 5), position(0, 7)]
+        );
+        assert_eq!(names(&patterns[0].notes()[..4]), ["G2", "A2", "B2", "C3"]);
+        for pattern in &patterns {
+            assert_eq!(pattern.notes.len(), 18);
+            assert!(pattern
+                .notes()
+                .windows(2)
+                .all(|pair| pair[0].midi_pitch() < pair[1].midi_pitch()));
+        }
+
+        let pentatonic = Scale::from_regex("C pentatonic major").unwrap();
+        assert!(matches!(
+            guitar().three_notes_per_string(&pentatonic),
+            Err(FretboardError::NotHeptatonic(_))
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+extern crate rust_music_theory as theory;
+use theory::chord::Chord;
+use theory::fretboard::{FretPosition, Fretboard, FretboardError, Tuning};
+use theory::note::{Note, NoteLetter, Notes, Pitch};
+use theory::scale::{Direction, Scale, ScaleType};
+
+fn guitar() -> Fretboard {
+    Fretboard::new(Tuning::standard(), 22)
+}
+
+fn easiest(symbol: &str) -> theory::fretboard::Fingering {
+    guitar()
+        .chord_fingerings(&Chord::parse(symbol).unwrap(), 4)
+        .remove(0)
+}
+
+fn position(string: usize, fret: u8) -> FretPosition {
+    FretPosition { string, fret }
+}
+
+#[cfg(test)]
+mod tab_tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_shapes() {
+        assert_eq!(easiest("C").compact(), "x32010");
+        assert_eq!(easiest("F").to_string(), "133211");
+        let wide = guitar()
+            .chord_fingerings(&Chord::parse("A").unwrap(), 4)
+            .into_iter()
+            .find(|fingering| fingering.frets[1] == Some(12))
+            .unwrap();
+        assert!(wide.compact().contains("-12-"));
+    }
+
+    #[test]
+    fn test_open_chord_diagram() {
+        assert_eq!(
+            easiest("C").diagram(),
+            "x     o   o\n\
+             ===========\n\
+             | | | | o |\n\
+             | | o | | |\n\
+             | o | | | |\n\
+             | | | | | |"
+        );
+    }
+
+    #[test]
+    fn test_barre_chord_diagrams() {
+        assert_eq!(
+            easiest("F").diagram(),
+            "\n\
+             ===========\n\
+             o-o-o-o-o-o\n\
+             | | | o | |\n\
+             | o o | | |\n\
+             | | | | | |"
+        );
+        let b_flat = guitar()
+            .chord_fingerings(&Chord::parse("Bb").unwrap(), 4)
+            .into_iter()
+            .find(|fingering| fingering.barre.map(|barre| barre.fret) == Some(6))
+            .unwrap();
+        assert_eq!(
+            b_flat.diagram(),
+            "\n\
+             -----------\n\
+             o-o-o-o-o-o 6fr\n\
+             | | | o | |\n\
+             | o o | | |\n\
+             | | | | | |"
+        );
+    }
+
+    #[test]
+    fn test_tab_positions_stay_in_one_position() {
+        let scale = Scale::new(
+            ScaleType::Diatonic,
+            Pitch::new(NoteLetter::C, 0),
+            3,
+            None,
+            Direction::Ascending,
+        )
+        .unwrap();
+        let positions = guitar().tab_positions(&scale.notes()).unwrap();
+        assert_eq!(
+            positions,
+            [
+                position(1, 3),
+                position(1, 5),
+                position(2, 2),
+                position(2, 3),
+                position(2, 5),
+                position(3, 2),
+                position(3, 4),
+                position(3, 5)
+            ]
+        );
+        assert_eq!(
+            guitar().tab(&scale.notes()).unwrap(),
+            "E|-----------------|\n\
+             B|-----------------|\n\
+             G|-----------2-4-5-|\n\
+             D|-----2-3-5-------|\n\
+             A|-3-5-------------|\n\
+             E|-----------------|"
+        );
+    }
+
+    #[test]
+    fn test_tab_pads_wide_frets() {
+        let tab = guitar().render_tab(&[position(5, 0), position(5, 12)]);
+        assert_eq!(tab.lines().next(), Some("E|-0--12-|"));
+        assert_eq!(tab.lines().nth(1), Some("B|-------|"));
+    }
+
+    #[test]
+    fn test_tab_rejects_notes_off_the_neck() {
+        let low = Note::new(Pitch::new(NoteLetter::C, 0), 2);
+        assert_eq!(
+            guitar().tab(std::slice::from_ref(&low)),
+            Err(FretboardError::NoteOutOfRange(low.clone()))
+        );
+        assert!(guitar().print_tab(&low).is_err());
+        assert_eq!(guitar().tab(&[]).unwrap().lines().count(), 6);
+    }
+}
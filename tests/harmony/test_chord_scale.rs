@@ -0,0 +1,128 @@
+extern crate rust_music_theory as theory;
+use theory::chord::{Chord, ChordTone};
+use theory::harmony::{ChordScale, ToneRole};
+use theory::scale::Mode;
+
+fn scales(symbol: &str) -> Vec<ChordScale> {
+    Chord::parse(symbol).unwrap().available_scales()
+}
+
+fn modes(symbol: &str) -> Vec<Mode> {
+    scales(symbol)
+        .iter()
+        .map(|candidate| candidate.scale.mode.unwrap())
+        .collect()
+}
+
+fn names(pitches: &[theory::note::Pitch]) -> Vec<String> {
+    pitches.iter().map(|pitch| pitch.to_string()).collect()
+}
+
+#[cfg(test)]
+mod chord_scale_tests {
+    use super::*;
+
+    #[test]
+    fn test_dominant_seventh_prefers_mixolydian() {
+        let candidates = scales("C7");
+        assert_eq!(
+            modes("C7"),
+            [
+                Mode::Mixolydian,
+                Mode::LydianDominant,
+                Mode::MixolydianFlat6,
+                Mode::PhrygianDominant
+            ]
+        );
+        let mixolydian = &candidates[0];
+        assert_eq!(names(&mixolydian.avoid_notes()), ["F"]);
+        assert_eq!(names(&mixolydian.tensions()), ["D", "A"]);
+        assert!(mixolydian.clashes.is_empty());
+    }
+
+    #[test]
+    fn test_altered_tones_select_the_melodic_minor_modes() {
+        assert_eq!(modes("C7alt"), [Mode::Altered]);
+        let altered = &scales("C7alt")[0];
+        assert!(altered
+            .tones
+            .iter()
+            .all(|tone| tone.role == ToneRole::ChordTone));
+        let pitches: Vec<String> = altered
+            .tones
+            .iter()
+            .map(|tone| tone.pitch.to_string())
+            .collect();
+        assert_eq!(pitches, ["C", "Db", "D#", "E", "Gb", "G#", "Bb"]);
+
+        let lydian_dominant = &scales("C7#11")[0];
+        assert_eq!(lydian_dominant.scale.mode, Some(Mode::LydianDominant));
+        assert!(lydian_dominant.avoid_notes().is_empty());
+        assert!(lydian_dominant.clashes.is_empty());
+    }
+
+    #[test]
+    fn test_tones_carry_their_degree_above_the_root() {
+        let candidates = scales("Cmaj7");
+        assert_eq!(
+            modes("Cmaj7"),
+            [Mode::Ionian, Mode::Lydian, Mode::LydianSharp2]
+        );
+        let lydian = &candidates[1];
+        let raised_eleventh = lydian
+            .tones
+            .iter()
+            .find(|tone| tone.pitch.to_string() == "F#")
+            .unwrap();
+        assert_eq!(raised_eleventh.degree, ChordTone::new(11, 1).unwrap());
+        assert_eq!(raised_eleventh.role, ToneRole::Tension);
+        assert_eq!(names(&candidates[0].avoid_notes()), ["F"]);
+    }
+
+    #[test]
+    fn test_minor_and_half_diminished_chords() {
+        assert_eq!(modes("Cm7")[0], Mode::Dorian);
+        assert!(scales("Cm7")[0].avoid_notes().is_empty());
+        let aeolian = scales("Cm7")
+            .into_iter()
+            .find(|candidate| candidate.scale.mode == Some(Mode::Aeolian))
+            .unwrap();
+        assert_eq!(names(&aeolian.avoid_notes()), ["Ab"]);
+        assert_eq!(
+            &modes("Cm7b5")[..3],
+            [Mode::LocrianSharp2, Mode::LocrianNatural6, Mode::Locrian]
+        );
+    }
+
+    #[test]
+    fn test_flat_nine_is_a_tension_on_dominant_chords_only() {
+        let phrygian_dominant = scales("C7")
+            .into_iter()
+            .find(|candidate| candidate.scale.mode == Some(Mode::PhrygianDominant))
+            .unwrap();
+        assert!(names(&phrygian_dominant.tensions()).contains(&"Db".to_string()));
+        let phrygian = scales("Cm7")
+            .into_iter()
+            .find(|candidate| candidate.scale.mode == Some(Mode::Phrygian))
+            .unwrap();
+        assert_eq!(names(&phrygian.avoid_notes()), ["Db", "Ab"]);
+        assert_eq!(modes("C7b9")[0], Mode::PhrygianDominant);
+    }
+
+    #[test]
+    fn test_near_fits_report_the_replaced_chord_tone() {
+        let candidates = scales("C7(b9,#11)");
+        assert_eq!(
+            modes("C7(b9,#11)"),
+            [Mode::LydianDominant, Mode::PhrygianDominant]
+        );
+        assert_eq!(names(&candidates[0].clashes), ["Db"]);
+        assert_eq!(names(&candidates[1].clashes), ["F#"]);
+    }
+
+    #[test]
+    fn test_inversions_do_not_change_the_scales() {
+        assert_eq!(modes("Cmaj7/E"), modes("Cmaj7"));
+        assert_eq!(scales("Cmaj7/E")[0].tones, scales("Cmaj7")[0].tones);
+    }
+}
@@ -0,0 +1,211 @@
+extern crate rust_music_theory as theory;
+use theory::harmony::{Progression, ProgressionError};
+use theory::interval::Interval;
+use theory::note::{KeySignature, NoteLetter, Notes, Pitch, Spelling};
+use theory::scale::Direction;
+
+fn symbols(progression: &Progression) -> Vec<String> {
+    progression
+        .iter()
+        .map(|timed| timed.chord.to_string())
+        .collect()
+}
+
+fn timing(progression: &Progression) -> Vec<(usize, f64, f64)> {
+    progression
+        .iter()
+        .map(|timed| (timed.bar, timed.start, timed.beats))
+        .collect()
+}
+
+#[cfg(test)]
+mod progression_tests {
+    use super::*;
+
+    #[test]
+    fn test_bars_share_their_beats() {
+        let progression = Progression::parse("| Dm7 G7 | Cmaj7 % | A7b9 |").unwrap();
+        assert_eq!(
+            symbols(&progression),
+            vec!["Dm7", "G7", "Cmaj7", "Cmaj7", "A7b9"]
+        );
+        assert_eq!(
+            timing(&progression),
+            vec![
+                (0, 0.0, 2.0),
+                (0, 2.0, 2.0),
+                (1, 4.0, 2.0),
+                (1, 6.0, 2.0),
+                (2, 8.0, 4.0)
+            ]
+        );
+        assert_eq!(progression.bars().len(), 3);
+        assert_eq!(progression.total_beats(), 12.0);
+    }
+
+    #[test]
+    fn test_explicit_durations_leave_the_rest_to_other_chords() {
+        let progression = Progression::parse("| C:3 G7 | F:1 G:1 C:2 |").unwrap();
+        assert_eq!(
+            timing(&progression),
+            vec![
+                (0, 0.0, 3.0),
+                (0, 3.0, 1.0),
+                (1, 4.0, 1.0),
+                (1, 5.0, 1.0),
+                (1, 6.0, 2.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_time_signatures_change_bar_length() {
+        let progression = Progression::parse("| 3/4 C G | 6/8 Am | F |").unwrap();
+        let bars: Vec<(f64, (u8, u8))> = progression
+            .bars()
+            .iter()
+            .map(|bar| (bar.beats, bar.time_signature))
+            .collect();
+        assert_eq!(bars, vec![(3.0, (3, 4)), (3.0, (6, 8)), (3.0, (6, 8))]);
+        assert_eq!(timing(&progression)[1], (0, 1.5, 1.5));
+    }
+
+    #[test]
+    fn test_repeats_are_written_out() {
+        let progression = Progression::parse("| C |: Dm7 | G7 :| C |").unwrap();
+        assert_eq!(
+            symbols(&progression),
+            vec!["C", "Dm7", "G7", "Dm7", "G7", "C"]
+        );
+        assert_eq!(progression.total_beats(), 24.0);
+
+        let from_start = Progression::parse("| Am | E7 :|").unwrap();
+        assert_eq!(symbols(&from_start), vec!["Am", "E7", "Am", "E7"]);
+    }
+
+    #[test]
+    fn test_unbarred_chords_last_a_bar() {
+        let progression = Progression::parse("Dm7 G7:2 C7(b9, #11)").unwrap();
+        assert_eq!(symbols(&progression), vec!["Dm7", "G7", "C7b9#11"]);
+        assert_eq!(
+            timing(&progression),
+            vec![(0, 0.0, 4.0), (1, 4.0, 2.0), (2, 6.0, 4.0)]
+        );
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        let text = "| Dm7 G7 | 3/4 C:2 C7:1 | Fmaj7 |";
+        let progression: Progression = text.parse().unwrap();
+        assert_eq!(progression.to_string(), text);
+        assert_eq!(
+            Progression::parse(&progression.to_string()).unwrap(),
+            progression
+        );
+    }
+
+    #[test]
+    fn test_unbarred_display_round_trips() {
+        for (text, shown) in [
+            ("C:2 D", "C:2 D"),
+            ("Dm7 G7:2 C7(b9, #11)", "Dm7 G7:2 C7b9#11"),
+            ("3/4 C D:1.5 6/8 E:6", "3/4 C D:1.5 6/8 E:6"),
+            ("C D", "| C | D |"),
+            (
+                "| C:1 D:3 | 3/4 E:2.5 F:0.5 |",
+                "| C:1 D:3 | 3/4 E:2.5 F:0.5 |",
+            ),
+        ] {
+            let progression = Progression::parse(text).unwrap();
+            assert_eq!(progression.to_string(), shown);
+            assert_eq!(
+                Progression::parse(&progression.to_string()).unwrap(),
+                progression
+            );
+        }
+    }
+
+    #[test]
+    fn test_transpose_keeps_timing() {
+        let progression = Progression::parse("| Dm7:2 G7:1 Db7 | Cmaj7 |").unwrap();
+        let up = progression.transpose(Interval::from_semitone(2).unwrap(), Direction::Ascending);
+        assert_eq!(symbols(&up), vec!["Em7", "A7", "Eb7", "Dmaj7"]);
+        assert_eq!(timing(&up), timing(&progression));
+
+        let down = progression.transpose_semitones(-1, &Spelling::Flats);
+        assert_eq!(symbols(&down), vec!["Dbm7", "Gb7", "C7", "Bmaj7"]);
+    }
+
+    #[test]
+    fn test_analyze_and_estimate_key() {
+        let progression = Progression::parse("| Dm7 | G7 | Cmaj7 | Cmaj7 |").unwrap();
+        let key = KeySignature::new(Pitch::new(NoteLetter::C, 0));
+        let numerals: Vec<String> = progression
+            .analyze(&key)
+            .iter()
+            .map(|numeral| numeral.to_string())
+            .collect();
+        assert_eq!(numerals, vec!["ii7", "V7", "IM7", "IM7"]);
+        assert_eq!(progression.estimate_key(), Some(key));
+    }
+
+    #[test]
+    fn test_notes_follow_the_chords() {
+        let progression = Progression::parse("| C G |").unwrap();
+        let pitches: Vec<String> = progression
+            .notes()
+            .iter()
+            .map(|note| note.pitch.to_string())
+            .collect();
+        assert_eq!(pitches, vec!["C", "E", "G", "G", "B", "D"]);
+    }
+
+    #[test]
+    fn test_errors_carry_positions() {
+        assert_eq!(
+            Progression::parse("| % C |"),
+            Err(ProgressionError::NothingToRepeat { position: 2 })
+        );
+        assert!(matches!(
+            Progression::parse("| C Xm7 |"),
+            Err(ProgressionError::InvalidChord { position: 4, .. })
+        ));
+        assert_eq!(
+            Progression::parse("| C:x |"),
+            Err(ProgressionError::InvalidDuration {
+                position: 4,
+                duration: "x".to_string()
+            })
+        );
+        assert_eq!(
+            Progression::parse("| 3/5 C |"),
+            Err(ProgressionError::InvalidTimeSignature {
+                position: 2,
+                signature: "3/5".to_string()
+            })
+        );
+        assert_eq!(
+            Progression::parse("| C 3/4 |"),
+            Err(ProgressionError::UnexpectedToken {
+                position: 4,
+                token: "3/4".to_string()
+            })
+        );
+        assert_eq!(
+            Progression::parse("| C |: G | F |"),
+            Err(ProgressionError::UnclosedRepeat { position: 4 })
+        );
+        assert_eq!(
+            Progression::parse("| C:3 G:3 |"),
+            Err(ProgressionError::BarLength {
+                position: 2,
+                beats: 6.0,
+                expected: 4.0
+            })
+        );
+        assert_eq!(
+            Progression::parse(" | | "),
+            Err(ProgressionError::EmptyProgression)
+        );
+    }
+}
@@ -0,0 +1,138 @@
+extern crate rust_music_theory as theory;
+use theory::chord::{Chord, TriadQuality};
+use theory::harmony::{AugmentedSixth, NumeralKind, RomanNumeral};
+use theory::note::{KeySignature, NoteLetter, Pitch};
+use theory::scale::Mode;
+
+fn major(letter: NoteLetter, accidental: i8) -> KeySignature {
+    KeySignature::new(Pitch::new(letter, accidental))
+}
+
+fn minor(letter: NoteLetter, accidental: i8) -> KeySignature {
+    KeySignature::new_with_mode(Pitch::new(letter, accidental), Some(Mode::Aeolian))
+}
+
+fn analyze(symbols: &[&str], key: &KeySignature) -> Vec<String> {
+    symbols
+        .iter()
+        .map(|symbol| RomanNumeral::analyze(&Chord::parse(symbol).unwrap(), key).to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod roman_tests {
+    use super::*;
+
+    #[test]
+    fn test_diatonic_chords_in_major() {
+        let key = major(NoteLetter::C, 0);
+        assert_eq!(
+            analyze(
+                &["C", "Dm7", "Em", "Fmaj7", "G7", "Am", "Bm7b5", "Bdim"],
+                &key
+            ),
+            ["I", "ii7", "iii", "IVM7", "V7", "vi", "viiø7", "vii°"]
+        );
+        let numeral = RomanNumeral::analyze(&Chord::parse("G7").unwrap(), &key);
+        assert_eq!(numeral.kind, NumeralKind::Degree);
+        assert_eq!(numeral.degree, 5);
+        assert!(!numeral.chromatic);
+    }
+
+    #[test]
+    fn test_diatonic_chords_in_minor_use_the_raised_leading_tone() {
+        let key = minor(NoteLetter::C, 0);
+        assert_eq!(
+            analyze(
+                &["Cm", "Ddim", "Eb", "Fm", "G7", "Ab", "Bb", "Bdim7", "Eb+", "F"],
+                &key
+            ),
+            ["i", "ii°", "III", "iv", "V7", "VI", "VII", "vii°7", "III+", "IV"]
+        );
+        // The raised sixth is a tone of the key but keeps its accidental in the numeral.
+        let numeral = RomanNumeral::analyze(&Chord::parse("Adim").unwrap(), &key);
+        assert_eq!(numeral.to_string(), "#vi°");
+        assert!(!numeral.chromatic);
+    }
+
+    #[test]
+    fn test_inversion_figures() {
+        let key = major(NoteLetter::G, 0);
+        assert_eq!(
+            analyze(&["G/B", "G/D", "D7/F#", "D7/A", "D7/C", "Am7/C"], &key),
+            ["I6", "I64", "V65", "V43", "V42", "ii65"]
+        );
+        let numeral = RomanNumeral::analyze(&Chord::parse("D7/C").unwrap(), &key);
+        assert_eq!(numeral.inversion, 3);
+        assert_eq!(numeral.figure(), "42");
+    }
+
+    #[test]
+    fn test_secondary_dominants_and_leading_tone_chords() {
+        let key = major(NoteLetter::C, 0);
+        assert_eq!(
+            analyze(&["D7", "D/F#", "E7", "A7", "C7", "F#dim7", "C#m7b5"], &key),
+            [
+                "V7/V",
+                "V6/V",
+                "V7/vi",
+                "V7/ii",
+                "V7/IV",
+                "vii°7/V",
+                "viiø7/ii"
+            ]
+        );
+        let numeral = RomanNumeral::analyze(&Chord::parse("E7").unwrap(), &key);
+        assert!(numeral.chromatic);
+        assert_eq!(
+            numeral.secondary.as_deref(),
+            Some(&RomanNumeral::new(6, TriadQuality::Minor))
+        );
+
+        let minor_key = minor(NoteLetter::A, 0);
+        assert_eq!(
+            analyze(&["B7", "C7", "A7", "G7", "D7"], &minor_key),
+            ["V7/V", "V7/VI", "V7/iv", "VII7", "IV7"]
+        );
+    }
+
+    #[test]
+    fn test_borrowed_chords() {
+        let key = major(NoteLetter::C, 0);
+        assert_eq!(
+            analyze(&["Ab", "Bb", "Fm", "Eb", "Dm7b5"], &key),
+            ["bVI", "bVII", "iv", "bIII", "iiø7"]
+        );
+        let numeral = RomanNumeral::analyze(&Chord::parse("Ab").unwrap(), &key);
+        assert_eq!((numeral.degree, numeral.accidental), (6, -1));
+        assert!(numeral.chromatic);
+        assert!(numeral.secondary.is_none());
+    }
+
+    #[test]
+    fn test_neapolitan_and_augmented_sixths() {
+        let key = minor(NoteLetter::A, 0);
+        assert_eq!(analyze(&["Bb/D", "Bb"], &key), ["N6", "N"]);
+
+        let key = major(NoteLetter::C, 0);
+        assert_eq!(
+            analyze(&["Ab7no5", "D7b5/Ab", "Ab7"], &key),
+            ["It+6", "Fr+6", "Ger+6"]
+        );
+        let french = RomanNumeral::analyze(&Chord::parse("D7b5/Ab").unwrap(), &key);
+        assert_eq!(
+            french.kind,
+            NumeralKind::AugmentedSixth(AugmentedSixth::French)
+        );
+        assert_eq!((french.degree, french.accidental), (6, -1));
+    }
+
+    #[test]
+    fn test_modal_keys_and_suspensions() {
+        let dorian = KeySignature::new_with_mode(Pitch::new(NoteLetter::D, 0), Some(Mode::Dorian));
+        assert_eq!(
+            analyze(&["Dm7", "G7", "C", "Gsus4", "G9"], &dorian),
+            ["i7", "IV7", "VII", "IVsus4", "IV9"]
+        );
+    }
+}
@@ -0,0 +1,191 @@
+extern crate rust_music_theory as theory;
+use theory::chord::Chord;
+use theory::harmony::{HarmonyError, RomanNumeral};
+use theory::note::{KeySignature, NoteLetter, Notes, Pitch};
+use theory::scale::Mode;
+
+fn major(letter: NoteLetter, accidental: i8) -> KeySignature {
+    KeySignature::new(Pitch::new(letter, accidental))
+}
+
+fn minor(letter: NoteLetter, accidental: i8) -> KeySignature {
+    KeySignature::new_with_mode(Pitch::new(letter, accidental), Some(Mode::Aeolian))
+}
+
+fn chord(numeral: &str, key: &KeySignature) -> Chord {
+    RomanNumeral::parse(numeral).unwrap().to_chord(key).unwrap()
+}
+
+fn symbols(numerals: &[&str], key: &KeySignature) -> Vec<String> {
+    numerals
+        .iter()
+        .map(|numeral| chord(numeral, key).canonical_symbol())
+        .collect()
+}
+
+fn pitches(chord: &Chord) -> Vec<String> {
+    chord
+        .notes()
+        .iter()
+        .map(|note| note.pitch.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod roman_chord_tests {
+    use super::*;
+
+    #[test]
+    fn test_diatonic_numerals_in_major() {
+        let key = major(NoteLetter::E, -1);
+        assert_eq!(
+            symbols(
+                &["I", "ii7", "iii", "IVM7", "V7", "vi", "viiø7", "vii°"],
+                &key
+            ),
+            ["Eb", "Fm7", "Gm", "Abmaj7", "Bb7", "Cm", "Dm7b5", "Ddim"]
+        );
+    }
+
+    #[test]
+    fn test_minor_keys_raise_the_leading_tone_but_not_the_subtonic() {
+        let key = minor(NoteLetter::A, 0);
+        assert_eq!(
+            symbols(&["i", "ii°", "III", "iv", "V7", "VI", "VII", "vii°7"], &key),
+            ["Am", "Bdim", "C", "Dm", "E7", "F", "G", "G#dim7"]
+        );
+        assert_eq!(symbols(&["viiø7", "vii"], &key), ["G#m7b5", "G#m"]);
+    }
+
+    #[test]
+    fn test_figures_set_the_inversion() {
+        let key = major(NoteLetter::C, 0);
+        assert_eq!(
+            symbols(&["iv6", "I64", "V65", "V43", "V42", "V6/5", "ii4/3"], &key),
+            ["Fm/Ab", "C/G", "G7/B", "G7/D", "G7/F", "G7/B", "Dm7/A"]
+        );
+        let dominant = chord("V65", &key);
+        assert_eq!(dominant.inversion(), 1);
+        assert_eq!(pitches(&dominant), ["B", "D", "F", "G"]);
+    }
+
+    #[test]
+    fn test_applied_numerals_use_the_key_of_their_target() {
+        let key = major(NoteLetter::C, 0);
+        assert_eq!(
+            symbols(
+                &["V/vi", "V7/V", "vii°7/V", "V65/ii", "V/V/V", "viiø7/IV"],
+                &key
+            ),
+            ["E", "D7", "F#dim7", "A7/C#", "A", "Em7b5"]
+        );
+        assert_eq!(
+            symbols(&["V7/iv", "V/III"], &minor(NoteLetter::C, 0)),
+            ["C7", "Bb"]
+        );
+    }
+
+    #[test]
+    fn test_chromatic_numerals() {
+        let key = major(NoteLetter::C, 0);
+        assert_eq!(
+            symbols(&["bVII", "bVI", "bIII", "iv", "#iv°7", "N6"], &key),
+            ["Bb", "Ab", "Eb", "Fm", "F#dim7", "Db/F"]
+        );
+        assert_eq!(symbols(&["N6"], &minor(NoteLetter::A, 0)), ["Bb/D"]);
+    }
+
+    #[test]
+    fn test_augmented_sixths() {
+        let key = major(NoteLetter::C, 0);
+        assert_eq!(pitches(&chord("It+6", &key)), ["Ab", "C", "F#"]);
+        assert_eq!(pitches(&chord("Fr+6", &key)), ["Ab", "C", "D", "F#"]);
+        assert_eq!(pitches(&chord("Ger+6", &key)), ["Ab", "C", "Eb", "F#"]);
+        assert_eq!(
+            pitches(&chord("Ger6", &minor(NoteLetter::A, 0))),
+            ["F", "A", "C", "D#"]
+        );
+    }
+
+    #[test]
+    fn test_ascii_and_alternative_spellings() {
+        let key = major(NoteLetter::C, 0);
+        for (ascii, symbol) in [
+            ("viio7", "vii°7"),
+            ("viih7", "viiø7"),
+            ("viih", "viiø7"),
+            ("IVmaj7", "IVM7"),
+            ("III+", "III+"),
+            ("♭VI", "bVI"),
+            ("V2", "V42"),
+        ]
+        .iter()
+        {
+            assert_eq!(
+                RomanNumeral::parse(ascii).unwrap().to_string(),
+                *symbol,
+                "{}",
+                ascii
+            );
+        }
+        assert_eq!(
+            chord("viio7", &key).canonical_symbol(),
+            chord("vii°7", &key).canonical_symbol()
+        );
+    }
+
+    #[test]
+    fn test_parsing_round_trips_analysis() {
+        let key = major(NoteLetter::D, 0);
+        for symbol in [
+            "D", "Em7/D", "F#m/A", "Gmaj7", "A7/G", "Bm", "C#m7b5", "E7/G#", "G#dim7", "C", "Eb/G",
+            "Bb", "Gm6", "Asus4", "A9",
+        ]
+        .iter()
+        {
+            let chord = Chord::parse(symbol).unwrap();
+            let numeral = RomanNumeral::analyze(&chord, &key);
+            let rebuilt = numeral.to_string().parse::<RomanNumeral>().unwrap();
+            assert_eq!(rebuilt.to_string(), numeral.to_string(), "{}", symbol);
+            assert_eq!(
+                pitches(&rebuilt.to_chord(&key).unwrap()),
+                pitches(&chord),
+                "{}",
+                symbol
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(RomanNumeral::parse("  "), Err(HarmonyError::EmptyNumeral));
+        assert_eq!(
+            RomanNumeral::parse("VX"),
+            Err(HarmonyError::UnexpectedToken {
+                position: 1,
+                token: "X".to_string()
+            })
+        );
+        assert_eq!(
+            RomanNumeral::parse("Vi"),
+            Err(HarmonyError::UnexpectedToken {
+                position: 0,
+                token: "V".to_string()
+            })
+        );
+        assert_eq!(
+            RomanNumeral::parse(" IM"),
+            Err(HarmonyError::UnexpectedToken {
+                position: 2,
+                token: "M".to_string()
+            })
+        );
+        assert_eq!(
+            RomanNumeral::parse("V7/"),
+            Err(HarmonyError::UnexpectedToken {
+                position: 3,
+                token: String::new()
+            })
+        );
+    }
+}
@@ -0,0 +1,189 @@
+extern crate rust_music_theory as theory;
+use std::time::{Duration, Instant};
+use theory::chord::Chord;
+use theory::harmony::{VoiceLeader, VoiceLeadingError, MAX_VOICES};
+use theory::note::{Note, NoteLetter, Notes, Pitch};
+
+fn chords(symbols: &[&str]) -> Vec<Chord> {
+    symbols
+        .iter()
+        .map(|symbol| Chord::parse(symbol).unwrap())
+        .collect()
+}
+
+fn names(notes: &[Note]) -> Vec<String> {
+    notes
+        .iter()
+        .map(|note| format!("{}{}", note.pitch, note.octave))
+        .collect()
+}
+
+fn parallels(voices: &[Vec<Note>]) -> usize {
+    let mut count = 0;
+    for step in 1..voices[0].len() {
+        for lower in 0..voices.len() {
+            for upper in lower + 1..voices.len() {
+                let interval = |at: usize| {
+                    voices[upper][at].midi_pitch() as i16 - voices[lower][at].midi_pitch() as i16
+                };
+                let (before, after) = (interval(step - 1) % 12, interval(step) % 12);
+                let moved = voices[lower][step] != voices[lower][step - 1];
+                if before == after && matches!(before, 0 | 7) && moved {
+                    count += 1;
+                }
+            }
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod voice_leading_tests {
+    use super::*;
+
+    #[test]
+    fn test_ii_v_i_moves_by_step() {
+        let leading = VoiceLeader::new(4)
+            .lead(&chords(&["Dm7", "G7", "Cmaj7"]))
+            .unwrap();
+        let voiced: Vec<Vec<String>> = leading.chords().iter().map(|chord| names(chord)).collect();
+        assert_eq!(
+            voiced,
+            vec![
+                vec!["D3", "C4", "F4", "A4"],
+                vec!["G3", "D4", "F4", "B4"],
+                vec!["C4", "E4", "G4", "B4"]
+            ]
+        );
+        assert_eq!(leading.total_motion(), 18);
+    }
+
+    #[test]
+    fn test_fewer_voices_drop_the_fifth() {
+        let leading = VoiceLeader::new(3)
+            .lead(&chords(&["Dm7", "G7", "Cmaj7"]))
+            .unwrap();
+        assert_eq!(names(&leading.chord(0).unwrap()), vec!["D3", "F4", "C5"]);
+        assert_eq!(names(&leading.chord(1).unwrap()), vec!["G3", "F4", "B4"]);
+        assert_eq!(names(&leading.chord(2).unwrap()), vec!["C4", "E4", "B4"]);
+        assert_eq!(leading.chord(3), None);
+    }
+
+    #[test]
+    fn test_bass_follows_inversions_and_slash_basses() {
+        let leading = VoiceLeader::new(4)
+            .lead(&chords(&["C/E", "F", "G7/B", "C"]))
+            .unwrap();
+        let basses: Vec<String> = leading.voices()[0]
+            .iter()
+            .map(|note| note.pitch.to_string())
+            .collect();
+        assert_eq!(basses, vec!["E", "F", "B", "C"]);
+    }
+
+    #[test]
+    fn test_parallel_fifths_and_octaves_are_avoided() {
+        let progression = chords(&["C", "D", "E", "F"]);
+        let strict = VoiceLeader::new(4).lead(&progression).unwrap();
+        assert_eq!(parallels(strict.voices()), 0);
+
+        let free = VoiceLeader::new(4)
+            .avoid_parallels(false)
+            .lead(&progression)
+            .unwrap();
+        assert!(parallels(free.voices()) > 0);
+        assert!(free.total_motion() < strict.total_motion());
+    }
+
+    #[test]
+    fn test_common_tones_are_held() {
+        let leading = VoiceLeader::new(4).lead(&chords(&["C", "Am"])).unwrap();
+        let first = names(&leading.chord(0).unwrap());
+        let second = names(&leading.chord(1).unwrap());
+        let held = first.iter().filter(|note| second.contains(note)).count();
+        assert!(held >= 2, "{:?} -> {:?}", first, second);
+    }
+
+    #[test]
+    fn test_voices_stay_in_range() {
+        let lowest = Note::new(Pitch::new(NoteLetter::G, 0), 3);
+        let highest = Note::new(Pitch::new(NoteLetter::G, 0), 5);
+        let leading = VoiceLeader::new(4)
+            .range(lowest.clone(), highest.clone())
+            .lead(&chords(&["C", "Dm7", "G7sus4", "G7", "C"]))
+            .unwrap();
+        for note in leading.voices().iter().flatten() {
+            assert!(note.midi_pitch() >= lowest.midi_pitch());
+            assert!(note.midi_pitch() <= highest.midi_pitch());
+        }
+    }
+
+    #[test]
+    fn test_voices_are_notes() {
+        let leading = VoiceLeader::new(4).lead(&chords(&["C", "G7"])).unwrap();
+        let top = leading.voices()[3].clone();
+        assert_eq!(top.notes(), top);
+        assert_eq!(top[0].notes(), vec![top[0].clone()]);
+        assert_eq!(leading.chords()[1].notes().len(), 4);
+    }
+
+    #[test]
+    fn test_errors() {
+        let progression = chords(&["C", "G"]);
+        assert_eq!(
+            VoiceLeader::new(0).lead(&progression),
+            Err(VoiceLeadingError::NoVoices)
+        );
+        let high = Note::new(Pitch::new(NoteLetter::C, 0), 5);
+        let low = Note::new(Pitch::new(NoteLetter::C, 0), 4);
+        assert_eq!(
+            VoiceLeader::new(4)
+                .range(high.clone(), low.clone())
+                .lead(&progression),
+            Err(VoiceLeadingError::EmptyRange {
+                lowest: high,
+                highest: low.clone()
+            })
+        );
+        let narrow = Note::new(Pitch::new(NoteLetter::A, 0), 4);
+        assert_eq!(
+            VoiceLeader::new(3)
+                .range(low.clone(), narrow)
+                .lead(&progression),
+            Err(VoiceLeadingError::NoVoicing { index: 1 })
+        );
+        assert_eq!(
+            VoiceLeader::new(MAX_VOICES + 1).lead(&progression),
+            Err(VoiceLeadingError::TooManyVoices {
+                voices: MAX_VOICES + 1,
+                max: MAX_VOICES
+            })
+        );
+        let d4 = Note::new(Pitch::new(NoteLetter::D, 0), 4);
+        assert_eq!(
+            VoiceLeader::new(4).range(low, d4).lead(&progression),
+            Err(VoiceLeadingError::TooManyVoices { voices: 4, max: 3 })
+        );
+    }
+
+    #[test]
+    fn test_many_voices_over_a_wide_range_are_led_quickly() {
+        let lowest = Note::new(Pitch::new(NoteLetter::C, 0), 2);
+        let highest = Note::new(Pitch::new(NoteLetter::C, 0), 7);
+        let progression = chords(&["Dm7", "G7", "Cmaj7", "A7"]);
+        let start = Instant::now();
+        for voices in 6..=MAX_VOICES {
+            let leading = VoiceLeader::new(voices)
+                .range(lowest.clone(), highest.clone())
+                .lead(&progression)
+                .unwrap();
+            assert_eq!(leading.voices().len(), voices);
+            assert_eq!(parallels(leading.voices()), 0);
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(20),
+            "{:?}",
+            start.elapsed()
+        );
+    }
+}
@@ -1,6 +1,7 @@
 extern crate rust_music_theory as theory;
 use theory::interval::Interval;
 use theory::note::{Note, Pitch, PitchSymbol::*};
+use theory::scale::Direction;
 
 #[cfg(test)]
 mod test_interval {
@@ -102,12 +103,88 @@ mod test_interval {
     }
 
     #[test]
-    fn test_from_semitone_error() {
-        // Test that values > 12 return an error
-        assert!(Interval::from_semitone(13).is_err());
-        assert!(Interval::from_semitone(14).is_err());
-        assert!(Interval::from_semitone(100).is_err());
-        assert!(Interval::from_semitone(255).is_err());
+    fn test_from_semitone_compound() {
+        let cases = [
+            (13, "m9"),
+            (14, "M9"),
+            (15, "m10"),
+            (16, "M10"),
+            (17, "P11"),
+            (18, "d12"),
+            (19, "P12"),
+            (21, "M13"),
+            (24, "P15"),
+            (26, "M16"),
+            (36, "P22"),
+        ];
+        for (semitones, expected) in cases {
+            let interval = Interval::from_semitone(semitones).unwrap();
+            assert_eq!(interval.semitone_count, semitones);
+            assert_eq!(interval.to_string(), expected);
+            assert!(interval.is_compound());
+            assert_eq!(interval.step, None);
+        }
+        assert!(!Interval::from_semitone(12).unwrap().is_compound());
+        assert!(Interval::from_semitone(255).is_ok());
+    }
+
+    #[test]
+    fn test_compound_structure() {
+        use theory::interval::{Number, Quality};
+
+        let ninth = Interval::from_semitone(14).unwrap();
+        assert_eq!(ninth.number, Number::Second);
+        assert_eq!(ninth.quality, Quality::Major);
+        assert_eq!(ninth.octaves, 1);
+        assert_eq!(ninth.ordinal(), 9);
+        assert_eq!(ninth.simple(), Interval::from_semitone(2).unwrap());
+
+        let double_octave = Interval::from_semitone(24).unwrap();
+        assert_eq!(double_octave.number, Number::Octave);
+        assert_eq!(double_octave.octaves, 1);
+        assert_eq!(double_octave.ordinal(), 15);
+
+        let major_second = Interval::from_semitone(2).unwrap();
+        assert_eq!(major_second.compound(1).unwrap(), ninth);
+        assert_eq!(major_second.compound(0).unwrap(), major_second);
+        assert_eq!(
+            Interval::from_semitone(0).unwrap().compound(1).unwrap(),
+            Interval::from_semitone(12).unwrap()
+        );
+        let fourth = Interval::from_semitone(5).unwrap();
+        assert_eq!(fourth.compound(2).unwrap().to_string(), "P18");
+        assert!(Interval::from_semitone(7).unwrap().compound(21).is_err());
+    }
+
+    #[test]
+    fn test_invert_compound() {
+        let cases = [(14, "m7"), (13, "M7"), (17, "P5"), (24, "P1"), (18, "T")];
+        for (semitones, expected) in cases {
+            let interval = Interval::from_semitone(semitones).unwrap();
+            assert_eq!(Interval::invert(&interval).unwrap().to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_compound_second_note_from() {
+        let cases = [
+            ("C", 4, 14, "D", 5),
+            ("B", 3, 13, "C", 5),
+            ("Eb", 4, 17, "Ab", 5),
+            ("G", 2, 24, "G", 4),
+            ("A", 4, 21, "Fs", 6),
+        ];
+        for (first, first_octave, semitones, expected, expected_octave) in cases {
+            let interval = Interval::from_semitone(semitones).unwrap();
+            let next_note =
+                interval.second_note_from(Note::new(Pitch::from_str(first).unwrap(), first_octave));
+            assert_eq!(next_note.pitch, Pitch::from_str(expected).unwrap());
+            assert_eq!(next_note.octave, expected_octave);
+
+            let back = interval.second_note_down_from(next_note);
+            assert_eq!(back.pitch, Pitch::from_str(first).unwrap());
+            assert_eq!(back.octave, first_octave);
+        }
     }
 
     #[test]
@@ -126,9 +203,10 @@ mod test_interval {
         let empty: Vec<u8> = vec![];
         assert!(Interval::from_semitones(&empty).is_err());
 
-        // Invalid semitone (> 12) should return error
-        let invalid_semitones = vec![0, 2, 4, 13];
-        assert!(Interval::from_semitones(&invalid_semitones).is_err());
+        // Semitones beyond the octave produce compound intervals
+        let compound_semitones = vec![0, 2, 4, 14];
+        let intervals = Interval::from_semitones(&compound_semitones).unwrap();
+        assert_eq!(intervals[3].to_string(), "M9");
     }
 
     #[test]
@@ -289,6 +367,227 @@ mod test_interval {
         assert_eq!(format!("{}", Number::Octave), "8");
     }
 
+    #[test]
+    fn test_between_spells_by_letter() {
+        let cases = [
+            ("Cs", 4, "Bb", 4, "d7"),
+            ("Cs", 4, "As", 4, "M6"),
+            ("C", 4, "Fs", 4, "T"),
+            ("C", 4, "Gb", 4, "T"),
+            ("E", 4, "As", 4, "T"),
+            ("C", 4, "Gs", 4, "A5"),
+            ("Eb", 4, "Ab", 4, "P4"),
+            ("B", 3, "C", 4, "m2"),
+            ("C", 4, "C", 5, "P8"),
+            ("C", 4, "Cb", 5, "d8"),
+            ("C", 4, "D", 5, "M9"),
+            ("C", 3, "F", 4, "P11"),
+            ("C", 4, "A", 5, "M13"),
+            ("C", 4, "C", 6, "P15"),
+            ("C", 4, "Cs", 4, "A1"),
+            ("C", 4, "Fx", 4, "AA4"),
+            ("C", 4, "Gbb", 4, "dd5"),
+            ("Cs", 4, "Ebb", 4, "dd3"),
+            ("F", 4, "Bs", 4, "AA4"),
+        ];
+        for (first, first_octave, second, second_octave, expected) in cases {
+            let first = Note::new(Pitch::from_str(first).unwrap(), first_octave);
+            let second = Note::new(Pitch::from_str(second).unwrap(), second_octave);
+            let (interval, direction) = Interval::between(&first, &second).unwrap();
+            assert_eq!(interval.to_string(), expected, "{} to {}", first, second);
+            assert_eq!(direction, Direction::Ascending);
+            assert_eq!(interval.second_note_from(first.clone()), second);
+        }
+    }
+
+    #[test]
+    fn test_between_direction_and_compound_quality() {
+        use theory::interval::{Number, Quality};
+
+        let c5 = Note::new(Pitch::from(C), 5);
+        let e3 = Note::new(Pitch::from(E), 3);
+        let (interval, direction) = Interval::between(&c5, &e3).unwrap();
+        assert_eq!(direction, Direction::Descending);
+        assert_eq!(interval.to_string(), "m13");
+        assert_eq!(interval.number, Number::Sixth);
+        assert_eq!(interval.quality, Quality::Minor);
+        assert_eq!(interval.octaves, 1);
+        assert_eq!(interval.semitone_count, 20);
+        assert_eq!(interval.second_note_down_from(c5), e3);
+
+        // Lowering a pitch on the same letter is a descending augmented unison.
+        let c4 = Note::new(Pitch::from(C), 4);
+        let c_flat4 = Note::new(Pitch::from(Cb), 4);
+        let (interval, direction) = Interval::between(&c4, &c_flat4).unwrap();
+        assert_eq!(interval.to_string(), "A1");
+        assert_eq!(direction, Direction::Descending);
+
+        let (unison, direction) = Interval::between(&c4, &c4).unwrap();
+        assert_eq!(unison, Interval::default());
+        assert_eq!(direction, Direction::Ascending);
+    }
+
+    #[test]
+    fn test_between_rejects_unspellable_qualities() {
+        let c4 = Note::new(Pitch::from(C), 4);
+        let triple_sharp = Note::new(Pitch::from_str("Gx#").unwrap(), 4);
+        assert!(Interval::between(&c4, &triple_sharp).is_err());
+
+        // B#4 lies above Cb5 although its letter is lower.
+        let b_sharp = Note::new(Pitch::from(Bs), 4);
+        let c_flat = Note::new(Pitch::from(Cb), 5);
+        assert!(Interval::between(&b_sharp, &c_flat).is_err());
+    }
+
+    #[test]
+    fn test_doubly_altered_quality_display_and_inversion() {
+        use theory::interval::Quality;
+
+        assert_eq!(format!("{}", Quality::DoublyAugmented), "AA");
+        assert_eq!(format!("{}", Quality::DoublyDiminished), "dd");
+
+        let c4 = Note::new(Pitch::from(C), 4);
+        let f_double_sharp = Note::new(Pitch::from_str("Fx").unwrap(), 4);
+        let (interval, _) = Interval::between(&c4, &f_double_sharp).unwrap();
+        assert_eq!(Interval::invert(&interval).unwrap().to_string(), "dd5");
+    }
+
+    #[test]
+    fn test_from_number_and_quality() {
+        use theory::interval::{Number, Quality};
+
+        let cases = [
+            (Number::Second, Quality::Augmented, 3, "A2"),
+            (Number::Fourth, Quality::DoublyDiminished, 3, "dd4"),
+            (Number::Fourth, Quality::Augmented, 6, "T"),
+            (Number::Third, Quality::DoublyAugmented, 6, "AA3"),
+            (Number::Seventh, Quality::Diminished, 9, "d7"),
+            (Number::Second, Quality::Diminished, 0, "d2"),
+            (Number::Octave, Quality::Augmented, 13, "A8"),
+            (Number::Unison, Quality::Augmented, 1, "A1"),
+        ];
+        for (number, quality, semitones, expected) in cases {
+            let interval = Interval::from_number_and_quality(number, quality).unwrap();
+            assert_eq!(interval.semitone_count, semitones);
+            assert_eq!(interval.number, number);
+            assert_eq!(interval.quality, quality);
+            assert_eq!(interval.to_string(), expected);
+        }
+
+        // The spelled augmented second lands on a new letter.
+        let augmented_second =
+            Interval::from_number_and_quality(Number::Second, Quality::Augmented).unwrap();
+        let b = augmented_second.second_note_from(Note::new(Pitch::from(Ab), 4));
+        assert_eq!(b, Note::new(Pitch::from(B), 4));
+
+        for (number, quality) in [
+            (Number::Fifth, Quality::Major),
+            (Number::Third, Quality::Perfect),
+            (Number::Unison, Quality::Diminished),
+            (Number::Second, Quality::DoublyDiminished),
+        ] {
+            assert!(Interval::from_number_and_quality(number, quality).is_err());
+        }
+    }
+
+    #[test]
+    fn test_enharmonic_spellings() {
+        let names = |semitones| {
+            Interval::enharmonic_spellings(semitones)
+                .iter()
+                .map(|interval| {
+                    assert_eq!(interval.semitone_count, semitones);
+                    format!("{}{}", interval.quality, interval.ordinal())
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(0), ["P1", "d2"]);
+        assert_eq!(names(3), ["A2", "m3", "dd4"]);
+        assert_eq!(names(6), ["AA3", "A4", "d5", "dd6"]);
+        assert_eq!(names(12), ["A7", "P8", "d9"]);
+        assert_eq!(names(14), ["AA8", "M9", "d10"]);
+    }
+
+    #[test]
+    fn test_parse_short_and_long_names() {
+        let cases = [
+            ("m3", 3, "m3"),
+            ("A4", 6, "T"),
+            ("P5", 7, "P5"),
+            ("d7", 9, "d7"),
+            ("M9", 14, "M9"),
+            ("P11", 17, "P11"),
+            ("AA4", 7, "AA4"),
+            ("dd5", 5, "dd5"),
+            ("minor sixth", 8, "m6"),
+            ("Perfect Fifth", 7, "P5"),
+            ("augmented-second", 3, "A2"),
+            ("doubly diminished fourth", 3, "dd4"),
+            ("major 9th", 14, "M9"),
+            ("maj7", 11, "M7"),
+            ("min3", 3, "m3"),
+            ("dim 5", 6, "T"),
+            ("octave", 12, "P8"),
+            ("fifteenth", 24, "P15"),
+            ("unison", 0, "P1"),
+            ("tritone", 6, "T"),
+            ("T", 6, "T"),
+        ];
+        for (name, semitones, display) in cases {
+            let interval: Interval = name.parse().unwrap();
+            assert_eq!(interval.semitone_count, semitones, "{}", name);
+            assert_eq!(interval.to_string(), display, "{}", name);
+        }
+        assert_eq!(Interval::parse("m3").unwrap().quality, theory::interval::Quality::Minor);
+        assert_eq!(Interval::parse("M3").unwrap().quality, theory::interval::Quality::Major);
+    }
+
+    #[test]
+    fn test_parse_round_trips_display() {
+        for semitones in 0..=36 {
+            let interval = Interval::from_semitone(semitones).unwrap();
+            assert_eq!(interval.to_string().parse::<Interval>().unwrap(), interval);
+        }
+        for semitones in 0..=24 {
+            for interval in Interval::enharmonic_spellings(semitones) {
+                if interval.to_string() != "T" {
+                    assert_eq!(interval.to_string().parse::<Interval>().unwrap(), interval);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use theory::interval::IntervalError;
+
+        assert!(matches!(Interval::parse("  "), Err(IntervalError::EmptyName)));
+        assert!(matches!(
+            Interval::parse("P3"),
+            Err(IntervalError::InvalidQuality { .. })
+        ));
+        assert!(matches!(
+            Interval::parse("d1"),
+            Err(IntervalError::InvalidQuality { .. })
+        ));
+        assert!(matches!(
+            Interval::parse("huge third"),
+            Err(IntervalError::UnknownQuality(_))
+        ));
+        assert!(matches!(
+            Interval::parse("sixth"),
+            Err(IntervalError::UnknownQuality(_))
+        ));
+        assert!(matches!(
+            Interval::parse("major zeroth"),
+            Err(IntervalError::UnknownNumber(_))
+        ));
+        assert!(matches!(Interval::parse("X3"), Err(IntervalError::UnknownQuality(_))));
+        assert!(Interval::parse("M0").is_err());
+        assert!(Interval::parse("P999").is_err());
+    }
+
     #[test]
     fn test_interval_step_display() {
         use theory::interval::Step;
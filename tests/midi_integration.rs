@@ -167,3 +167,277 @@ fn lead_sheet_theoretical_accidentals_export_at_the_correct_midi_octaves() {
     assert_eq!(exported_pitches("Cbmaj7"), [59, 63, 66, 70]);
     assert_eq!(exported_pitches("B#maj7"), [72, 76, 79, 83]);
 }
+
+#[test]
+fn progression_export_keeps_chord_timing() {
+    use midly::{MidiMessage, Smf, TrackEventKind};
+    use rust_music_theory::harmony::Progression;
+
+    let progression = Progression::parse("| Dm7:1 G7:2 C:1 | Cmaj7 |").unwrap();
+    let mut builder = MidiBuilder::new();
+    builder.add_progression(&progression, Velocity::new(100).unwrap());
+    assert_eq!(builder.cursor(), 3840);
+
+    let bytes = MidiFile::new()
+        .track(builder, Channel::new(0).unwrap())
+        .to_bytes();
+    let midi = Smf::parse(&bytes).unwrap();
+    let mut onsets = Vec::new();
+    for track in &midi.tracks {
+        let mut tick = 0;
+        for event in track {
+            tick += event.delta.as_int();
+            if let TrackEventKind::Midi { message: MidiMessage::NoteOn { vel, .. }, .. } = event.kind {
+                if vel.as_int() > 0 && !onsets.contains(&tick) {
+                    onsets.push(tick);
+                }
+            }
+        }
+    }
+    assert_eq!(onsets, vec![0, 480, 1440, 1920]);
+}
+
+#[test]
+fn tone_row_exports_as_a_melody() {
+    use midly::{MidiMessage, Smf, TrackEventKind};
+    use rust_music_theory::note::{Notes, Spelling};
+    use rust_music_theory::pitch_class_set::ToneRow;
+
+    let row = ToneRow::new(&[7, 10, 2, 6, 9, 0, 4, 8, 11, 1, 3, 5])
+        .unwrap()
+        .spelling(Spelling::Flats)
+        .octave(3);
+    let mut builder = MidiBuilder::new();
+    for note in row.notes() {
+        builder.add(&note, Duration::Eighth, Velocity::new(90).unwrap());
+    }
+    let bytes = MidiFile::new()
+        .track(builder, Channel::new(0).unwrap())
+        .to_bytes();
+    let midi = Smf::parse(&bytes).unwrap();
+    let pitches: Vec<u8> = midi
+        .tracks
+        .iter()
+        .flat_map(|track| track.iter())
+        .filter_map(|event| match event.kind {
+            TrackEventKind::Midi {
+                message: MidiMessage::NoteOn { key, vel },
+                ..
+            } if vel.as_int() > 0 => Some(key.as_int()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(pitches, [55, 58, 50, 54, 57, 48, 52, 56, 59, 49, 51, 53]);
+}
+
+#[test]
+fn tuned_notes_export_pitch_bends() {
+    use midly::{MidiMessage, Smf, TrackEventKind};
+    use rust_music_theory::note::Note;
+    use rust_music_theory::tuning::TuningSystem;
+
+    let mut builder = MidiBuilder::new();
+    builder.tuning(TuningSystem::quarter_comma_meantone().reference("C4".parse().unwrap(), 261.63));
+    for name in ["C4", "E4", "G4"] {
+        let note: Note = name.parse().unwrap();
+        builder.add(&note, Duration::Quarter, Velocity::new(90).unwrap());
+    }
+    let bytes = MidiFile::new()
+        .track(builder, Channel::new(0).unwrap())
+        .to_bytes();
+    let midi = Smf::parse(&bytes).unwrap();
+    let messages: Vec<(u8, i16)> = midi
+        .tracks
+        .iter()
+        .flat_map(|track| track.iter())
+        .filter_map(|event| match event.kind {
+            TrackEventKind::Midi {
+                message: MidiMessage::NoteOn { key, vel },
+                ..
+            } if vel.as_int() > 0 => Some((key.as_int(), 0)),
+            TrackEventKind::Midi {
+                message: MidiMessage::PitchBend { bend },
+                ..
+            } => Some((0, bend.as_int())),
+            _ => None,
+        })
+        .collect();
+    // C4 at 261.63 Hz is a hair sharp of equal temperament; the pure third E4 is 13.7 cents
+    // flat of its key and the meantone fifth G4 3.4 cents flat.
+    assert_eq!(messages.len(), 6);
+    assert_eq!(messages[1].0, 60);
+    assert_eq!(messages[3].0, 64);
+    assert_eq!(messages[5].0, 67);
+    assert!((-570..=-550).contains(&(messages[2].1 - messages[0].1)));
+    assert!((-150..=-130).contains(&(messages[4].1 - messages[0].1)));
+}
+
+#[test]
+fn tuned_chords_export_a_channel_per_note() {
+    use midly::{MidiMessage, Smf, TrackEventKind};
+    use rust_music_theory::tuning::TuningSystem;
+
+    let mut builder = MidiBuilder::new();
+    builder.tuning(TuningSystem::just_intonation(Pitch::from(C)).reference("C4".parse().unwrap(), 261.6256));
+    let chord = Chord::new(Pitch::from(C), Quality::Major, Number::Triad);
+    builder.add(&chord, Duration::Whole, Velocity::new(90).unwrap());
+    let bytes = MidiFile::new()
+        .track(builder, Channel::new(4).unwrap())
+        .to_bytes();
+    let midi = Smf::parse(&bytes).unwrap();
+    let mut bends = [0i16; 16];
+    let mut notes: Vec<(u8, u8, i16)> = Vec::new();
+    for event in midi.tracks.iter().flat_map(|track| track.iter()) {
+        match event.kind {
+            TrackEventKind::Midi {
+                channel,
+                message: MidiMessage::PitchBend { bend },
+            } => bends[channel.as_int() as usize] = bend.as_int(),
+            TrackEventKind::Midi {
+                channel,
+                message: MidiMessage::NoteOn { key, vel },
+            } if vel.as_int() > 0 => {
+                notes.push((channel.as_int(), key.as_int(), bends[channel.as_int() as usize]))
+            }
+            _ => {}
+        }
+    }
+    // Each chord tone sounds on its own channel from the track's, with its own bend: the
+    // root on its key, the pure third 13.7 cents flat and the pure fifth 2 cents sharp.
+    assert_eq!(notes, vec![(4, 60, 0), (5, 64, -561), (6, 67, 80)]);
+}
+
+#[test]
+fn progression_renders_to_wav_headless() {
+    use rust_music_theory::harmony::Progression;
+    use rust_music_theory::midi::{Envelope, Instrument, SampleFormat, Synth, Waveform};
+
+    let progression = Progression::parse("| Dm7 G7 | Cmaj7 |").unwrap();
+    let mut chords = MidiBuilder::new();
+    chords.add_progression(&progression, Velocity::new(90).unwrap());
+    let mut bass = MidiBuilder::new();
+    for root in [D, G, C] {
+        let note = rust_music_theory::note::Note::new(Pitch::from(root), 2);
+        bass.add(&note, Duration::Half, Velocity::new(110).unwrap());
+    }
+    let file = MidiFile::new()
+        .tempo(96)
+        .track(chords, Channel::new(0).unwrap())
+        .track(bass, Channel::new(1).unwrap());
+
+    let pad = Instrument::new(Waveform::Triangle).envelope(Envelope::new(0.05, 0.2, 0.6, 0.5));
+    let synth = Synth::new()
+        .sample_rate(22_050)
+        .instrument(pad)
+        .channel(Channel::new(1).unwrap(), Instrument::new(Waveform::Saw).gain(0.6));
+    let audio = synth.render_file(&file);
+
+    // Two bars of 4/4 at 96 BPM, plus the pad's release.
+    assert!((audio.duration() - 5.5).abs() < 1e-3);
+    assert!(audio.peak() > 0.1 && audio.peak() <= 1.0);
+
+    let path = std::env::temp_dir().join("rust_music_theory_progression.wav");
+    audio.save_wav(&path, SampleFormat::Float32).unwrap();
+    let wav = std::fs::read(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(&wav[0..4], b"RIFF");
+    assert_eq!(&wav[8..12], b"WAVE");
+    assert_eq!(wav.len(), 58 + audio.samples().len() * 4);
+    assert_eq!(audio.to_wav(SampleFormat::Int16).unwrap().len(), 44 + audio.samples().len() * 2);
+}
+
+#[test]
+fn exported_file_imports_back_into_notes() {
+    use rust_music_theory::midi::MidiImport;
+    use rust_music_theory::note::{KeyFinder, Notes};
+
+    let scale = Scale::new(ScaleType::Diatonic, Pitch::from(A), 4, Some(Mode::Aeolian), Direction::Ascending).unwrap();
+    let mut melody = MidiBuilder::new();
+    melody.tempo(90);
+    for note in scale.notes() {
+        melody.add(&note, Duration::Eighth, Velocity::new(80).unwrap());
+    }
+    let mut chords = MidiBuilder::new();
+    chords.add(&Chord::new(Pitch::from(A), Quality::Minor, Number::Triad), Duration::Whole, Velocity::new(70).unwrap());
+
+    let bytes = MidiFile::new()
+        .time_signature(3, 4)
+        .track(melody, Channel::new(0).unwrap())
+        .track(chords, Channel::new(1).unwrap())
+        .to_bytes();
+    let import = MidiImport::from_bytes(&bytes).unwrap();
+
+    assert_eq!(import.ppq, 480);
+    assert_eq!(import.tracks.len(), 3);
+    assert_eq!(import.time_signature_at(0), (3, 4));
+    assert_eq!(import.tempos.last().unwrap().bpm().round(), 90.0);
+
+    let melody = &import.tracks[1];
+    assert_eq!(melody.notes(), scale.notes());
+    assert!(melody.notes.iter().all(|n| n.duration == 240 && n.channel == Channel::new(0).unwrap()));
+    assert_eq!(melody.notes[7].tick, 1680);
+    assert!((import.seconds(melody.notes[7].tick) - 7.0 / 3.0).abs() < 1e-5);
+
+    let chord = &import.tracks[2];
+    assert_eq!(chord.notes.len(), 3);
+    assert!(chord.notes.iter().all(|n| n.tick == 0 && n.duration == 1920));
+
+    let key = &KeyFinder::default().rank(&import.notes())[0].key;
+    assert!(key.is_minor());
+    assert_eq!(key.tonic, Pitch::from(A));
+}
+
+#[test]
+fn meta_events_survive_export_and_import() {
+    use midly::{MetaMessage, Smf, TrackEventKind};
+    use rust_music_theory::harmony::Progression;
+    use rust_music_theory::midi::MidiImport;
+    use rust_music_theory::note::{KeySignature, NoteLetter};
+
+    let progression = Progression::parse("| Bbmaj7 Eb7 | Fm7 Bb7 |").unwrap();
+    let mut chords = MidiBuilder::new();
+    chords
+        .track_name("Comping")
+        .instrument_name("Electric Piano")
+        .key_signature(&KeySignature::new(Pitch::new(NoteLetter::B, -1)))
+        .marker("Intro")
+        .add_progression(&progression, Velocity::new(80).unwrap());
+    chords.marker("Fine");
+
+    let bytes = MidiFile::new()
+        .title("Blues in Bb")
+        .track(chords, Channel::new(0).unwrap())
+        .to_bytes();
+
+    let smf = Smf::parse(&bytes).unwrap();
+    let mut texts = Vec::new();
+    for track in &smf.tracks {
+        for event in track {
+            match event.kind {
+                TrackEventKind::Meta(MetaMessage::Marker(text)) => texts.push(format!("marker {}", String::from_utf8_lossy(text))),
+                TrackEventKind::Meta(MetaMessage::Text(text)) => texts.push(format!("text {}", String::from_utf8_lossy(text))),
+                TrackEventKind::Meta(MetaMessage::InstrumentName(text)) => texts.push(format!("instrument {}", String::from_utf8_lossy(text))),
+                _ => {}
+            }
+        }
+    }
+    assert_eq!(texts, vec![
+        "instrument Electric Piano",
+        "marker Intro",
+        "text Bbmaj7",
+        "text Eb7",
+        "text Fm7",
+        "text Bb7",
+        "marker Fine",
+    ]);
+
+    let import = MidiImport::from_bytes(&bytes).unwrap();
+    assert_eq!(import.tracks[0].name.as_deref(), Some("Blues in Bb"));
+    assert_eq!(import.tracks[1].name.as_deref(), Some("Comping"));
+    assert_eq!(import.key_at(0), Some(&KeySignature::from_midi(-2, false)));
+    let first: Vec<String> = import.tracks[1].notes.iter()
+        .take(4)
+        .map(|n| n.note.pitch.to_string())
+        .collect();
+    assert_eq!(first, vec!["Bb", "D", "F", "A"]);
+}
@@ -0,0 +1,86 @@
+extern crate rust_music_theory as theory;
+use theory::chord::Chord;
+use theory::note::{KeyFinder, KeyProfile, KeySignature, Note, NoteLetter, Notes, Pitch};
+use theory::scale::{Mode, Scale};
+
+fn notes(names: &[&str]) -> Vec<Note> {
+    names.iter().map(|name| name.parse().unwrap()).collect()
+}
+
+#[cfg(test)]
+mod key_finding_tests {
+    use super::*;
+
+    #[test]
+    fn test_major_scale_finds_its_key() {
+        let scale = Scale::from_regex("C major").unwrap();
+        let ranked = KeyFinder::default().rank(&scale.notes());
+        assert_eq!(ranked.len(), 24);
+        assert_eq!(ranked[0].key, KeySignature::new(Pitch::new(NoteLetter::C, 0)));
+        assert!(ranked[0].correlation > ranked[1].correlation);
+        assert!(ranked.windows(2).all(|pair| pair[0].correlation >= pair[1].correlation));
+    }
+
+    #[test]
+    fn test_harmonic_minor_finds_the_minor_key() {
+        let scale = Scale::from_regex("A harmonic minor").unwrap();
+        for profile in [KeyProfile::KrumhanslKessler, KeyProfile::Temperley].iter() {
+            let key = KeyFinder::new(*profile).find(&scale.notes()).unwrap();
+            assert_eq!(
+                key,
+                KeySignature::new_with_mode(Pitch::new(NoteLetter::A, 0), Some(Mode::Aeolian))
+            );
+        }
+    }
+
+    #[test]
+    fn test_keys_use_conventional_spellings() {
+        let db_major = Scale::from_regex("Db major").unwrap();
+        let key = KeyFinder::default().find(&db_major.notes()).unwrap();
+        assert_eq!(key.tonic, Pitch::new(NoteLetter::D, -1));
+
+        let g_sharp_minor = Scale::from_regex("G# aeolian").unwrap();
+        let key = KeyFinder::default().find(&g_sharp_minor.notes()).unwrap();
+        assert_eq!(key.tonic, Pitch::new(NoteLetter::G, 1));
+        assert!(key.is_minor());
+    }
+
+    #[test]
+    fn test_chord_material_and_duration_weights() {
+        let progression: Vec<Note> = ["C", "F", "G7", "C"]
+            .iter()
+            .flat_map(|symbol| Chord::parse(symbol).unwrap().notes())
+            .collect();
+        let evenly = KeyFinder::default().find(&progression).unwrap();
+        assert_eq!(evenly, KeySignature::new(Pitch::new(NoteLetter::C, 0)));
+
+        let weighted: Vec<(Note, f64)> = notes(&["A3", "C4", "E4", "G#4", "B4", "D4"])
+            .into_iter()
+            .zip([4.0, 1.0, 3.0, 1.0, 1.0, 0.5].iter().copied())
+            .collect();
+        let key = KeyFinder::default().rank_weighted(&weighted)[0].key.clone();
+        assert_eq!(
+            key,
+            KeySignature::new_with_mode(Pitch::new(NoteLetter::A, 0), Some(Mode::Aeolian))
+        );
+    }
+
+    #[test]
+    fn test_pitch_class_weights() {
+        let mut weights = [0.0; 12];
+        for &pitch_class in [7, 9, 11, 0, 2, 4, 6].iter() {
+            weights[pitch_class] = 1.0;
+        }
+        weights[7] = 3.0;
+        let ranked = KeyFinder::default().rank_pitch_classes(&weights);
+        assert_eq!(ranked[0].key, KeySignature::new(Pitch::new(NoteLetter::G, 0)));
+        assert!(ranked[0].correlation > 0.8 && ranked[0].correlation <= 1.0);
+    }
+
+    #[test]
+    fn test_uniform_material_has_no_candidates() {
+        assert!(KeyFinder::default().rank(&[]).is_empty());
+        assert!(KeyFinder::default().rank_pitch_classes(&[1.0; 12]).is_empty());
+        assert_eq!(KeyFinder::default().find(&[]), None);
+    }
+}
@@ -100,7 +100,7 @@ mod key_signature_tests {
     fn test_unknown_theoretical_keys_use_consistent_fallback_accidentals() {
         use PitchSymbol::*;
 
-        let flat_fallback = KeySignature::new(Pitch::new(NoteLetter::C, 2));
+        let flat_fallback = KeySignature::new(Pitch::new(NoteLetter::C, -2));
         let sharp_fallback = KeySignature::new(Pitch::new(NoteLetter::G, 2));
         let expected_flat = [C, Db, D, Eb, E, F, Gb, G, Ab, A, Bb, B];
         let expected_sharp = [C, Cs, D, Ds, E, F, Fs, G, Gs, A, As, B];
@@ -117,6 +117,35 @@ mod key_signature_tests {
         }
     }
 
+    #[test]
+    fn test_flat_keys_spell_chromatic_notes_with_flats() {
+        use PitchSymbol::*;
+
+        let cases = [
+            (key(NoteLetter::F, 0), vec![(1, Db), (3, Eb), (6, Gb), (8, Ab), (11, B)]),
+            (key(NoteLetter::B, -1), vec![(1, Db), (4, E), (6, Gb), (8, Ab)]),
+            (key(NoteLetter::E, -1), vec![(1, Db), (6, Gb), (11, B)]),
+            (key(NoteLetter::A, -1), vec![(6, Gb), (9, A)]),
+            (key(NoteLetter::D, -1), vec![(2, D), (9, A)]),
+            (key(NoteLetter::G, -1), vec![(0, C), (7, G)]),
+            (minor_key(NoteLetter::C, 0), vec![(1, Db), (6, Gb), (11, B)]),
+        ];
+        for (key, spellings) in cases.iter() {
+            for &(pitch_class, expected) in spellings {
+                assert_eq!(
+                    key.get_preferred_spelling(Pitch::from_u8(pitch_class)),
+                    expected,
+                    "{} in {:?}",
+                    pitch_class,
+                    key
+                );
+            }
+        }
+        // Sharp keys and keys without accidentals keep sharps.
+        assert_eq!(key(NoteLetter::D, 0).get_preferred_spelling(Pitch::from_u8(8)), Gs);
+        assert_eq!(key(NoteLetter::C, 0).get_preferred_spelling(Pitch::from_u8(10)), As);
+    }
+
      #[test]
     fn test_pitch_symbol_bs_cb_spellings() {
         // Test B# spelling (should be preferred in C# major, for example)
@@ -279,4 +308,112 @@ mod key_signature_tests {
         assert_eq!(key_sig.get_preferred_spelling(Pitch::from_u8(6)), PitchSymbol::Gb, "F#/Gb in F Major should be Gb");
         assert_eq!(key_sig.get_preferred_spelling(Pitch::from_u8(8)), PitchSymbol::Ab, "G#/Ab in F Major should be Ab");
     }
+
+    fn key(letter: NoteLetter, accidental: i8) -> KeySignature {
+        KeySignature::new(Pitch::new(letter, accidental))
+    }
+
+    fn minor_key(letter: NoteLetter, accidental: i8) -> KeySignature {
+        KeySignature::new_with_mode(Pitch::new(letter, accidental), Some(Mode::Aeolian))
+    }
+
+    #[test]
+    fn test_fifths_and_accidental_counts() {
+        let cases = [
+            (key(NoteLetter::C, 0), 0),
+            (key(NoteLetter::A, 0), 3),
+            (key(NoteLetter::C, 1), 7),
+            (key(NoteLetter::G, 1), 8),
+            (key(NoteLetter::E, -1), -3),
+            (key(NoteLetter::C, -1), -7),
+            (key(NoteLetter::F, -1), -8),
+            (minor_key(NoteLetter::E, 0), 1),
+            (minor_key(NoteLetter::A, -1), -7),
+            (KeySignature::new_with_mode(Pitch::new(NoteLetter::D, 0), Some(Mode::Dorian)), 0),
+            (KeySignature::new_with_mode(Pitch::new(NoteLetter::A, 0), Some(Mode::HarmonicMinor)), 0),
+        ];
+        for (key, fifths) in cases.iter() {
+            assert_eq!(key.fifths(), *fifths, "{:?}", key);
+        }
+        assert_eq!(key(NoteLetter::E, 0).sharps(), 4);
+        assert_eq!(key(NoteLetter::E, 0).flats(), 0);
+        assert_eq!(key(NoteLetter::D, -1).flats(), 5);
+        assert_eq!(key(NoteLetter::D, -1).sharps(), 0);
+    }
+
+    #[test]
+    fn test_ordered_accidentals() {
+        let as_strings = |key: KeySignature| -> Vec<String> {
+            key.accidentals().iter().map(|pitch| pitch.to_string()).collect()
+        };
+        assert!(key(NoteLetter::C, 0).accidentals().is_empty());
+        assert_eq!(as_strings(key(NoteLetter::E, 0)), ["F#", "C#", "G#", "D#"]);
+        assert_eq!(as_strings(key(NoteLetter::A, -1)), ["Bb", "Eb", "Ab", "Db"]);
+        assert_eq!(as_strings(minor_key(NoteLetter::G, 0)), ["Bb", "Eb"]);
+        assert_eq!(
+            as_strings(key(NoteLetter::G, 1)),
+            ["F##", "C#", "G#", "D#", "A#", "E#", "B#"]
+        );
+        assert_eq!(
+            as_strings(key(NoteLetter::F, -1)),
+            ["Bbb", "Eb", "Ab", "Db", "Gb", "Cb", "Fb"]
+        );
+    }
+
+    #[test]
+    fn test_theoretical_keys_are_computed() {
+        let g_sharp = key(NoteLetter::G, 1);
+        let pitches: Vec<String> = g_sharp.pitches().iter().map(|p| p.to_string()).collect();
+        assert_eq!(pitches, ["G#", "A#", "B#", "C#", "D#", "E#", "F##"]);
+        assert_eq!(g_sharp.get_preferred_spelling(Pitch::from_u8(0)), PitchSymbol::Bs);
+        assert_eq!(g_sharp.spell(Pitch::from_u8(7)), Pitch::new(NoteLetter::F, 2));
+
+        let f_flat = key(NoteLetter::F, -1);
+        assert_eq!(f_flat.get_preferred_spelling(Pitch::from_u8(4)), PitchSymbol::Fb);
+        assert_eq!(f_flat.spell(Pitch::from_u8(9)), Pitch::new(NoteLetter::B, -2));
+    }
+
+    #[test]
+    fn test_from_fifths_and_midi() {
+        assert_eq!(KeySignature::from_fifths(0, None), key(NoteLetter::C, 0));
+        assert_eq!(KeySignature::from_fifths(6, None), key(NoteLetter::F, 1));
+        assert_eq!(KeySignature::from_fifths(-6, None), key(NoteLetter::G, -1));
+        assert_eq!(KeySignature::from_fifths(8, None), key(NoteLetter::G, 1));
+        assert_eq!(KeySignature::from_fifths(-8, None), key(NoteLetter::F, -1));
+        assert_eq!(
+            KeySignature::from_fifths(2, Some(Mode::Mixolydian)),
+            KeySignature::new_with_mode(Pitch::new(NoteLetter::A, 0), Some(Mode::Mixolydian))
+        );
+
+        assert_eq!(KeySignature::from_midi(-3, true), minor_key(NoteLetter::C, 0));
+        assert_eq!(KeySignature::from_midi(7, false), key(NoteLetter::C, 1));
+        assert_eq!(minor_key(NoteLetter::F, 1).to_midi(), Some((3, true)));
+        assert_eq!(key(NoteLetter::B, -1).to_midi(), Some((-2, false)));
+        assert_eq!(key(NoteLetter::G, 1).to_midi(), None);
+
+        assert_eq!(key(NoteLetter::C, 1).to_midi_enharmonic(), (7, false));
+        assert_eq!(key(NoteLetter::C, -1).to_midi_enharmonic(), (-7, false));
+        assert_eq!(key(NoteLetter::G, 1).to_midi_enharmonic(), (-4, false));
+        assert_eq!(key(NoteLetter::F, -1).to_midi_enharmonic(), (4, false));
+        assert_eq!(minor_key(NoteLetter::E, 1).to_midi_enharmonic(), (-4, true));
+        assert_eq!(KeySignature::from_fifths(20, None).to_midi_enharmonic(), (-4, false));
+        assert_eq!(KeySignature::from_fifths(-20, None).to_midi_enharmonic(), (4, false));
+        assert_eq!(KeySignature::from_fifths(12, None).to_midi_enharmonic(), (0, false));
+    }
+
+    #[test]
+    fn test_relative_parallel_and_neighbouring_keys() {
+        let e_minor = minor_key(NoteLetter::E, 0);
+        assert!(e_minor.is_minor());
+        assert_eq!(e_minor.relative_major(), key(NoteLetter::G, 0));
+        assert_eq!(key(NoteLetter::E, -1).relative_minor(), minor_key(NoteLetter::C, 0));
+        assert_eq!(e_minor.parallel_major(), key(NoteLetter::E, 0));
+        assert_eq!(key(NoteLetter::D, -1).parallel_minor(), minor_key(NoteLetter::D, -1));
+
+        assert_eq!(key(NoteLetter::C, 0).dominant(), key(NoteLetter::G, 0));
+        assert_eq!(key(NoteLetter::C, 0).subdominant(), key(NoteLetter::F, 0));
+        assert_eq!(e_minor.dominant(), minor_key(NoteLetter::B, 0));
+        assert_eq!(minor_key(NoteLetter::D, 0).subdominant(), minor_key(NoteLetter::G, 0));
+        assert_eq!(key(NoteLetter::C, 1).dominant(), key(NoteLetter::G, 1));
+    }
 }
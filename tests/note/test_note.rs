@@ -1,5 +1,7 @@
 extern crate rust_music_theory as theory;
-use theory::note::{Note, Pitch, NoteLetter};
+use theory::interval::Interval;
+use theory::note::{KeySignature, Note, NoteLetter, Pitch, Spelling};
+use theory::scale::Direction;
 
 #[cfg(test)]
 mod test_note {
@@ -73,4 +75,68 @@ mod test_note {
         let note_f_triple_flat = Note::new(Pitch::new(NoteLetter::F, -3), 5);
         assert_eq!(format!("{}", note_f_triple_flat), "Fbbb");
     }
+
+    #[test]
+    fn test_note_from_str() {
+        let cases = [
+            ("Eb4", NoteLetter::E, -1, 4),
+            ("F#-1", NoteLetter::F, 1, -1),
+            ("Bbb3", NoteLetter::B, -2, 3),
+            (" C ", NoteLetter::C, 0, 4),
+        ];
+        for (string, letter, accidental, octave) in cases {
+            let note: Note = string.parse().unwrap();
+            assert_eq!(note, Note::new(Pitch::new(letter, accidental), octave));
+        }
+
+        assert!("H4".parse::<Note>().is_err());
+        assert!("C4x".parse::<Note>().is_err());
+        assert!("C four".parse::<Note>().is_err());
+    }
+
+    #[test]
+    fn test_note_transpose_by_interval() {
+        let e4: Note = "E4".parse().unwrap();
+        let major_third: Interval = "M3".parse().unwrap();
+        assert_eq!(e4.transpose(major_third, Direction::Ascending).to_string(), "G#");
+        assert_eq!(e4.transpose(major_third, Direction::Ascending).octave, 4);
+        assert_eq!(e4.transpose(major_third, Direction::Descending), "C4".parse().unwrap());
+
+        let b3: Note = "B3".parse().unwrap();
+        let minor_ninth: Interval = "m9".parse().unwrap();
+        assert_eq!(&b3 + minor_ninth, "C5".parse().unwrap());
+        assert_eq!(b3 - minor_ninth, "A#2".parse().unwrap());
+
+        let low: Note = "D-1".parse().unwrap();
+        let perfect_fifth: Interval = "P5".parse().unwrap();
+        assert_eq!(low.clone() - perfect_fifth, "G-2".parse().unwrap());
+        assert_eq!(low + perfect_fifth, "A-1".parse().unwrap());
+    }
+
+    #[test]
+    fn test_note_transpose_semitones() {
+        let c4: Note = "C4".parse().unwrap();
+        assert_eq!(c4.transpose_semitones(1, &Spelling::Sharps), "C#4".parse().unwrap());
+        assert_eq!(c4.transpose_semitones(1, &Spelling::Flats), "Db4".parse().unwrap());
+        assert_eq!(c4.transpose_semitones(-1, &Spelling::Flats), "B3".parse().unwrap());
+        assert_eq!(c4.transpose_semitones(-13, &Spelling::Sharps), "B2".parse().unwrap());
+        assert_eq!(c4.transpose_semitones(0, &Spelling::Sharps), c4);
+
+        let f_major = Spelling::Key(KeySignature::new(Pitch::new(NoteLetter::F, 0)));
+        assert_eq!(c4.transpose_semitones(10, &f_major), "Bb4".parse().unwrap());
+        let e_major = Spelling::from(KeySignature::new(Pitch::new(NoteLetter::E, 0)));
+        assert_eq!(c4.transpose_semitones(8, &e_major), "G#4".parse().unwrap());
+
+        let negative: Note = "A-2".parse().unwrap();
+        assert_eq!(negative.transpose_semitones(5, &Spelling::Flats), "D-1".parse().unwrap());
+        assert_eq!(negative.transpose_semitones(-10, &Spelling::Flats), "B-3".parse().unwrap());
+    }
+
+    #[test]
+    fn test_note_transpose_semitones_from_cross_octave_spellings() {
+        let c_flat: Note = "Cb4".parse().unwrap();
+        assert_eq!(c_flat.transpose_semitones(1, &Spelling::Sharps), "C4".parse().unwrap());
+        let b_sharp: Note = "B#3".parse().unwrap();
+        assert_eq!(b_sharp.transpose_semitones(-1, &Spelling::Sharps), "B3".parse().unwrap());
+    }
 }
@@ -1,5 +1,5 @@
 extern crate rust_music_theory as theory;
-use theory::note::{Pitch, NoteLetter, Note};
+use theory::note::{Pitch, NoteLetter, Note, Spelling};
 use theory::scale::{Direction, Mode};
 use theory::interval::Interval;
 use NoteLetter::*;
@@ -281,4 +281,16 @@ mod test_note {
         assert_eq!(lowest.len(), 129);
         assert!(lowest[1..].chars().all(|character| character == 'b'));
     }
+
+    #[test]
+    fn test_pitch_transpose() {
+        let minor_third = Interval::from_semitone(3).unwrap();
+        assert_eq!(Pitch::new(A, 0) + minor_third, Pitch::new(C, 0));
+        assert_eq!(Pitch::new(A, 0) - minor_third, Pitch::new(F, 1));
+        assert_eq!(Pitch::new(E, -1).transpose(minor_third, Direction::Ascending), Pitch::new(G, -1));
+        assert_eq!(Pitch::new(B, 0).transpose_semitones(3, &Spelling::Flats), Pitch::new(D, 0));
+        assert_eq!(Pitch::new(B, 0).transpose_semitones(-4, &Spelling::Flats), Pitch::new(G, 0));
+        assert_eq!(Pitch::new(C, 0).transpose_semitones(-25, &Spelling::Sharps), Pitch::new(B, 0));
+        assert_eq!(Pitch::new(C, 0).transpose_semitones(6, &Spelling::Flats), Pitch::new(G, -1));
+    }
 }
@@ -0,0 +1,160 @@
+extern crate rust_music_theory as theory;
+use theory::chord::Chord;
+use theory::note::{NoteLetter, Pitch};
+use theory::pitch_class_set::{ForteNumber, PitchClassSet, PitchClassSetError};
+use theory::scale::Scale;
+
+fn set(classes: &[u8]) -> PitchClassSet {
+    PitchClassSet::new(classes).unwrap()
+}
+
+fn forte(name: &str) -> ForteNumber {
+    name.parse().unwrap()
+}
+
+#[cfg(test)]
+mod pitch_class_set_tests {
+    use super::*;
+
+    #[test]
+    fn test_construction() {
+        assert_eq!(set(&[7, 0, 4, 0]).pitch_classes(), [0, 4, 7]);
+        assert_eq!(set(&[7, 0, 4]).len(), 3);
+        assert!(set(&[]).is_empty());
+        assert_eq!(
+            PitchClassSet::new(&[0, 12]),
+            Err(PitchClassSetError::InvalidPitchClass(12))
+        );
+        let pitches = [
+            Pitch::new(NoteLetter::B, 1),
+            Pitch::new(NoteLetter::D, -1),
+            Pitch::new(NoteLetter::E, 0),
+        ];
+        assert_eq!(PitchClassSet::from_pitches(&pitches), set(&[0, 1, 4]));
+        let chord = Chord::parse("Cmaj7").unwrap();
+        assert_eq!(PitchClassSet::from_notes(&chord), set(&[0, 4, 7, 11]));
+        assert_eq!(set(&[0, 4, 7, 10, 11]).to_string(), "{0,4,7,T,E}");
+    }
+
+    #[test]
+    fn test_normal_and_prime_forms() {
+        assert_eq!(set(&[0, 4, 7]).normal_form(), [0, 4, 7]);
+        assert_eq!(set(&[11, 2, 7]).normal_form(), [7, 11, 2]);
+        assert_eq!(set(&[0, 4, 7]).prime_form(), [0, 3, 7]);
+        assert_eq!(set(&[1, 5, 6, 7]).prime_form(), [0, 1, 2, 6]);
+        assert_eq!(set(&[0, 3, 6, 9]).normal_form(), [0, 3, 6, 9]);
+        assert_eq!(set(&[]).prime_form(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_forte_and_rahn_prime_forms() {
+        let five_twenty = forte("5-20").set_class();
+        assert_eq!(five_twenty.forte_prime_form(), [0, 1, 3, 7, 8]);
+        assert_eq!(five_twenty.prime_form(), [0, 1, 5, 6, 8]);
+        assert_eq!(forte("6-Z29").set_class().prime_form(), [0, 2, 3, 6, 7, 9]);
+        assert_eq!(
+            forte("6-Z29").set_class().forte_prime_form(),
+            [0, 1, 3, 6, 8, 9]
+        );
+        let mut differing = 0;
+        for mask in 0..4096u16 {
+            let classes: Vec<u8> = (0..12).filter(|&c| mask & 1 << c != 0).collect();
+            let set = set(&classes);
+            if set.prime_form() != set.forte_prime_form() {
+                differing += 1;
+                let name = set.forte_number().to_string();
+                assert!(["5-20", "6-Z29", "6-31", "7-Z18", "7-20", "8-26"].contains(&name.as_str()));
+            }
+        }
+        assert!(differing > 0);
+    }
+
+    #[test]
+    fn test_forte_numbers() {
+        assert_eq!(set(&[0, 4, 7]).forte_number().to_string(), "3-11");
+        assert_eq!(set(&[0, 1, 4, 6]).forte_number().to_string(), "4-Z15");
+        assert_eq!(set(&[0, 1, 3, 7]).forte_number().to_string(), "4-Z29");
+        assert_eq!(set(&[0, 4, 8]).forte_number().to_string(), "3-12");
+        let major = Scale::from_regex("C major").unwrap();
+        assert_eq!(
+            PitchClassSet::from_notes(&major).forte_number().to_string(),
+            "7-35"
+        );
+        assert_eq!(set(&[0, 3, 6, 9]).forte_number(), forte("4-28"));
+        assert_eq!(set(&[]).forte_number().to_string(), "0-1");
+        assert_eq!(set(&[5]).forte_number().to_string(), "1-1");
+        assert_eq!(set(&[2, 8]).forte_number().to_string(), "2-6");
+        let aggregate: Vec<u8> = (0..12).collect();
+        assert_eq!(set(&aggregate).forte_number().to_string(), "12-1");
+    }
+
+    #[test]
+    fn test_parsing_forte_numbers() {
+        let number = forte("4-Z15");
+        assert_eq!(
+            (number.cardinality, number.ordinal, number.z),
+            (4, 15, true)
+        );
+        assert_eq!(forte("4-15"), number);
+        assert_eq!(forte("3-11").set_class().pitch_classes(), [0, 3, 7]);
+        assert_eq!(forte("9-12").set_class().len(), 9);
+        assert_eq!(
+            ForteNumber::parse("3-13"),
+            Err(PitchClassSetError::InvalidForteNumber("3-13".to_string()))
+        );
+        assert!(ForteNumber::parse("311").is_err());
+    }
+
+    #[test]
+    fn test_interval_vectors() {
+        assert_eq!(set(&[0, 4, 7]).interval_vector(), [0, 0, 1, 1, 1, 0]);
+        assert_eq!(set(&[0, 1, 4, 6]).interval_vector(), [1, 1, 1, 1, 1, 1]);
+        assert_eq!(
+            forte("6-35").set_class().interval_vector(),
+            [0, 6, 0, 6, 0, 3]
+        );
+        assert_eq!(
+            forte("7-35").set_class().interval_vector(),
+            [2, 5, 4, 3, 6, 1]
+        );
+    }
+
+    #[test]
+    fn test_transposition_and_inversion() {
+        let major = set(&[0, 4, 7]);
+        assert_eq!(major.transpose(2), set(&[2, 6, 9]));
+        assert_eq!(major.transpose(7), set(&[7, 11, 2]));
+        assert_eq!(major.transpose(12), major);
+        assert_eq!(major.invert(0), set(&[0, 8, 5]));
+        assert_eq!(major.invert(7), set(&[7, 3, 0]));
+        assert!(major.is_equivalent(&major.invert(7)));
+        assert!(!major.is_equivalent(&set(&[0, 4, 8])));
+    }
+
+    #[test]
+    fn test_complements() {
+        let major = PitchClassSet::from_notes(&Scale::from_regex("C major").unwrap());
+        let black_keys = major.complement();
+        assert_eq!(black_keys.pitch_classes(), [1, 3, 6, 8, 10]);
+        assert_eq!(black_keys.forte_number().to_string(), "5-35");
+        assert_eq!(
+            forte("6-Z29").set_class().complement().forte_number(),
+            forte("6-Z50")
+        );
+    }
+
+    #[test]
+    fn test_z_relations_and_subsets() {
+        let all_interval = set(&[0, 1, 4, 6]);
+        assert!(all_interval.is_z_related(&set(&[0, 1, 3, 7])));
+        assert!(!all_interval.is_z_related(&all_interval.transpose(3)));
+
+        let c_major = set(&[0, 4, 7]);
+        let scale = PitchClassSet::from_notes(&Scale::from_regex("C major").unwrap());
+        assert!(c_major.is_subset_of(&scale));
+        assert!(scale.is_superset_of(&c_major));
+        assert!(!set(&[1, 4, 7]).is_subset_of(&scale));
+        assert!(set(&[1, 4, 8]).is_abstract_subset_of(&scale));
+        assert!(!set(&[0, 4, 8]).is_abstract_subset_of(&scale));
+    }
+}
@@ -0,0 +1,156 @@
+extern crate rust_music_theory as theory;
+use theory::note::{KeySignature, Notes, Spelling};
+use theory::pitch_class_set::{PitchClassSetError, RowForm, RowLabel, ToneRow};
+
+/// The row of Berg's Violin Concerto.
+fn berg() -> ToneRow {
+    ToneRow::new(&[7, 10, 2, 6, 9, 0, 4, 8, 11, 1, 3, 5]).unwrap()
+}
+
+/// The row of Webern's Concerto, Op. 24, derived from its first trichord.
+fn webern() -> ToneRow {
+    ToneRow::new(&[11, 10, 2, 3, 7, 6, 8, 4, 5, 0, 1, 9]).unwrap()
+}
+
+fn chromatic() -> ToneRow {
+    let classes: Vec<u8> = (0..12).collect();
+    ToneRow::new(&classes).unwrap()
+}
+
+fn label(text: &str) -> RowLabel {
+    text.parse().unwrap()
+}
+
+#[cfg(test)]
+mod tone_row_tests {
+    use super::*;
+
+    #[test]
+    fn test_rows_must_be_permutations() {
+        assert_eq!(
+            ToneRow::new(&[0, 1, 2]),
+            Err(PitchClassSetError::RowLength(3))
+        );
+        assert_eq!(
+            ToneRow::new(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 10]),
+            Err(PitchClassSetError::RepeatedPitchClass(10))
+        );
+        assert_eq!(
+            ToneRow::new(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 12]),
+            Err(PitchClassSetError::InvalidPitchClass(12))
+        );
+        let from_pitches = ToneRow::from_pitches(&berg().pitches()).unwrap();
+        assert_eq!(from_pitches, berg());
+        assert_eq!(ToneRow::from_notes(&berg()).unwrap(), berg());
+    }
+
+    #[test]
+    fn test_forms() {
+        let row = berg();
+        assert_eq!(
+            row.prime(0).pitch_classes(),
+            [0, 3, 7, 11, 2, 5, 9, 1, 4, 6, 8, 10]
+        );
+        assert_eq!(row.prime(7), row);
+        assert_eq!(
+            row.retrograde(7).pitch_classes(),
+            [5, 3, 1, 11, 8, 4, 0, 9, 6, 2, 10, 7]
+        );
+        assert_eq!(
+            row.inversion(7).pitch_classes(),
+            [7, 4, 0, 8, 5, 2, 10, 6, 3, 1, 11, 9]
+        );
+        assert_eq!(
+            row.retrograde_inversion(7).pitch_classes(),
+            [9, 11, 1, 3, 6, 10, 2, 5, 8, 0, 4, 7]
+        );
+        assert_eq!(row.form(label("I7")), row.inversion(7));
+        assert_eq!(row.forms().len(), 48);
+    }
+
+    #[test]
+    fn test_matrix() {
+        let row = berg();
+        let matrix = row.matrix();
+        assert_eq!(matrix[0], row.pitch_classes());
+        let column: Vec<u8> = matrix.iter().map(|line| line[0]).collect();
+        assert_eq!(column, row.inversion(7).pitch_classes());
+        for line in &matrix {
+            let prime = row.prime(line[0]);
+            assert_eq!(*line, prime.pitch_classes());
+        }
+        for (index, line) in matrix.iter().enumerate() {
+            assert_eq!(line[index], 7);
+        }
+    }
+
+    #[test]
+    fn test_labels() {
+        assert_eq!(label("RI7"), RowLabel::new(RowForm::RetrogradeInversion, 7));
+        assert_eq!(label("P0").to_string(), "P0");
+        assert_eq!(label("ri11").to_string(), "RI11");
+        assert_eq!(
+            RowLabel::parse("Q3"),
+            Err(PitchClassSetError::InvalidRowLabel("Q3".to_string()))
+        );
+        assert!(RowLabel::parse("P12").is_err());
+        assert!(RowLabel::parse("I").is_err());
+
+        let row = berg();
+        assert_eq!(row.labels_of(&row.inversion(5)), [label("I5")]);
+        assert_eq!(row.labels_of(&chromatic()), Vec::<RowLabel>::new());
+        let symmetric = chromatic();
+        assert_eq!(
+            symmetric.labels_of(&symmetric.retrograde(11)),
+            [label("R11"), label("I10")]
+        );
+    }
+
+    #[test]
+    fn test_combinatoriality() {
+        let chromatic = chromatic();
+        assert!(chromatic.is_all_combinatorial());
+        assert!(chromatic.combinatorial_forms().contains(&label("P6")));
+
+        let schoenberg = ToneRow::new(&[4, 5, 7, 1, 6, 3, 8, 2, 11, 0, 9, 10]).unwrap();
+        assert!(schoenberg.is_combinatorial(RowForm::Inversion));
+        assert!(schoenberg.combinatorial_forms().contains(&label("I11")));
+        assert!(!schoenberg.is_combinatorial(RowForm::Prime));
+        assert!(!schoenberg.is_all_combinatorial());
+
+        assert_eq!(berg().combinatorial_forms(), [label("R7")]);
+    }
+
+    #[test]
+    fn test_derived_rows() {
+        assert!(webern().is_derived(3));
+        assert_eq!(webern().derivations(), [3]);
+        assert!(!berg().is_derived(3));
+        assert!(!webern().is_derived(5));
+        assert_eq!(chromatic().derivations(), [2, 3, 4, 6]);
+    }
+
+    #[test]
+    fn test_spelling_and_display() {
+        let row = berg();
+        assert_eq!(row.to_string(), "7 T 2 6 9 0 4 8 E 1 3 5");
+        let names: Vec<String> = row
+            .clone()
+            .spelling(Spelling::Flats)
+            .notes()
+            .iter()
+            .map(|note| format!("{}{}", note.pitch, note.octave))
+            .collect();
+        assert_eq!(
+            names,
+            ["G4", "Bb4", "D4", "Gb4", "A4", "C4", "E4", "Ab4", "B4", "Db4", "Eb4", "F4"]
+        );
+        let in_d = row
+            .spelling(Spelling::Key(KeySignature::from_fifths(2, None)))
+            .octave(3)
+            .notes();
+        assert_eq!(in_d[3].to_string(), "F#");
+        assert_eq!(in_d[3].octave, 3);
+        assert_eq!(in_d[3].midi_pitch(), 54);
+    }
+}
@@ -0,0 +1,190 @@
+extern crate rust_music_theory as theory;
+use theory::chord::{Chord, ChordExtension};
+use theory::note::{NoteLetter, Notes, Pitch};
+use theory::scale::{Direction, Mode, Scale, ScaleError, ScaleType};
+
+fn scale(letter: NoteLetter, accidental: i8, mode: Mode) -> Scale {
+    Scale::new(
+        mode.scale_type(),
+        Pitch::new(letter, accidental),
+        4,
+        Some(mode),
+        Direction::Ascending,
+    )
+    .unwrap()
+}
+
+fn symbols(scale: &Scale, extension: ChordExtension) -> Vec<String> {
+    scale
+        .diatonic_chords(extension)
+        .unwrap()
+        .iter()
+        .map(|chord| chord.canonical_symbol())
+        .collect()
+}
+
+#[cfg(test)]
+mod diatonic_chord_tests {
+    use super::*;
+
+    #[test]
+    fn test_major_scale_triads_and_sevenths() {
+        let major = scale(NoteLetter::C, 0, Mode::Ionian);
+        assert_eq!(
+            symbols(&major, ChordExtension::Triad),
+            ["C", "Dm", "Em", "F", "G", "Am", "Bdim"]
+        );
+        assert_eq!(
+            symbols(&major, ChordExtension::Seventh),
+            ["Cmaj7", "Dm7", "Em7", "Fmaj7", "G7", "Am7", "Bm7b5"]
+        );
+    }
+
+    #[test]
+    fn test_roots_keep_the_scale_spelling() {
+        let minor = scale(NoteLetter::F, 1, Mode::Aeolian);
+        assert_eq!(
+            symbols(&minor, ChordExtension::Seventh),
+            ["F#m7", "G#m7b5", "Amaj7", "Bm7", "C#m7", "Dmaj7", "E7"]
+        );
+        let chords = minor.diatonic_chords(ChordExtension::Triad).unwrap();
+        let octaves: Vec<i16> = chords.iter().map(|chord| chord.octave()).collect();
+        assert_eq!(octaves, [4, 4, 4, 4, 5, 5, 5]);
+    }
+
+    #[test]
+    fn test_harmonic_and_melodic_minor_families() {
+        assert_eq!(
+            symbols(
+                &scale(NoteLetter::A, 0, Mode::HarmonicMinor),
+                ChordExtension::Seventh
+            ),
+            ["AmMaj7", "Bm7b5", "CaugMaj7", "Dm7", "E7", "Fmaj7", "G#dim7"]
+        );
+        assert_eq!(
+            symbols(
+                &scale(NoteLetter::C, 0, Mode::MelodicMinor),
+                ChordExtension::Triad
+            ),
+            ["Cm", "Dm", "Ebaug", "F", "G", "Adim", "Bdim"]
+        );
+        assert_eq!(
+            symbols(
+                &scale(NoteLetter::G, 0, Mode::LydianDominant),
+                ChordExtension::Seventh
+            ),
+            ["G7", "A7", "Bm7b5", "C#m7b5", "DmMaj7", "Em7", "FaugMaj7"]
+        );
+    }
+
+    #[test]
+    fn test_extended_chords_alter_departures_from_the_usual_form() {
+        let major = scale(NoteLetter::C, 0, Mode::Ionian);
+        let ninths = symbols(&major, ChordExtension::Ninth);
+        assert_eq!(ninths[0], "Cmaj9");
+        assert_eq!(ninths[4], "G9");
+        let fourth = major
+            .chord_on_degree(4, ChordExtension::Thirteenth)
+            .unwrap();
+        let pitches: Vec<String> = fourth
+            .notes()
+            .iter()
+            .map(|note| note.pitch.to_string())
+            .collect();
+        assert_eq!(pitches, ["F", "A", "C", "E", "G", "B", "D"]);
+
+        let dominant = scale(NoteLetter::A, 0, Mode::HarmonicMinor)
+            .chord_on_degree(5, ChordExtension::Thirteenth)
+            .unwrap();
+        let pitches: Vec<String> = dominant
+            .notes()
+            .iter()
+            .map(|note| note.pitch.to_string())
+            .collect();
+        assert_eq!(pitches, ["E", "G#", "B", "D", "F", "A", "C"]);
+    }
+
+    #[test]
+    fn test_every_heptatonic_mode_stacks_to_the_seventh() {
+        for &mode in Mode::heptatonic_modes() {
+            let scale = scale(NoteLetter::D, 0, mode);
+            for &extension in [ChordExtension::Triad, ChordExtension::Seventh].iter() {
+                let chords = scale.diatonic_chords(extension).unwrap();
+                assert_eq!(chords.len(), 7, "{:?}", mode);
+                for (chord, note) in chords.iter().zip(scale.notes()) {
+                    assert_eq!(chord.root(), note.pitch, "{:?}", mode);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_unsupported_scales_degrees_and_stacks() {
+        let pentatonic = Scale::new(
+            ScaleType::PentatonicMajor,
+            Pitch::new(NoteLetter::C, 0),
+            4,
+            None,
+            Direction::Ascending,
+        )
+        .unwrap();
+        assert_eq!(
+            pentatonic
+                .chord_on_degree(1, ChordExtension::Triad)
+                .unwrap_err(),
+            ScaleError::NotHeptatonic(ScaleType::PentatonicMajor)
+        );
+        let major = scale(NoteLetter::C, 0, Mode::Ionian);
+        assert_eq!(
+            major.chord_on_degree(8, ChordExtension::Triad).unwrap_err(),
+            ScaleError::InvalidDegree(8)
+        );
+        assert_eq!(
+            major.chord_on_degree(1, ChordExtension::Sixth).unwrap_err(),
+            ScaleError::UnsupportedExtension(ChordExtension::Sixth)
+        );
+    }
+
+    #[test]
+    fn test_harmonic_minor_stacks_to_the_thirteenth() {
+        let minor = scale(NoteLetter::A, 0, Mode::HarmonicMinor);
+        assert_eq!(
+            symbols(&minor, ChordExtension::Thirteenth),
+            [
+                "AmMaj13b13",
+                "Bm13b5b9",
+                "Cmaj13#5",
+                "Dm13#11",
+                "E13b9b13",
+                "Fmaj13#9#11",
+                "G#dim7addb9addb11addb13",
+            ]
+        );
+        let leading = minor.chord_on_degree(7, ChordExtension::Ninth).unwrap();
+        let pitches: Vec<String> = leading
+            .notes()
+            .iter()
+            .map(|note| note.pitch.to_string())
+            .collect();
+        assert_eq!(pitches, ["G#", "B", "D", "F", "A"]);
+        let parsed: Chord = leading.canonical_symbol().parse().unwrap();
+        assert_eq!(parsed.canonical_symbol(), "G#dim7addb9");
+    }
+
+    #[test]
+    fn test_every_heptatonic_mode_stacks_to_the_thirteenth() {
+        for &mode in Mode::heptatonic_modes() {
+            let scale = scale(NoteLetter::D, 0, mode);
+            for &extension in [
+                ChordExtension::Ninth,
+                ChordExtension::Eleventh,
+                ChordExtension::Thirteenth,
+            ]
+            .iter()
+            {
+                let chords = scale.diatonic_chords(extension);
+                assert_eq!(chords.map(|chords| chords.len()), Ok(7), "{:?}", mode);
+            }
+        }
+    }
+}
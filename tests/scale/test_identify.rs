@@ -0,0 +1,143 @@
+extern crate rust_music_theory as theory;
+use theory::note::{Notes, Pitch};
+use theory::scale::{Mode, Scale, ScaleCandidate, ScaleMatch};
+
+fn pitches(names: &[&str]) -> Vec<Pitch> {
+    names.iter().map(|name| name.parse().unwrap()).collect()
+}
+
+fn describe(candidate: &ScaleCandidate) -> String {
+    format!(
+        "{} {}",
+        candidate.scale.tonic,
+        candidate.scale.mode.unwrap().canonical_name()
+    )
+}
+
+#[cfg(test)]
+mod identify_tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_diatonic_collection_lists_every_mode() {
+        let white_keys = pitches(&["C", "D", "E", "F", "G", "A", "B"]);
+        let names: Vec<String> = Scale::containing(&white_keys, ScaleMatch::Exact)
+            .iter()
+            .map(describe)
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "C Ionian",
+                "D Dorian",
+                "E Phrygian",
+                "F Lydian",
+                "G Mixolydian",
+                "A Aeolian",
+                "B Locrian"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_exact_harmonic_minor_collection() {
+        let collection = pitches(&["A", "B", "C", "D", "E", "F", "G#"]);
+        let candidates = Scale::containing(&collection, ScaleMatch::Exact);
+        assert_eq!(candidates.len(), 7);
+        assert_eq!(describe(&candidates[0]), "A Harmonic Minor");
+        assert!(candidates
+            .iter()
+            .any(|candidate| describe(candidate) == "E Phrygian Dominant"));
+        assert!(candidates
+            .iter()
+            .all(|candidate| candidate.extra_notes == 0));
+    }
+
+    #[test]
+    fn test_subset_ranks_by_extra_notes() {
+        let triad = pitches(&["C", "E", "G"]);
+        let candidates = Scale::containing(&triad, ScaleMatch::Subset);
+        assert_eq!(describe(&candidates[0]), "C Pentatonic Major");
+        assert_eq!(candidates[0].extra_notes, 2);
+        assert!(candidates
+            .windows(2)
+            .all(|pair| pair[0].extra_notes <= pair[1].extra_notes));
+        assert!(candidates
+            .iter()
+            .any(|candidate| describe(candidate) == "A Pentatonic Minor"));
+        assert!(candidates
+            .iter()
+            .any(|candidate| describe(candidate) == "G Mixolydian"));
+        assert!(!candidates
+            .iter()
+            .any(|candidate| candidate.scale.mode == Some(Mode::WholeTone)));
+        assert_eq!(candidates.last().unwrap().scale.mode, Some(Mode::Chromatic));
+    }
+
+    #[test]
+    fn test_candidates_follow_the_given_spelling() {
+        let flats = pitches(&["Db", "F", "Ab", "Gb"]);
+        let candidates = Scale::containing(&flats, ScaleMatch::Subset);
+        let db_major = candidates
+            .iter()
+            .find(|candidate| {
+                candidate.scale.mode == Some(Mode::Ionian) && candidate.scale.tonic.into_u8() == 1
+            })
+            .unwrap();
+        assert_eq!(db_major.scale.tonic.to_string(), "Db");
+
+        let sharps = pitches(&["C#", "E#", "G#", "F#"]);
+        let candidates = Scale::containing(&sharps, ScaleMatch::Subset);
+        let c_sharp_major = candidates
+            .iter()
+            .find(|candidate| {
+                candidate.scale.mode == Some(Mode::Ionian) && candidate.scale.tonic.into_u8() == 1
+            })
+            .unwrap();
+        assert_eq!(c_sharp_major.scale.tonic.to_string(), "C#");
+    }
+
+    #[test]
+    fn test_whole_tone_and_empty_input() {
+        let whole_tone = pitches(&["C", "D", "E", "F#", "G#", "A#"]);
+        let candidates = Scale::containing(&whole_tone, ScaleMatch::Exact);
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(describe(&candidates[0]), "C Whole Tone");
+        assert!(Scale::containing(&[], ScaleMatch::Subset).is_empty());
+    }
+
+    #[test]
+    fn test_symmetric_scales_are_listed_once_per_pitch_set() {
+        for collection in &[vec!["C"], vec!["C", "E", "G"], vec!["D", "F#", "A#"]] {
+            let candidates = Scale::containing(&pitches(collection), ScaleMatch::Subset);
+            let sets: Vec<(Option<Mode>, Vec<u8>)> = candidates
+                .iter()
+                .map(|candidate| {
+                    let mut set: Vec<u8> = candidate
+                        .scale
+                        .notes()
+                        .iter()
+                        .map(|note| note.pitch.into_u8())
+                        .collect();
+                    set.sort_unstable();
+                    set.dedup();
+                    (candidate.scale.mode, set)
+                })
+                .collect();
+            for (index, set) in sets.iter().enumerate() {
+                assert!(!sets[..index].contains(set), "{:?} listed twice", set);
+            }
+            let chromatic = candidates
+                .iter()
+                .filter(|candidate| candidate.scale.mode == Some(Mode::Chromatic))
+                .count();
+            assert_eq!(chromatic, 1);
+        }
+        let whole_tone = Scale::containing(&pitches(&["D", "F#", "A#"]), ScaleMatch::Subset)
+            .into_iter()
+            .filter(|candidate| candidate.scale.mode == Some(Mode::WholeTone))
+            .map(|candidate| candidate.scale.tonic.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(whole_tone, ["D"]);
+    }
+}
@@ -224,4 +224,37 @@ mod scale_tests {
         // D Dorian has the same notes as C major
         assert_eq!(notes[2].pitch, Pitch::new(NoteLetter::F, 0)); // Natural F, not F#
     }
+
+    #[test]
+    fn test_scale_transpose() {
+        let scale = Scale::from_regex("D dorian").unwrap();
+        let up = scale.transpose("m3".parse().unwrap(), Direction::Ascending);
+        assert_eq!(up.tonic, Pitch::new(NoteLetter::F, 0));
+        assert_eq!(up.mode, Some(Dorian));
+        assert_eq!(up.direction, Direction::Ascending);
+        assert_notes(&[F, G, Ab, Bb, C, D, Eb, F], up.notes());
+
+        let descending =
+            Scale::new(ScaleType::HarmonicMinor, Pitch::new(NoteLetter::A, 0), 4, None, Direction::Descending)
+                .unwrap();
+        let down = descending.transpose("M2".parse().unwrap(), Direction::Descending);
+        assert_eq!(down.direction, Direction::Descending);
+        assert_eq!(down.tonic, Pitch::new(NoteLetter::G, 0));
+        assert_eq!(down.octave, 4);
+    }
+
+    #[test]
+    fn test_scale_transpose_semitones_and_to() {
+        let scale = Scale::from_regex("C ionian").unwrap();
+        let gb_major = Spelling::Key(KeySignature::new(Pitch::new(NoteLetter::G, -1)));
+        let flat_side = scale.transpose_semitones(6, &gb_major);
+        assert_eq!(flat_side.tonic, Pitch::new(NoteLetter::G, -1));
+        assert_eq!(scale.transpose_semitones(6, &Spelling::Sharps).tonic, Pitch::new(NoteLetter::F, 1));
+        assert_eq!(scale.transpose_semitones(-1, &Spelling::Sharps).octave, 3);
+
+        let moved = scale.transpose_to(Pitch::new(NoteLetter::E, -1));
+        assert_eq!(moved.tonic, Pitch::new(NoteLetter::E, -1));
+        assert_eq!(moved.intervals, scale.intervals);
+        assert_eq!(moved.octave, 4);
+    }
 }
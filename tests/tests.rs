@@ -8,6 +8,8 @@ mod chord {
     mod test_lead_sheet_audit;
     mod test_lead_sheet_second_audit;
     mod test_lead_sheet_third_audit;
+    mod test_identify;
+    mod test_voicing;
 }
 
 mod scale {
@@ -16,6 +18,8 @@ mod scale {
     mod test_exotic_scales;
     mod test_additional_scales;
     mod test_minor_modes;
+    mod test_identify;
+    mod test_diatonic_chords;
 }
 
 mod note {
@@ -24,6 +28,29 @@ mod note {
     mod test_note;
     mod test_pitch_symbol;
     mod test_notes_trait;
+    mod test_key_finding;
+}
+
+mod fretboard {
+    mod test_fretboard;
+    mod test_tab;
+}
+
+mod harmony {
+    mod test_roman;
+    mod test_roman_chords;
+    mod test_chord_scale;
+    mod test_progression;
+    mod test_voice_leading;
+}
+
+mod pitch_class_set {
+    mod test_pitch_class_set;
+    mod test_tone_row;
+}
+
+mod tuning {
+    mod test_tuning;
 }
 
 mod interval {
@@ -0,0 +1,370 @@
+extern crate rust_music_theory as theory;
+use theory::note::{Note, NoteLetter, Pitch};
+use theory::tuning::{KeyboardMapping, ScalaScale, Temperament, TuningError, TuningSystem};
+
+fn note(name: &str) -> Note {
+    name.parse().unwrap()
+}
+
+fn hz(name: &str, tuning: &TuningSystem) -> f64 {
+    note(name).frequency(tuning)
+}
+
+fn cents(low: &str, high: &str, tuning: &TuningSystem) -> f64 {
+    1200.0 * (hz(high, tuning) / hz(low, tuning)).log2()
+}
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-6,
+        "expected {}, got {}",
+        expected,
+        actual
+    );
+}
+
+const PENTATONIC: &str = "! pentatonic.scl
+!
+Just pentatonic
+ 5
+!
+ 9/8
+ 5/4
+ 3/2
+ 5/3
+ 2
+";
+
+const JUST_MAJOR: &str = "! just_major.scl
+Just major
+7
+9/8
+5/4
+4/3
+3/2
+5/3
+15/8
+2/1
+";
+
+const WHITE_KEYS: &str = "! white_keys.kbm
+! Map size
+12
+! First and last keys
+0
+127
+! Middle key, reference key and frequency
+60
+69
+440.0
+! Formal octave degree
+7
+! Mapping
+0
+x
+1
+x
+2
+3
+x
+4
+x
+5
+x
+6
+";
+
+#[cfg(test)]
+mod tuning_tests {
+    use super::*;
+
+    #[test]
+    fn test_equal_temperament() {
+        let tuning = TuningSystem::equal_temperament();
+        assert_close(hz("A4", &tuning), 440.0);
+        assert_close(hz("A5", &tuning), 880.0);
+        assert_close(hz("C4", &tuning), 261.625_565_300_598_6);
+        assert_close(hz("B#3", &tuning), hz("C4", &tuning));
+        assert_close(cents("G#4", "Ab4", &tuning), 0.0);
+        assert_eq!(TuningSystem::default(), tuning);
+    }
+
+    #[test]
+    fn test_reference_frequency() {
+        let verdi = TuningSystem::equal_temperament().reference_frequency(432.0);
+        assert_close(hz("A4", &verdi), 432.0);
+        assert_close(hz("A3", &verdi), 216.0);
+        let baroque = TuningSystem::equal_temperament().reference_frequency(415.0);
+        assert_close(hz("A4", &baroque), 415.0);
+        assert_close(cents("A4", "A4", &baroque), 0.0);
+
+        let scientific = TuningSystem::equal_temperament().reference(note("C4"), 256.0);
+        assert_close(hz("C4", &scientific), 256.0);
+        assert_close(hz("C5", &scientific), 512.0);
+        assert_eq!(scientific.reference_note(), &note("C4"));
+    }
+
+    #[test]
+    fn test_pythagorean() {
+        let tuning = TuningSystem::pythagorean();
+        assert_eq!(tuning.temperament(), &Temperament::Pythagorean);
+        assert_close(hz("A4", &tuning), 440.0);
+        assert_close(hz("E5", &tuning) / hz("A4", &tuning), 1.5);
+        assert_close(hz("D4", &tuning) / hz("A3", &tuning), 4.0 / 3.0);
+        assert_close(hz("C5", &tuning), 440.0 * 32.0 / 27.0);
+        assert_close(cents("C4", "E4", &tuning), 1200.0 * (81.0f64 / 64.0).log2());
+        // The Pythagorean comma separates enharmonic spellings.
+        assert_close(
+            cents("Ab4", "G#4", &tuning),
+            1200.0 * (531_441.0f64 / 524_288.0).log2(),
+        );
+        assert_close(cents("C4", "C5", &tuning), 1200.0);
+    }
+
+    #[test]
+    fn test_quarter_comma_meantone() {
+        let tuning = TuningSystem::quarter_comma_meantone();
+        assert_close(hz("E4", &tuning) / hz("C4", &tuning), 1.25);
+        assert_close(hz("G#4", &tuning) / hz("E4", &tuning), 1.25);
+        assert_close(cents("C4", "G4", &tuning), 696.578_428_466_208_7);
+        assert!(hz("G#4", &tuning) < hz("Ab4", &tuning));
+        assert_close(cents("G#4", "Ab4", &tuning), 41.058_858_717_418);
+        assert_close(cents("C4", "C5", &tuning), 1200.0);
+    }
+
+    #[test]
+    fn test_just_intonation() {
+        let c = TuningSystem::just_intonation(Pitch::new(NoteLetter::C, 0));
+        assert_close(hz("A4", &c), 440.0);
+        assert_close(hz("C4", &c), 264.0);
+        assert_close(hz("E4", &c), 330.0);
+        assert_close(hz("G4", &c), 396.0);
+        assert_close(hz("B4", &c), 495.0);
+        assert_close(hz("C5", &c), 528.0);
+        assert_close(hz("E3", &c), 165.0);
+
+        let d = TuningSystem::just_intonation(Pitch::new(NoteLetter::D, 0));
+        assert_close(hz("A4", &d) / hz("D4", &d), 1.5);
+        assert_close(hz("F#4", &d) / hz("D4", &d), 1.25);
+        assert_close(hz("C5", &d) / hz("D4", &d), 9.0 / 5.0);
+        assert_close(hz("C4", &d) / hz("D3", &d), 9.0 / 5.0);
+    }
+
+    #[test]
+    fn test_equal_divisions() {
+        let twelve = TuningSystem::equal_divisions(12).unwrap();
+        let equal = TuningSystem::equal_temperament();
+        for name in &["C4", "C#4", "Db4", "F#2", "Bb6", "E#5"] {
+            assert_close(hz(name, &twelve), hz(name, &equal));
+        }
+
+        let nineteen = TuningSystem::equal_divisions(19).unwrap();
+        let step = 1200.0 / 19.0;
+        assert_close(hz("A4", &nineteen), 440.0);
+        assert_close(cents("C4", "G4", &nineteen), 11.0 * step);
+        assert_close(cents("C4", "C#4", &nineteen), step);
+        assert_close(cents("C4", "Db4", &nineteen), 2.0 * step);
+        assert_close(cents("E4", "F4", &nineteen), 2.0 * step);
+
+        let thirty_one = TuningSystem::equal_divisions(31).unwrap();
+        assert_close(cents("C4", "G4", &thirty_one), 18.0 * 1200.0 / 31.0);
+        assert_close(cents("C4", "E4", &thirty_one), 10.0 * 1200.0 / 31.0);
+
+        let fifty_three = TuningSystem::equal_divisions(53).unwrap();
+        assert_close(cents("C4", "G4", &fifty_three), 31.0 * 1200.0 / 53.0);
+        assert_close(cents("C4", "C5", &fifty_three), 1200.0);
+
+        let twenty_four = TuningSystem::equal_divisions(24).unwrap();
+        assert_close(hz("C4", &twenty_four), hz("C4", &equal));
+        assert_close(cents("C4", "C#4", &twenty_four), 100.0);
+
+        assert_eq!(
+            TuningSystem::equal_divisions(0),
+            Err(TuningError::InvalidDivisions(0))
+        );
+    }
+
+    #[test]
+    fn test_step_frequency() {
+        let equal = TuningSystem::equal_temperament();
+        assert_close(equal.step_frequency(0).unwrap(), hz("C4", &equal));
+        assert_close(equal.step_frequency(9).unwrap(), 440.0);
+
+        let quarter_tones = TuningSystem::equal_divisions(24).unwrap();
+        assert_close(quarter_tones.step_frequency(18).unwrap(), 440.0);
+        assert_close(
+            quarter_tones.step_frequency(19).unwrap(),
+            440.0 * 2f64.powf(1.0 / 24.0),
+        );
+        assert_close(quarter_tones.step_frequency(-6).unwrap(), hz("A3", &equal));
+
+        assert_eq!(TuningSystem::pythagorean().step_frequency(0), None);
+        assert_eq!(
+            TuningSystem::just_intonation(Pitch::new(NoteLetter::C, 0)).step_frequency(0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_scala_scale_parse() {
+        let scale = ScalaScale::parse(PENTATONIC).unwrap();
+        assert_eq!(scale.description(), "Just pentatonic");
+        assert_eq!(scale.len(), 5);
+        assert!(!scale.is_empty());
+        assert_close(scale.period(), 1200.0);
+        assert_close(scale.cents()[0], 1200.0 * (9.0f64 / 8.0).log2());
+        assert_close(scale.cents()[2], 1200.0 * 1.5f64.log2());
+
+        let in_cents = ScalaScale::parse("Cents\n3\n100.0\n350.5 a comment\n1200.\n").unwrap();
+        assert_eq!(in_cents.cents(), &[100.0, 350.5, 1200.0]);
+
+        let built = ScalaScale::new("Built", vec![100.0, 1200.0]).unwrap();
+        assert_eq!(built.description(), "Built");
+    }
+
+    #[test]
+    fn test_scala_scale_errors() {
+        assert_eq!(
+            ScalaScale::parse("! only comments\n"),
+            Err(TuningError::InvalidScala {
+                line: 0,
+                message: "missing description".to_string()
+            })
+        );
+        assert_eq!(
+            ScalaScale::parse("Bad count\nmany\n"),
+            Err(TuningError::InvalidScala {
+                line: 2,
+                message: "expected the number of notes".to_string()
+            })
+        );
+        assert_eq!(
+            ScalaScale::parse("! comment\nBad degree\n2\n9/8\nthree halves\n"),
+            Err(TuningError::InvalidScala {
+                line: 5,
+                message: "expected cents or a ratio".to_string()
+            })
+        );
+        assert!(matches!(
+            ScalaScale::parse("Short\n3\n9/8\n"),
+            Err(TuningError::InvalidScala { .. })
+        ));
+        assert!(matches!(
+            ScalaScale::parse("Negative\n1\n-3/2\n"),
+            Err(TuningError::InvalidScala { line: 3, .. })
+        ));
+        assert!(matches!(
+            ScalaScale::parse("Huge\n1000000000000000000\n9/8\n"),
+            Err(TuningError::InvalidScala { line: 0, .. })
+        ));
+        assert!(matches!(
+            ScalaScale::parse("Infinite\n1\n1e400/1\n"),
+            Err(TuningError::InvalidScala { line: 3, .. })
+        ));
+        assert!(ScalaScale::new("Empty", Vec::new()).is_err());
+        assert!(matches!(
+            ScalaScale::load("does/not/exist.scl"),
+            Err(TuningError::Io(_))
+        ));
+        assert_eq!(
+            TuningError::InvalidScala {
+                line: 5,
+                message: "expected cents or a ratio".to_string()
+            }
+            .to_string(),
+            "Invalid Scala scale at line 5: expected cents or a ratio"
+        );
+    }
+
+    #[test]
+    fn test_scala_tuning_with_linear_mapping() {
+        let tuning = TuningSystem::scala(ScalaScale::parse(PENTATONIC).unwrap());
+        // Degree 0 sits on middle C and every key plays the next degree.
+        assert_close(hz("A4", &tuning), 440.0);
+        assert_close(hz("C4", &tuning), 132.0);
+        assert_close(hz("C#4", &tuning), 148.5);
+        assert_close(hz("D4", &tuning), 165.0);
+        assert_close(hz("C5", &tuning), 660.0);
+        assert!(tuning.is_mapped(&note("C4")));
+        assert_close(tuning.step_frequency(0).unwrap(), 132.0);
+        assert_close(tuning.step_frequency(5).unwrap(), 264.0);
+    }
+
+    #[test]
+    fn test_keyboard_mapping() {
+        let mapping = KeyboardMapping::parse(WHITE_KEYS).unwrap();
+        assert_eq!(mapping.middle_key(), 60);
+        assert_eq!(mapping.reference_key(), 69);
+        assert_close(mapping.reference_frequency(), 440.0);
+
+        let scale = ScalaScale::parse(JUST_MAJOR).unwrap();
+        let tuning = TuningSystem::scala_with_mapping(scale, mapping);
+        assert_close(hz("A4", &tuning), 440.0);
+        assert_close(hz("C4", &tuning), 264.0);
+        assert_close(hz("E4", &tuning), 330.0);
+        assert_close(hz("F4", &tuning), 352.0);
+        assert_close(hz("C5", &tuning), 528.0);
+        assert_close(hz("B2", &tuning), 123.75);
+
+        // Silent keys keep equal temperament.
+        assert!(!tuning.is_mapped(&note("C#4")));
+        assert!(tuning.is_mapped(&note("D4")));
+        assert_close(hz("C#4", &tuning), 440.0 * 2f64.powf(-8.0 / 12.0));
+    }
+
+    #[test]
+    fn test_keyboard_mapping_errors() {
+        assert_eq!(
+            KeyboardMapping::parse("12\n0\n127\n60\n69\nconcert A\n"),
+            Err(TuningError::InvalidKeyboardMapping {
+                line: 6,
+                message: "expected the reference frequency".to_string()
+            })
+        );
+        assert_eq!(
+            KeyboardMapping::parse("! size\n0\n0\n127\n200\n"),
+            Err(TuningError::InvalidKeyboardMapping {
+                line: 5,
+                message: "expected the middle key from 0 to 127".to_string()
+            })
+        );
+        assert!(matches!(
+            KeyboardMapping::parse("2\n0\n127\n60\n69\n440\n2\n0\nz\n"),
+            Err(TuningError::InvalidKeyboardMapping { line: 9, .. })
+        ));
+        assert!(matches!(
+            KeyboardMapping::parse("0\n0\n127\n"),
+            Err(TuningError::InvalidKeyboardMapping { line: 0, .. })
+        ));
+        assert_eq!(
+            KeyboardMapping::parse("100000000000000000000\n0\n127\n60\n69\n440\n0\n"),
+            Err(TuningError::InvalidKeyboardMapping {
+                line: 1,
+                message: "expected the map size from 0 to 128".to_string()
+            })
+        );
+        assert!(KeyboardMapping::parse("128\n0\n127\n60\n69\n440\n0\n").is_ok());
+        for frequency in &["NaN", "inf", "-440", "0"] {
+            let text = format!("0\n0\n127\n60\n69\n{}\n0\n", frequency);
+            assert_eq!(
+                KeyboardMapping::parse(&text),
+                Err(TuningError::InvalidKeyboardMapping {
+                    line: 6,
+                    message: "expected a positive reference frequency".to_string()
+                })
+            );
+        }
+        assert!(matches!(
+            KeyboardMapping::parse("1\n0\n127\n60\n69\n440\n1e30\n0\n"),
+            Err(TuningError::InvalidKeyboardMapping { line: 7, .. })
+        ));
+        assert!(matches!(
+            KeyboardMapping::parse("1\n0\n127\n60\n69\n440\n0\n99999999999\n"),
+            Err(TuningError::InvalidKeyboardMapping { line: 8, .. })
+        ));
+        assert!(matches!(
+            KeyboardMapping::load("does/not/exist.kbm"),
+            Err(TuningError::Io(_))
+        ));
+    }
+}